@@ -0,0 +1,109 @@
+// Generated from src/table_structs.rs, run `UPDATE_BINDINGS=1 cargo test` to update.
+
+export type Table = { key: string, title: string, position: number, color: Color, description?: string | null, titleLocals?: Array<Local> | null, descriptionLocals?: Array<Local> | null, schema: Schema, };
+
+export type Schema = { partitions?: Array<Partition>, levels?: Array<Level>, identifiers?: Array<Identifier>, classifications?: Array<Classification>, fields?: Array<Field>, formulas?: Array<Formula>, matrix: Matrix, conditionalFormattings?: Array<ConditionalFormatting>, sections?: Array<Section>, screens?: Array<Screen>, };
+
+export type Partition = { key: string, title: string, position: number, description?: string | null, titleLocals?: Array<Local> | null, descriptionLocals?: Array<Local> | null, metadata?: Array<Metadata> | null, };
+
+export type Level = { key: LevelKey, index: number, title: string, description?: string | null, titleLocals?: Array<Local> | null, descriptionLocals?: Array<Local> | null, metadata?: Array<Metadata> | null, };
+
+export type Identifier = { key: IdentifierKey, index: number, level: LevelKey, title: string, description?: string | null, titleLocals?: Array<Local> | null, descriptionLocals?: Array<Local> | null, metadata?: Array<Metadata> | null, };
+
+export type Classification = { key: ClassificationKey, title: string, description?: string | null, titleLocals?: Array<Local> | null, descriptionLocals?: Array<Local> | null, metadata?: Array<Metadata> | null, categories?: Array<Category>, };
+
+export type Category = { key: CategoryKey, parent?: CategoryKey | null, title: string, description?: string | null, titleLocals?: Array<Local> | null, descriptionLocals?: Array<Local> | null, metadata?: Array<Metadata> | null, };
+
+export type DataType = "SINGLE-LINE-TEXT" | "LONG-TEXT" | "HTML-TEXT" | "NUMBER" | "SINGLE-SELECT" | "MULTIPLE-SELECT" | "MULTIPLE-SELECT-QUANTIFIED" | "MULTIPLE-SELECT-QUANTIFIED-WITH-COMMENTS" | "DATE" | "DATE-TIME" | "IMAGE" | "ATTACHMENT";
+
+export type Field = { key: FieldKey, level: LevelKey, type: DataType, title: string, description?: string | null, titleLocals?: Array<Local> | null, descriptionLocals?: Array<Local> | null, metadata?: Array<Metadata> | null, prefix?: string | null, suffix?: string | null, precision?: number | null, suffixes?: Array<Suffix> | null, options?: Array<SelectOption> | null, };
+
+export type SelectOption = { key: OptionKey, title: string, description?: string | null, titleLocals?: Array<Local> | null, descriptionLocals?: Array<Local> | null, color?: Color | null, metadata?: Array<Metadata> | null, };
+
+export type Suffix = { key: SuffixKey, title: string, default?: boolean | null, description?: string | null, titleLocals?: Array<Local> | null, descriptionLocals?: Array<Local> | null, metadata?: Array<Metadata> | null, };
+
+export type Matrix = { common?: Array<MatrixField>, specifics?: Array<MatrixSpecific>, };
+
+export type MatrixField = { key: FieldKey, };
+
+export type MatrixSpecific = { classification: ClassificationKey, category: CategoryKey, fields?: Array<MatrixField>, };
+
+export type ConditionalFormatting = { key: ConditionalFormattingKey, level: LevelKey, title: string, description?: string | null, titleLocals?: Array<Local> | null, descriptionLocals?: Array<Local> | null, metadata?: Array<Metadata> | null, defaultStatus: DefaultStatus, statuses?: Array<Status>, };
+
+export type DefaultStatus = { key: string, title: string, color: Color, description?: string | null, titleLocals?: Array<Local> | null, descriptionLocals?: Array<Local> | null, metadata?: Array<Metadata> | null, };
+
+export type Status = { key: string, title: string, description?: string | null, titleLocals?: Array<Local> | null, descriptionLocals?: Array<Local> | null, metadata?: Array<Metadata> | null, color: Color, priority: number, rules: Rules, };
+
+export type Rules = { common?: Array<CommonAttributeRules>, specifics?: Array<SpecificRules>, };
+
+export type AttributeType = "Identifier" | "Classification" | "Field";
+
+export type CommonAttributeRules = { attributeType: AttributeType, key: string, controls?: Array<Control>, };
+
+export type SpecificRules = { classification: ClassificationKey, category: CategoryKey, attributes?: Array<SpecificAttributeRules>, };
+
+export type SpecificAttributeRules = { key: FieldKey, controls?: Array<Control>, };
+
+export type Control = { "type": "Rule-Required" } | { "type": "Rule-Is-Leaf" } | { "type": "Rule-Min-Length", min: number, } | { "type": "Rule-Max-Length", max: number, } | { "type": "Rule-Regex", regex: string, } | { "type": "Rule-Barcode", barcodeType: BarcodeType, } | { "type": "Rule-Less-Than", value: number, } | { "type": "Rule-Greater-Than", value: number, } | { "type": "Rule-Less-Than-Or-Equal", value: number, } | { "type": "Rule-Greater-Than-Or-Equal", value: number, } | { "type": "Rule-Decimal-Places", precision: number, } | { "type": "Rule-Min-Width-Px", min: number, } | { "type": "Rule-Max-Width-Px", max: number, } | { "type": "Rule-Min-Height-Px", min: number, } | { "type": "Rule-Max-Height-Px", max: number, } | { "type": "Rule-Max-Size-Kb", max: number, } | { "type": "Rule-Extension", extension: string, } | { "type": "Rule-Color-Space", name: string, } | { "type": "Rule-Color-Profile", name: string, } | { "type": "Rule-Min-Values", min: number, } | { "type": "Rule-Max-Values", max: number, } | { "type": "Rule-Must-Be-Greater-Than-Another-Field", field: FieldKey, } | { "type": "Rule-Must-Be-Greater-Than-Or-Equal-Another-Field", field: FieldKey, } | { "type": "Rule-Must-Be-Less-Than-Another-Field", field: FieldKey, } | { "type": "Rule-Must-Be-Less-Than-Or-Equal-Another-Field", field: FieldKey, } | { "type": "Rule-Required-If-Another-Field-Is-Not-Empty", field: FieldKey, } | { "type": "Rule-Required-If-Another-Field-Has-Options", field: FieldKey, options?: Array<OptionRule>, } | { "type": "Rule-Required-If-Another-Field-Is-Greater-Than", field: FieldKey, value: string, } | { "type": "Rule-Required-If-Another-Field-Is-Greater-Than-Or-Equal", field: FieldKey, value: string, } | { "type": "Rule-Required-If-Another-Field-Is-Less-Than", field: FieldKey, value: string, } | { "type": "Rule-Required-If-Another-Field-Is-Less-Than-Or-Equal", field: FieldKey, value: string, } | { "type": "Rule-Required-If-Another-Field-Is-Equal-To", field: FieldKey, value: string, } | { "type": "Rule-Condition", key: string, conditionGroups?: Array<ConditionGroup>, title: string, titleLocals?: Array<Local> | null, };
+
+export type OptionRule = { key: OptionKey, };
+
+export type ConditionGroup = { conditions?: Array<Condition>, };
+
+export type Condition = { "type": "EMPTY", source: string, } | { "type": "NOT_EMPTY", source: string, } | { "type": "CONTAINS", source: string, value: string, } | { "type": "EQUALS", source: string, value: string, useSuffix?: UseSuffix | null, } | { "type": "NOT_EQUALS", source: string, value: string, } | { "type": "STARTS_WITH", source: string, value: string, } | { "type": "ENDS_WITH", source: string, value: string, } | { "type": "LESS_THAN", source: string, value: number, } | { "type": "LESS_THAN_OR_EQUAL", source: string, value: number, } | { "type": "GREATER_THAN", source: string, value: number, } | { "type": "GREATER_THAN_OR_EQUAL", source: string, value: number, } | { "type": "IN", source: string, values?: Array<string>, } | { "type": "NOT_IN", source: string, values?: Array<string>, };
+
+export type UseSuffix = "VALUE" | "SUFFIX_KEY" | "SUFFIX";
+
+export type Formula = { attributeType: AttributeType, key: string, rules?: Array<Rule>, };
+
+export type Rule = { priority: number, conditions?: Array<ConditionGroup>, action: Action, };
+
+export type Action = { "type": "SET_TEXT", trimSpaces: boolean, value: string, } | { "type": "SET_NUMBER", precision: number, round: Rounding, value: string, } | { "type": "SET_SELECTABLE_OPTIONS", values?: Array<string>, };
+
+export type Section = { key: SectionKey, title: string, description?: string | null, titleLocals?: Array<Local> | null, descriptionLocals?: Array<Local> | null, metadata?: Array<Metadata> | null, };
+
+export type Screen = { key: string, level: LevelKey, position: number, title: string, description?: string | null, titleLocals?: Array<Local> | null, descriptionLocals?: Array<Local> | null, metadata?: Array<Metadata> | null, grid: ScreenGrid, };
+
+export type ScreenGrid = { lineHeight: LineHeight, common?: Array<CommonSection>, specifics?: Array<GridSpecific>, };
+
+export type CommonSection = { key: SectionKey, position: number, columns?: Array<CommonColumn>, };
+
+export type CommonColumn = { "type": "Column-Identifier" } & Column<IdentifierKey> | { "type": "Column-Classification" } & Column<ClassificationKey> | { "type": "Column-Conditional-Formatting" } & Column<ConditionalFormattingKey> | { "type": "Column-Field" } & Column<FieldKey>;
+
+export type GridSpecific = { classification: ClassificationKey, category: CategoryKey, sections?: Array<SpecificSection>, };
+
+export type SpecificSection = { key: SectionKey, position: number, columns?: Array<Column<FieldKey>>, };
+
+export type Column<K> = { key: K, position: number, width?: ColumnWidth | null, readOnly?: boolean | null, fixed?: boolean | null, };
+
+export type Local = { lang: string, value: string, };
+
+export type Metadata = { key: string, value: string, };
+
+export type Color = "NONE" | "GREY" | "BLUE" | "GREEN" | "YELLOW" | "ORANGE" | "RED" | "PINK" | "PURPLE" | "BROWN" | (string & {});
+
+export type LineHeight = "SHORT" | "MEDIUM" | "TALL" | "EXTRA_TALL" | (string & {});
+
+export type ColumnWidth = "SMALL" | "MEDIUM" | "LARGE" | (string & {});
+
+export type Rounding = "UP" | "DOWN" | "CEILING" | "FLOOR" | "HALF_UP" | "HALF_DOWN" | "HALF_EVEN" | (string & {});
+
+export type BarcodeType = "EAN8" | "EAN13" | "UPCA" | "GTIN14" | (string & {});
+
+export type LevelKey = string;
+
+export type IdentifierKey = string;
+
+export type ClassificationKey = string;
+
+export type CategoryKey = string;
+
+export type FieldKey = string;
+
+export type OptionKey = string;
+
+export type SuffixKey = string;
+
+export type ConditionalFormattingKey = string;
+
+export type SectionKey = string;
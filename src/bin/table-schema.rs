@@ -0,0 +1,450 @@
+use std::{env, error::Error, fs, path::Path, process::ExitCode};
+
+use table_schema_core::{
+    table_diff::diff,
+    table_documentation::document,
+    table_effective::effective_schema,
+    table_json::{from_json, to_json},
+    table_lint::{suppressions, LintConfig, Linter, Severity},
+    table_locals::LangChain,
+    table_matrix::{coverage, pivot, Inheritance},
+    table_screens::ScreenOptions,
+    table_spreadsheet::{from_sheets, from_xlsx, to_sheets, to_xlsx, Sheet},
+    table_structs::{CategoryRef, Table},
+    table_validation::TableValidation,
+    table_xml_parser::TableXmlParser,
+    table_xml_writer::TableXmlWriter,
+};
+
+const USAGE: &str = "Usage: table-schema <command> [options]
+
+Commands:
+  validate <file>                Parse and validate a table schema
+  fmt <file> [--check] [--normalize-positions]
+                                 Rewrite a table schema in its canonical form, optionally with the
+                                 screen sections and columns renumbered from 1
+  convert <input> <output>       Convert a table schema from xml to json or from json to xml
+  diff <old> <new> [--json]      Show the differences between two table schemas
+  doc <file> <output> [--lang=fra,eng]
+                                 Write the data dictionary of a table schema in markdown or html
+  export-sheets <file> <output>  Export the fields and categories to a xlsx file or a directory of csv files
+  import-sheets <file> <input>   Merge edited sheets into a table schema and show the changes
+  matrix <file> <output> [--inherit]
+                                 Write the categories x fields pivot to csv or xlsx and show the coverage
+  effective <file> <classification> <category> [--inherit]
+                                 Print the fields, rules and screen sections of a category as json
+  lint <file> [--config=lint.toml] [--json]
+                                 Check a table schema against the validation and house-style rules
+  stats <file> [--json]          Show entity counts and complexity metrics of a table schema
+  screens <file> <output> [--line-height=SHORT] [--width=SMALL]
+                                 Add the missing default \"All properties\" screen of each level
+
+fmt, import-sheets and screens refuse xml files with lint-disable comments, which rewriting would drop.
+
+Exit codes: 0 on success, 1 when errors or differences are found, 2 on usage or io errors.";
+
+// Flags accepted by each command, those ending with '=' take a value
+const FLAGS: &[(&str, &[&str])] = &[
+    ("validate", &[]),
+    ("fmt", &["--check", "--normalize-positions"]),
+    ("convert", &[]),
+    ("diff", &["--json"]),
+    ("doc", &["--lang="]),
+    ("export-sheets", &[]),
+    ("import-sheets", &[]),
+    ("matrix", &["--inherit"]),
+    ("effective", &["--inherit"]),
+    ("lint", &["--config=", "--json"]),
+    ("stats", &["--json"]),
+    ("screens", &["--line-height=", "--width="]),
+];
+
+// Outcome of a command, turned into the exit code
+enum Outcome {
+    Success,
+    Failure,
+}
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+    match run(&args) {
+        Ok(Outcome::Success) => ExitCode::SUCCESS,
+        Ok(Outcome::Failure) => ExitCode::from(1),
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<Outcome, Box<dyn Error>> {
+    let flags = args
+        .iter()
+        .filter(|arg| arg.starts_with("--"))
+        .map(|arg| arg.as_str())
+        .collect::<Vec<&str>>();
+    let params = args
+        .iter()
+        .filter(|arg| !arg.starts_with("--"))
+        .map(|arg| arg.as_str())
+        .collect::<Vec<&str>>();
+    if flags.contains(&"--help") {
+        println!("{}", USAGE);
+        return Ok(Outcome::Success);
+    }
+    // A mistyped flag must not be ignored, fmt would rewrite the file instead of checking it
+    if let Some((command, accepted)) = params
+        .first()
+        .and_then(|command| FLAGS.iter().find(|(name, _)| name == command))
+    {
+        if let Some(flag) = flags.iter().find(|flag| {
+            !accepted
+                .iter()
+                .any(|accepted| match accepted.ends_with('=') {
+                    true => flag.starts_with(accepted),
+                    false => flag == &accepted,
+                })
+        }) {
+            return Err(format!("unknown option '{}' for {}.\n\n{}", flag, command, USAGE).into());
+        }
+    }
+    match params.as_slice() {
+        ["validate", path] => validate(path),
+        ["fmt", path] => format(
+            path,
+            flags.contains(&"--check"),
+            flags.contains(&"--normalize-positions"),
+        ),
+        ["convert", input, output] => convert(input, output),
+        ["diff", old, new] => compare(old, new, flags.contains(&"--json")),
+        ["doc", path, output] => {
+            let langs = flags
+                .iter()
+                .find_map(|flag| flag.strip_prefix("--lang="))
+                .map(|langs| langs.split(',').collect::<Vec<&str>>())
+                .unwrap_or_default();
+            documentation(path, output, &LangChain::new(&langs))
+        }
+        ["export-sheets", path, output] => export_sheets(path, output),
+        ["import-sheets", path, input] => import_sheets(path, input),
+        ["matrix", path, output] => {
+            let inheritance = match flags.contains(&"--inherit") {
+                true => Inheritance::Parents,
+                false => Inheritance::None,
+            };
+            matrix(path, output, inheritance)
+        }
+        ["effective", path, classification, category] => {
+            let inheritance = match flags.contains(&"--inherit") {
+                true => Inheritance::Parents,
+                false => Inheritance::None,
+            };
+            let category = CategoryRef::new((*classification).into(), (*category).into());
+            effective(path, &category, inheritance)
+        }
+        ["lint", path] => {
+            let config = match flags.iter().find_map(|flag| flag.strip_prefix("--config=")) {
+                Some(config) => LintConfig::read(config)?,
+                None => LintConfig::default(),
+            };
+            lint(path, config, flags.contains(&"--json"))
+        }
+        ["stats", path] => stats(path, flags.contains(&"--json")),
+        ["screens", path, output] => {
+            let mut options = ScreenOptions::default();
+            for flag in flags.iter() {
+                if let Some(line_height) = flag.strip_prefix("--line-height=") {
+                    options.line_height = line_height.into();
+                }
+                if let Some(width) = flag.strip_prefix("--width=") {
+                    options.field_width = Some(width.into());
+                }
+            }
+            screens(path, output, &options)
+        }
+        _ => Err(format!("invalid arguments.\n\n{}", USAGE).into()),
+    }
+}
+
+fn is_json(path: &str) -> bool {
+    has_extension(path, "json")
+}
+
+fn has_extension(path: &str, expected: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case(expected))
+}
+
+// Reads a table from a xml or json file, xml parser logs are printed on stderr
+fn read_table(path: &str) -> Result<(Table, bool), Box<dyn Error>> {
+    if is_json(path) {
+        return Ok((from_json(&fs::read_to_string(path)?)?, true));
+    }
+    let table_xml_parser = TableXmlParser::read(path)?;
+    for warning in table_xml_parser.warnings.iter() {
+        eprintln!("warning[{}]: {}", warning.code, warning.message);
+    }
+    for error in table_xml_parser.errors.iter() {
+        eprintln!("error[{}]: {}", error.code, error.message);
+    }
+    Ok((table_xml_parser.table, table_xml_parser.errors.is_empty()))
+}
+
+// The writer drops the xml comments, files with lint-disable comments are not rewritten so
+// that their suppressions are kept
+fn refuse_suppressions(path: &str) -> Result<(), Box<dyn Error>> {
+    if !is_json(path) && !suppressions(&fs::read_to_string(path)?)?.is_empty() {
+        return Err(format!(
+            "{} has lint-disable comments, they would be lost by rewriting the file.",
+            path
+        )
+        .into());
+    }
+    Ok(())
+}
+
+fn write_table(table: &Table, path: &str) -> Result<(), Box<dyn Error>> {
+    if is_json(path) {
+        fs::write(path, to_json(table)?)?;
+    } else {
+        fs::write(path, TableXmlWriter::write_to_string(table)?)?;
+    }
+    Ok(())
+}
+
+fn validate(path: &str) -> Result<Outcome, Box<dyn Error>> {
+    let (table, parsed) = read_table(path)?;
+    let mut table_validation = TableValidation::new();
+    table_validation.validate(&table)?;
+    for warning in table_validation.warnings.iter() {
+        eprintln!(
+            "warning[{}]: {} ({})",
+            warning.code(),
+            warning.message(),
+            warning.xpath()
+        );
+    }
+    for error in table_validation.errors.iter() {
+        eprintln!(
+            "error[{}]: {} ({})",
+            error.code(),
+            error.message(),
+            error.xpath()
+        );
+    }
+    if parsed && table_validation.errors.is_empty() {
+        println!("{}: valid", path);
+        Ok(Outcome::Success)
+    } else {
+        Ok(Outcome::Failure)
+    }
+}
+
+fn format(path: &str, check: bool, normalize_positions: bool) -> Result<Outcome, Box<dyn Error>> {
+    refuse_suppressions(path)?;
+    let (mut table, parsed) = read_table(path)?;
+    if !parsed {
+        return Ok(Outcome::Failure);
+    }
+    if normalize_positions {
+        table.normalize_positions();
+    }
+    let formatted = if is_json(path) {
+        to_json(&table)?
+    } else {
+        TableXmlWriter::write_to_string(&table)?
+    };
+    if fs::read_to_string(path)? == formatted {
+        return Ok(Outcome::Success);
+    }
+    if check {
+        println!("{}: not formatted", path);
+        return Ok(Outcome::Failure);
+    }
+    fs::write(path, formatted)?;
+    Ok(Outcome::Success)
+}
+
+fn convert(input: &str, output: &str) -> Result<Outcome, Box<dyn Error>> {
+    let (table, parsed) = read_table(input)?;
+    if !parsed {
+        return Ok(Outcome::Failure);
+    }
+    write_table(&table, output)?;
+    Ok(Outcome::Success)
+}
+
+fn compare(old: &str, new: &str, json: bool) -> Result<Outcome, Box<dyn Error>> {
+    let (old_table, _) = read_table(old)?;
+    let (new_table, _) = read_table(new)?;
+    let schema_diff = diff(&old_table, &new_table);
+    if json {
+        println!("{}", schema_diff.to_json()?);
+    } else {
+        print!("{}", schema_diff.to_text());
+    }
+    if schema_diff.is_empty() {
+        Ok(Outcome::Success)
+    } else {
+        Ok(Outcome::Failure)
+    }
+}
+
+fn documentation(path: &str, output: &str, chain: &LangChain) -> Result<Outcome, Box<dyn Error>> {
+    let (table, parsed) = read_table(path)?;
+    if !parsed {
+        return Ok(Outcome::Failure);
+    }
+    let documentation = document(&table, chain);
+    if has_extension(output, "html") || has_extension(output, "htm") {
+        fs::write(output, documentation.to_html())?;
+    } else {
+        fs::write(output, documentation.to_markdown())?;
+    }
+    Ok(Outcome::Success)
+}
+
+// Sheets are written to a xlsx workbook, or to {sheet}.csv files in a directory
+fn export_sheets(path: &str, output: &str) -> Result<Outcome, Box<dyn Error>> {
+    let (table, parsed) = read_table(path)?;
+    if !parsed {
+        return Ok(Outcome::Failure);
+    }
+    let sheets = to_sheets(&table);
+    if has_extension(output, "xlsx") {
+        fs::write(output, to_xlsx(&sheets)?)?;
+    } else {
+        fs::create_dir_all(output)?;
+        for sheet in sheets.iter() {
+            fs::write(
+                Path::new(output).join(format!("{}.csv", sheet.name)),
+                sheet.to_csv(),
+            )?;
+        }
+    }
+    Ok(Outcome::Success)
+}
+
+fn import_sheets(path: &str, input: &str) -> Result<Outcome, Box<dyn Error>> {
+    refuse_suppressions(path)?;
+    let (mut table, parsed) = read_table(path)?;
+    if !parsed {
+        return Ok(Outcome::Failure);
+    }
+    let sheets = if has_extension(input, "xlsx") {
+        from_xlsx(&fs::read(input)?)?
+    } else {
+        let mut sheets = vec![];
+        for entry in fs::read_dir(input)? {
+            let entry = entry?.path();
+            if let (Some(name), true) = (
+                entry.file_stem().and_then(|name| name.to_str()),
+                has_extension(&entry.to_string_lossy(), "csv"),
+            ) {
+                sheets.push(Sheet::from_csv(name, &fs::read_to_string(&entry)?)?);
+            }
+        }
+        sheets
+    };
+    let schema_diff = from_sheets(&mut table, &sheets)?;
+    write_table(&table, path)?;
+    print!("{}", schema_diff.to_text());
+    Ok(Outcome::Success)
+}
+
+fn matrix(path: &str, output: &str, inheritance: Inheritance) -> Result<Outcome, Box<dyn Error>> {
+    let (table, parsed) = read_table(path)?;
+    if !parsed {
+        return Ok(Outcome::Failure);
+    }
+    let sheet = pivot(&table.schema, inheritance);
+    if has_extension(output, "xlsx") {
+        fs::write(output, to_xlsx(&[sheet])?)?;
+    } else {
+        fs::write(output, sheet.to_csv())?;
+    }
+    let matrix_coverage = coverage(&table.schema, inheritance);
+    for category in matrix_coverage.categories_without_specific_fields.iter() {
+        println!(
+            "category without specific fields: {}/{}",
+            category.classification, category.category
+        );
+    }
+    for field in matrix_coverage.unused_fields.iter() {
+        println!("field not in the matrix: {}", field);
+    }
+    Ok(Outcome::Success)
+}
+
+fn effective(
+    path: &str,
+    category: &CategoryRef,
+    inheritance: Inheritance,
+) -> Result<Outcome, Box<dyn Error>> {
+    let (table, parsed) = read_table(path)?;
+    if !parsed {
+        return Ok(Outcome::Failure);
+    }
+    let effective = effective_schema(&table.schema, category, inheritance)?;
+    println!("{}", serde_json::to_string_pretty(&effective)?);
+    Ok(Outcome::Success)
+}
+
+fn screens(path: &str, output: &str, options: &ScreenOptions) -> Result<Outcome, Box<dyn Error>> {
+    refuse_suppressions(path)?;
+    let (mut table, parsed) = read_table(path)?;
+    if !parsed {
+        return Ok(Outcome::Failure);
+    }
+    for key in table.add_default_screens(options) {
+        println!("screen added: {}", key);
+    }
+    write_table(&table, output)?;
+    Ok(Outcome::Success)
+}
+
+fn stats(path: &str, json: bool) -> Result<Outcome, Box<dyn Error>> {
+    let (table, parsed) = read_table(path)?;
+    if !parsed {
+        return Ok(Outcome::Failure);
+    }
+    let stats = table.stats();
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+    } else {
+        print!("{}", stats.to_text());
+    }
+    Ok(Outcome::Success)
+}
+
+// Errors make the command fail, warnings and infos are only reported
+fn lint(path: &str, config: LintConfig, json: bool) -> Result<Outcome, Box<dyn Error>> {
+    let (table, parsed) = read_table(path)?;
+    if !parsed {
+        return Ok(Outcome::Failure);
+    }
+    // Suppressions are xml comments
+    let suppressions = match is_json(path) {
+        true => vec![],
+        false => suppressions(&fs::read_to_string(path)?)?,
+    };
+    let diagnostics = Linter::new(config).lint(&table, &suppressions)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&diagnostics)?);
+    } else {
+        for diagnostic in diagnostics.iter() {
+            println!(
+                "{}[{}]: {} ({})",
+                diagnostic.severity, diagnostic.rule, diagnostic.message, diagnostic.xpath
+            );
+        }
+    }
+    match diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity == Severity::Error)
+    {
+        true => Ok(Outcome::Failure),
+        false => Ok(Outcome::Success),
+    }
+}
@@ -1,10 +1,27 @@
-mod table_structs;
-mod table_validation;
-mod table_xml_parser;
-mod table_xml_writer;
+pub mod table_diagnostics;
+pub mod table_diff;
+pub mod table_documentation;
+pub mod table_effective;
+pub mod table_json;
+pub mod table_lint;
+pub mod table_locals;
+pub mod table_matrix;
+pub mod table_merge;
+pub mod table_patch;
+pub mod table_record_schema;
+pub mod table_refactoring;
+pub mod table_screens;
+pub mod table_spreadsheet;
+pub mod table_stats;
+pub mod table_structs;
+pub mod table_translation;
+pub mod table_validation;
+pub mod table_visitor;
+pub mod table_xml_parser;
+pub mod table_xml_writer;
 mod utils;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 #[cfg(test)]
 mod tests;
-
-    
\ No newline at end of file
@@ -0,0 +1,68 @@
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    table_structs::Table,
+    table_validation::{LogError, TableValidation},
+    table_xml_parser::{Log, TableXmlParser},
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub code: String,
+    pub message: String,
+    pub xpath: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParseResult {
+    pub table: Table,
+    pub warnings: Vec<Diagnostic>,
+    pub errors: Vec<Diagnostic>,
+}
+
+// Result of parseTable in the wasm bindings. The parser logs have no position in the file, their xpath is null.
+pub fn parse(xml: &str) -> Result<ParseResult, Box<dyn Error>> {
+    let table_xml_parser = TableXmlParser::read_from_str(xml)?;
+    let diagnostics = |logs: &[Log]| {
+        logs.iter()
+            .map(|log| Diagnostic {
+                code: log.code.to_owned(),
+                message: log.message.to_owned(),
+                xpath: None,
+            })
+            .collect::<Vec<Diagnostic>>()
+    };
+    Ok(ParseResult {
+        warnings: diagnostics(&table_xml_parser.warnings),
+        errors: diagnostics(&table_xml_parser.errors),
+        table: table_xml_parser.table,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationResult {
+    pub warnings: Vec<Diagnostic>,
+    pub errors: Vec<Diagnostic>,
+}
+
+// Result of validateTable in the wasm bindings
+pub fn validate(table: &Table) -> Result<ValidationResult, Box<dyn Error>> {
+    let mut table_validation = TableValidation::new();
+    table_validation.validate(table)?;
+    let diagnostics = |errors: &[LogError]| {
+        errors
+            .iter()
+            .map(|error| Diagnostic {
+                code: error.code().to_owned(),
+                message: error.message().to_owned(),
+                xpath: Some(error.xpath().to_owned()),
+            })
+            .collect::<Vec<Diagnostic>>()
+    };
+    Ok(ValidationResult {
+        warnings: diagnostics(&table_validation.warnings),
+        errors: diagnostics(&table_validation.errors),
+    })
+}
@@ -0,0 +1,282 @@
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::table_structs::Table;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+    Moved,
+}
+
+// One difference between two tables. The path addresses items of collections by
+// their key (e.g. schema.fields[COLOR].options[RED].title), not by their position.
+// A reordered list is one Moved change whose values are the keys in their old and
+// new order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaChange {
+    pub kind: ChangeKind,
+    pub path: String,
+    pub old_value: Option<Value>,
+    pub new_value: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaDiff {
+    pub changes: Vec<SchemaChange>,
+}
+impl SchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for change in self.changes.iter() {
+            let line = match change.kind {
+                ChangeKind::Added => match &change.new_value {
+                    Some(value) if is_scalar(value) => {
+                        format!("+ {} added: {}", change.path, value)
+                    }
+                    _ => format!("+ {} added", change.path),
+                },
+                ChangeKind::Removed => match &change.old_value {
+                    Some(value) if is_scalar(value) => {
+                        format!("- {} removed: {}", change.path, value)
+                    }
+                    _ => format!("- {} removed", change.path),
+                },
+                ChangeKind::Modified => match (&change.old_value, &change.new_value) {
+                    (Some(old_value), Some(new_value))
+                        if is_scalar(old_value) && is_scalar(new_value) =>
+                    {
+                        format!("~ {} changed: {} -> {}", change.path, old_value, new_value)
+                    }
+                    _ => format!("~ {} changed", change.path),
+                },
+                ChangeKind::Moved => format!(
+                    "> {} moved: {} -> {}",
+                    change.path,
+                    keys(&change.old_value),
+                    keys(&change.new_value)
+                ),
+            };
+            text.push_str(&line);
+            text.push('\n');
+        }
+        text
+    }
+    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+pub fn diff(old: &Table, new: &Table) -> SchemaDiff {
+    let mut changes = vec![];
+    // Tables are always serializable, the derives only contain strings, numbers and lists
+    let old_value = serde_json::to_value(old).unwrap_or(Value::Null);
+    let new_value = serde_json::to_value(new).unwrap_or(Value::Null);
+    diff_value("", &old_value, &new_value, &mut changes);
+    SchemaDiff { changes }
+}
+
+fn keys(value: &Option<Value>) -> String {
+    match value {
+        Some(Value::Array(keys)) => keys
+            .iter()
+            .filter_map(|key| key.as_str())
+            .collect::<Vec<&str>>()
+            .join(", "),
+        _ => String::new(),
+    }
+}
+
+fn is_scalar(value: &Value) -> bool {
+    !value.is_object() && !value.is_array()
+}
+
+pub(crate) fn join(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+// Controls, conditions, actions and columns are tagged with their type
+pub(crate) fn tag(map: &Map<String, Value>) -> Option<&str> {
+    match map.contains_key("key") {
+        true => None,
+        false => map.get("type").and_then(|tag| tag.as_str()),
+    }
+}
+
+// Identity of an item of a list, None when the item can only be matched by position
+pub(crate) fn identity(value: &Value) -> Option<String> {
+    match value {
+        Value::String(value) => Some(value.to_owned()),
+        Value::Object(map) => {
+            if let Some(Value::String(key)) = map.get("key") {
+                return Some(key.to_owned());
+            }
+            if let Some(Value::String(lang)) = map.get("lang") {
+                return Some(lang.to_owned());
+            }
+            if let (Some(Value::String(classification)), Some(Value::String(category))) =
+                (map.get("classification"), map.get("category"))
+            {
+                return Some(format!("{}/{}", classification, category));
+            }
+            if let Some(priority) = map.get("priority") {
+                return Some(priority.to_string());
+            }
+            // Conditions are only matched by position
+            if map.contains_key("source") {
+                return None;
+            }
+            tag(map).map(|tag| tag.to_owned())
+        }
+        _ => None,
+    }
+}
+
+// Locals are compared as a whole: titleLocals[fra] changed
+fn is_local(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => map.contains_key("lang"),
+        _ => false,
+    }
+}
+
+fn diff_value(path: &str, old: &Value, new: &Value, changes: &mut Vec<SchemaChange>) {
+    if old == new {
+        return;
+    }
+    match (old, new) {
+        (Value::Null, _) => changes.push(SchemaChange {
+            kind: ChangeKind::Added,
+            path: path.to_owned(),
+            old_value: None,
+            new_value: Some(new.to_owned()),
+        }),
+        (_, Value::Null) => changes.push(SchemaChange {
+            kind: ChangeKind::Removed,
+            path: path.to_owned(),
+            old_value: Some(old.to_owned()),
+            new_value: None,
+        }),
+        (Value::Object(old_map), Value::Object(new_map)) => match (tag(old_map), tag(new_map)) {
+            (Some(old_tag), Some(new_tag)) if old_tag != new_tag => {
+                modified(path, old, new, changes)
+            }
+            _ => diff_object(path, old_map, new_map, changes),
+        },
+        (Value::Array(old_items), Value::Array(new_items)) => {
+            diff_array(path, old_items, new_items, changes)
+        }
+        _ => modified(path, old, new, changes),
+    }
+}
+
+fn modified(path: &str, old: &Value, new: &Value, changes: &mut Vec<SchemaChange>) {
+    changes.push(SchemaChange {
+        kind: ChangeKind::Modified,
+        path: path.to_owned(),
+        old_value: Some(old.to_owned()),
+        new_value: Some(new.to_owned()),
+    })
+}
+
+fn diff_object(
+    path: &str,
+    old: &Map<String, Value>,
+    new: &Map<String, Value>,
+    changes: &mut Vec<SchemaChange>,
+) {
+    for (name, old_value) in old.iter() {
+        let new_value = new.get(name).unwrap_or(&Value::Null);
+        diff_value(&join(path, name), old_value, new_value, changes);
+    }
+    for (name, new_value) in new.iter().filter(|(name, _)| !old.contains_key(*name)) {
+        diff_value(&join(path, name), &Value::Null, new_value, changes);
+    }
+}
+
+pub(crate) fn identities(items: &[Value]) -> Option<Vec<String>> {
+    let keys = items
+        .iter()
+        .map(identity)
+        .collect::<Option<Vec<String>>>()?;
+    let mut unique = keys.to_owned();
+    unique.sort();
+    unique.dedup();
+    if unique.len() == keys.len() {
+        Some(keys)
+    } else {
+        None
+    }
+}
+
+fn diff_array(path: &str, old: &[Value], new: &[Value], changes: &mut Vec<SchemaChange>) {
+    match (identities(old), identities(new)) {
+        (Some(old_keys), Some(new_keys)) => {
+            for (old_key, old_item) in old_keys.iter().zip(old.iter()) {
+                let item_path = format!("{}[{}]", path, old_key);
+                match new_keys.iter().position(|new_key| new_key == old_key) {
+                    Some(index) if is_local(old_item) && old_item != &new[index] => {
+                        modified(&item_path, old_item, &new[index], changes)
+                    }
+                    Some(index) => diff_value(&item_path, old_item, &new[index], changes),
+                    None => diff_value(&item_path, old_item, &Value::Null, changes),
+                }
+            }
+            for (new_key, new_item) in new_keys.iter().zip(new.iter()) {
+                if !old_keys.contains(new_key) {
+                    let item_path = format!("{}[{}]", path, new_key);
+                    diff_value(&item_path, &Value::Null, new_item, changes);
+                }
+            }
+            // Locals are not ordered
+            if old.first().is_some_and(is_local) {
+                return;
+            }
+            let old_order: Vec<&String> = old_keys
+                .iter()
+                .filter(|key| new_keys.contains(key))
+                .collect();
+            let new_order: Vec<&String> = new_keys
+                .iter()
+                .filter(|key| old_keys.contains(key))
+                .collect();
+            if old_order != new_order {
+                changes.push(SchemaChange {
+                    kind: ChangeKind::Moved,
+                    path: path.to_owned(),
+                    old_value: Some(Value::from(
+                        old_order.into_iter().cloned().collect::<Vec<String>>(),
+                    )),
+                    new_value: Some(Value::from(
+                        new_order.into_iter().cloned().collect::<Vec<String>>(),
+                    )),
+                });
+            }
+        }
+        // Lists without keys (condition groups, conditions) and lists with duplicate keys
+        // are compared by position: an item inserted before others shows up as changes
+        // of all the items after it, and a reorder as changes of the moved positions.
+        _ => {
+            for index in 0..old.len().max(new.len()) {
+                let item_path = format!("{}[{}]", path, index + 1);
+                diff_value(
+                    &item_path,
+                    old.get(index).unwrap_or(&Value::Null),
+                    new.get(index).unwrap_or(&Value::Null),
+                    changes,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,1020 @@
+use std::collections::HashSet;
+
+use quick_xml::escape::escape;
+
+use crate::{
+    table_locals::{LangChain, Localized},
+    table_structs::{
+        Action, AttributeType, CategoryKey, CategoryRef, Classification, ClassificationKey, Column,
+        CommonColumn, Condition, ConditionGroup, ConditionalFormattingKey, Control, FieldKey,
+        Formula, IdentifierKey, LevelKey, Schema, SectionKey, Table, UseSuffix,
+    },
+};
+
+// Data dictionary of a table, built once and rendered to Markdown or HTML. Titles and
+// descriptions are taken from the locals of the lang chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Documentation {
+    title: String,
+    lang: Option<String>,
+    blocks: Vec<Block>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Block {
+    Heading(usize, Text),
+    Paragraph(Text),
+    // Items with their depth, a depth is at most one more than the depth of the previous item
+    List(Vec<(usize, Text)>),
+    Table(Vec<&'static str>, Vec<Vec<Text>>),
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Text(Vec<Span>);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Span {
+    Plain(String),
+    Code(String),
+}
+
+impl Text {
+    fn plain(mut self, value: &str) -> Self {
+        self.0.push(Span::Plain(value.to_owned()));
+        self
+    }
+    fn code(mut self, value: &str) -> Self {
+        self.0.push(Span::Code(value.to_owned()));
+        self
+    }
+    fn text(mut self, text: Text) -> Self {
+        self.0.extend(text.0);
+        self
+    }
+    fn join(texts: Vec<Text>, separator: &str) -> Self {
+        let mut joined = Text::default();
+        for (index, text) in texts.into_iter().enumerate() {
+            if index > 0 {
+                joined = joined.plain(separator);
+            }
+            joined = joined.text(text);
+        }
+        joined
+    }
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    // Unresolved references are shown by their key only
+    fn is_key(&self) -> bool {
+        matches!(self.0.as_slice(), [Span::Code(_)])
+    }
+}
+impl From<&str> for Text {
+    fn from(value: &str) -> Self {
+        Text::default().plain(value)
+    }
+}
+impl From<String> for Text {
+    fn from(value: String) -> Self {
+        Text::default().plain(&value)
+    }
+}
+
+pub fn document(table: &Table, chain: &LangChain) -> Documentation {
+    let mut documenter = Documenter {
+        schema: &table.schema,
+        chain,
+        blocks: vec![],
+    };
+    documenter.table(table);
+    Documentation {
+        title: table.title_with(chain).to_owned(),
+        lang: chain.langs().first().cloned(),
+        blocks: documenter.blocks,
+    }
+}
+
+impl Documentation {
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        for block in self.blocks.iter() {
+            match block {
+                Block::Heading(level, text) => {
+                    markdown.push_str(&format!(
+                        "{} {}\n\n",
+                        "#".repeat(*level),
+                        markdown_text(text, false)
+                    ));
+                }
+                Block::Paragraph(text) => {
+                    markdown.push_str(&format!("{}\n\n", markdown_text(text, false)));
+                }
+                Block::List(items) => {
+                    for (depth, text) in items.iter() {
+                        markdown.push_str(&format!(
+                            "{}- {}\n",
+                            "  ".repeat(*depth),
+                            markdown_text(text, false)
+                        ));
+                    }
+                    markdown.push('\n');
+                }
+                Block::Table(headers, rows) => {
+                    markdown.push_str(&format!("| {} |\n", headers.join(" | ")));
+                    markdown.push_str(&format!("|{}\n", " --- |".repeat(headers.len())));
+                    for row in rows.iter() {
+                        let cells = row
+                            .iter()
+                            .map(|text| markdown_text(text, true))
+                            .collect::<Vec<String>>();
+                        markdown.push_str(&format!("| {} |\n", cells.join(" | ")));
+                    }
+                    markdown.push('\n');
+                }
+            }
+        }
+        markdown
+    }
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<!DOCTYPE html>\n");
+        match &self.lang {
+            Some(lang) => html.push_str(&format!("<html lang=\"{}\">\n", escape(lang.as_str()))),
+            None => html.push_str("<html>\n"),
+        }
+        html.push_str(&format!(
+            "<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n",
+            escape(self.title.as_str())
+        ));
+        for block in self.blocks.iter() {
+            match block {
+                Block::Heading(level, text) => {
+                    html.push_str(&format!("<h{}>{}</h{}>\n", level, html_text(text), level));
+                }
+                Block::Paragraph(text) => {
+                    html.push_str(&format!("<p>{}</p>\n", html_text(text)));
+                }
+                Block::List(items) => {
+                    html.push_str("<ul>\n");
+                    for (index, (depth, text)) in items.iter().enumerate() {
+                        html.push_str(&format!("<li>{}", html_text(text)));
+                        let next = items.get(index + 1).map_or(0, |(depth, _)| *depth);
+                        if next > *depth {
+                            html.push_str("\n<ul>\n");
+                        } else {
+                            html.push_str("</li>\n");
+                            for _ in next..*depth {
+                                html.push_str("</ul>\n</li>\n");
+                            }
+                        }
+                    }
+                    html.push_str("</ul>\n");
+                }
+                Block::Table(headers, rows) => {
+                    html.push_str("<table>\n<thead>\n<tr>");
+                    for header in headers.iter() {
+                        html.push_str(&format!("<th>{}</th>", escape(header)));
+                    }
+                    html.push_str("</tr>\n</thead>\n<tbody>\n");
+                    for row in rows.iter() {
+                        html.push_str("<tr>");
+                        for text in row.iter() {
+                            html.push_str(&format!("<td>{}</td>", html_text(text)));
+                        }
+                        html.push_str("</tr>\n");
+                    }
+                    html.push_str("</tbody>\n</table>\n");
+                }
+            }
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+fn markdown_text(text: &Text, in_table: bool) -> String {
+    let mut markdown = String::new();
+    for span in text.0.iter() {
+        match span {
+            Span::Plain(value) => {
+                for c in value.chars() {
+                    match c {
+                        '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '|' => {
+                            markdown.push('\\');
+                            markdown.push(c);
+                        }
+                        '\n' if in_table => markdown.push(' '),
+                        _ => markdown.push(c),
+                    }
+                }
+            }
+            Span::Code(value) => {
+                let mut value = value.replace('\n', " ");
+                if in_table {
+                    value = value.replace('|', "\\|");
+                }
+                // A code span is delimited by more backticks than it contains
+                let ticks = "`".repeat(longest_backtick_run(&value) + 1);
+                let padding = if value.starts_with('`') || value.ends_with('`') {
+                    " "
+                } else {
+                    ""
+                };
+                markdown.push_str(&format!(
+                    "{}{}{}{}{}",
+                    ticks, padding, value, padding, ticks
+                ));
+            }
+        }
+    }
+    markdown
+}
+
+fn longest_backtick_run(value: &str) -> usize {
+    value
+        .split(|c| c != '`')
+        .map(|run| run.len())
+        .max()
+        .unwrap_or_default()
+}
+
+fn html_text(text: &Text) -> String {
+    text.0
+        .iter()
+        .map(|span| match span {
+            Span::Plain(value) => escape(value.as_str()).to_string(),
+            Span::Code(value) => format!("<code>{}</code>", escape(value.as_str())),
+        })
+        .collect()
+}
+
+struct Documenter<'a> {
+    schema: &'a Schema,
+    chain: &'a LangChain,
+    blocks: Vec<Block>,
+}
+
+impl Documenter<'_> {
+    fn title<L: Localized>(&self, element: &L) -> String {
+        element.title_with(self.chain).to_owned()
+    }
+    fn description<L: Localized>(&self, element: &L) -> Text {
+        element
+            .description_with(self.chain)
+            .map(Text::from)
+            .unwrap_or_default()
+    }
+    // Title followed by the key: Color (`COLOR`)
+    fn named(&self, title: &str, key: &str) -> Text {
+        Text::from(title).plain(" (").code(key).plain(")")
+    }
+    fn level(&self, key: &LevelKey) -> Text {
+        match self.schema.level(key) {
+            Some(level) => Text::from(self.title(level)),
+            None => Text::default().code(key),
+        }
+    }
+    fn field(&self, key: &FieldKey) -> Text {
+        match self.schema.field(key) {
+            Some(field) => Text::from(self.title(field)),
+            None => Text::default().code(key),
+        }
+    }
+    fn attribute(&self, attribute_type: &AttributeType, key: &str) -> Text {
+        let title = match attribute_type {
+            AttributeType::Identifier => self
+                .schema
+                .identifier(&IdentifierKey::from(key))
+                .map(|identifier| self.title(identifier)),
+            AttributeType::Classification => self
+                .schema
+                .classification(&ClassificationKey::from(key))
+                .map(|classification| self.title(classification)),
+            AttributeType::Field => self
+                .schema
+                .field(&FieldKey::from(key))
+                .map(|field| self.title(field)),
+        };
+        match title {
+            Some(title) => Text::from(title),
+            None => Text::default().code(key),
+        }
+    }
+    // Sources of conditions and templates are fields, identifiers or classifications
+    fn source(&self, key: &str) -> Text {
+        [
+            AttributeType::Field,
+            AttributeType::Identifier,
+            AttributeType::Classification,
+        ]
+        .iter()
+        .map(|attribute_type| self.attribute(attribute_type, key))
+        .find(|text| !text.is_key())
+        .unwrap_or_else(|| Text::default().code(key))
+    }
+    fn category(&self, reference: &CategoryRef) -> Text {
+        let classification = match self.schema.classification(&reference.classification) {
+            Some(classification) => Text::from(self.title(classification)),
+            None => Text::default().code(&reference.classification),
+        };
+        let category = match self.schema.category(reference) {
+            Some(category) => Text::from(self.title(category)),
+            None => Text::default().code(&reference.category),
+        };
+        classification.plain(" > ").text(category)
+    }
+
+    fn table(&mut self, table: &Table) {
+        self.blocks
+            .push(Block::Heading(1, Text::from(self.title(table))));
+        let description = self.description(table);
+        if !description.is_empty() {
+            self.blocks.push(Block::Paragraph(description));
+        }
+        self.blocks
+            .push(Block::Paragraph(Text::from("Key: ").code(&table.key)));
+        self.levels();
+        self.identifiers();
+        self.fields();
+        self.classifications();
+        self.matrix();
+        self.formulas();
+        self.conditional_formattings();
+        self.screens();
+    }
+
+    fn levels(&mut self) {
+        if self.schema.levels.is_empty() {
+            return;
+        }
+        let mut levels = self.schema.levels.iter().collect::<Vec<_>>();
+        levels.sort_by_key(|level| level.index);
+        let rows = levels
+            .iter()
+            .map(|level| {
+                vec![
+                    Text::from(self.title(*level)),
+                    Text::default().code(&level.key),
+                    self.description(*level),
+                ]
+            })
+            .collect();
+        self.blocks.push(Block::Heading(2, Text::from("Levels")));
+        self.blocks
+            .push(Block::Table(vec!["Level", "Key", "Description"], rows));
+    }
+
+    fn identifiers(&mut self) {
+        if self.schema.identifiers.is_empty() {
+            return;
+        }
+        let rows = self
+            .schema
+            .identifiers
+            .iter()
+            .map(|identifier| {
+                vec![
+                    Text::from(self.title(identifier)),
+                    Text::default().code(&identifier.key),
+                    self.level(&identifier.level),
+                    self.description(identifier),
+                ]
+            })
+            .collect();
+        self.blocks
+            .push(Block::Heading(2, Text::from("Identifiers")));
+        self.blocks.push(Block::Table(
+            vec!["Identifier", "Key", "Level", "Description"],
+            rows,
+        ));
+    }
+
+    fn fields(&mut self) {
+        if self.schema.fields.is_empty() {
+            return;
+        }
+        let rows = self
+            .schema
+            .fields
+            .iter()
+            .map(|field| {
+                let mut format = vec![];
+                if let Some(prefix) = &field.prefix {
+                    format.push(Text::from("prefix ").code(prefix));
+                }
+                if let Some(suffix) = &field.suffix {
+                    format.push(Text::from("suffix ").code(suffix));
+                }
+                if let Some(precision) = field.precision {
+                    format.push(Text::from(format!("{} decimals", precision)));
+                }
+                vec![
+                    Text::from(self.title(field)),
+                    Text::default().code(&field.key),
+                    self.level(&field.level),
+                    Text::default().code(&field.data_type.to_string()),
+                    Text::join(format, ", "),
+                    self.description(field),
+                ]
+            })
+            .collect();
+        self.blocks.push(Block::Heading(2, Text::from("Fields")));
+        self.blocks.push(Block::Table(
+            vec!["Field", "Key", "Level", "Type", "Format", "Description"],
+            rows,
+        ));
+        for field in self.schema.fields.iter() {
+            let options = field.options.as_deref().unwrap_or_default();
+            let suffixes = field.suffixes.as_deref().unwrap_or_default();
+            if options.is_empty() && suffixes.is_empty() {
+                continue;
+            }
+            self.blocks.push(Block::Heading(
+                3,
+                self.named(&self.title(field), &field.key),
+            ));
+            if !options.is_empty() {
+                let rows = options
+                    .iter()
+                    .map(|option| {
+                        vec![
+                            Text::from(self.title(option)),
+                            Text::default().code(&option.key),
+                            option
+                                .color
+                                .as_ref()
+                                .map(|color| Text::from(color.to_string()))
+                                .unwrap_or_default(),
+                            self.description(option),
+                        ]
+                    })
+                    .collect();
+                self.blocks.push(Block::Table(
+                    vec!["Option", "Key", "Color", "Description"],
+                    rows,
+                ));
+            }
+            if !suffixes.is_empty() {
+                let rows = suffixes
+                    .iter()
+                    .map(|suffix| {
+                        vec![
+                            Text::from(self.title(suffix)),
+                            Text::default().code(&suffix.key),
+                            Text::from(match suffix.default {
+                                Some(true) => "Yes",
+                                _ => "",
+                            }),
+                            self.description(suffix),
+                        ]
+                    })
+                    .collect();
+                self.blocks.push(Block::Table(
+                    vec!["Suffix", "Key", "Default", "Description"],
+                    rows,
+                ));
+            }
+        }
+    }
+
+    fn classifications(&mut self) {
+        if self.schema.classifications.is_empty() {
+            return;
+        }
+        self.blocks
+            .push(Block::Heading(2, Text::from("Classifications")));
+        for classification in self.schema.classifications.iter() {
+            self.blocks.push(Block::Heading(
+                3,
+                self.named(&self.title(classification), &classification.key),
+            ));
+            let description = self.description(classification);
+            if !description.is_empty() {
+                self.blocks.push(Block::Paragraph(description));
+            }
+            let mut items = vec![];
+            let mut visited = HashSet::new();
+            // Categories whose parent is unknown are shown as roots
+            for category in classification.categories.iter().filter(|category| {
+                category
+                    .parent
+                    .as_ref()
+                    .is_none_or(|parent| classification.category(parent).is_none())
+            }) {
+                self.category_tree(classification, &category.key, 0, &mut visited, &mut items);
+            }
+            if !items.is_empty() {
+                self.blocks.push(Block::List(items));
+            }
+        }
+    }
+    fn category_tree<'a>(
+        &self,
+        classification: &'a Classification,
+        key: &'a CategoryKey,
+        depth: usize,
+        visited: &mut HashSet<&'a CategoryKey>,
+        items: &mut Vec<(usize, Text)>,
+    ) {
+        let category = match classification.category(key) {
+            Some(category) if visited.insert(key) => category,
+            _ => return,
+        };
+        items.push((depth, self.named(&self.title(category), &category.key)));
+        for child in classification
+            .categories
+            .iter()
+            .filter(|child| child.parent.as_ref() == Some(key))
+        {
+            self.category_tree(classification, &child.key, depth + 1, visited, items);
+        }
+    }
+
+    fn matrix(&mut self) {
+        let matrix = &self.schema.matrix;
+        if matrix.common.is_empty() && matrix.specifics.is_empty() {
+            return;
+        }
+        self.blocks.push(Block::Heading(2, Text::from("Matrix")));
+        if !matrix.common.is_empty() {
+            self.blocks
+                .push(Block::Paragraph(Text::from("Fields of every category:")));
+            let items = matrix
+                .common
+                .iter()
+                .map(|field| (0, self.field(&field.key)))
+                .collect();
+            self.blocks.push(Block::List(items));
+        }
+        if !matrix.specifics.is_empty() {
+            let rows = matrix
+                .specifics
+                .iter()
+                .map(|specific| {
+                    let fields = specific
+                        .fields
+                        .iter()
+                        .map(|field| self.field(&field.key))
+                        .collect();
+                    vec![
+                        self.category(&specific.category_ref()),
+                        Text::join(fields, ", "),
+                    ]
+                })
+                .collect();
+            self.blocks
+                .push(Block::Table(vec!["Category", "Specific fields"], rows));
+        }
+    }
+
+    fn formulas(&mut self) {
+        if self.schema.formulas.is_empty() {
+            return;
+        }
+        self.blocks.push(Block::Heading(2, Text::from("Formulas")));
+        for formula in self.schema.formulas.iter() {
+            let mut title = self.attribute(&formula.attribute_type, &formula.key);
+            if !title.is_key() {
+                title = title.plain(" (").code(&formula.key).plain(")");
+            }
+            self.blocks.push(Block::Heading(3, title));
+            let mut rules = formula.rules.iter().collect::<Vec<_>>();
+            rules.sort_by_key(|rule| rule.priority);
+            let items = rules
+                .iter()
+                .map(|rule| {
+                    let action = self.action(formula, &rule.action);
+                    let sentence = if rule.conditions.is_empty() {
+                        Text::from("Always ").text(action)
+                    } else {
+                        Text::from("If ")
+                            .text(self.condition_groups(&rule.conditions))
+                            .plain(", then ")
+                            .text(action)
+                    };
+                    (0, sentence.plain("."))
+                })
+                .collect();
+            self.blocks.push(Block::List(items));
+        }
+    }
+    // Conditions of a group must all be met, one of the groups must be met
+    fn condition_groups(&self, groups: &[ConditionGroup]) -> Text {
+        let groups = groups
+            .iter()
+            .map(|group| {
+                let conditions = group
+                    .conditions
+                    .iter()
+                    .map(|condition| self.condition(condition))
+                    .collect();
+                Text::join(conditions, " and ")
+            })
+            .collect();
+        Text::join(groups, ", or ")
+    }
+    fn condition(&self, condition: &Condition) -> Text {
+        let source = self.source(condition.source());
+        let values = |values: &[String]| {
+            Text::join(
+                values
+                    .iter()
+                    .map(|value| Text::default().code(value))
+                    .collect(),
+                ", ",
+            )
+        };
+        match condition {
+            Condition::Empty { .. } => source.plain(" is empty"),
+            Condition::NotEmpty { .. } => source.plain(" is not empty"),
+            Condition::Contains { value, .. } => source.plain(" contains ").code(value),
+            Condition::Equals {
+                value, use_suffix, ..
+            } => {
+                let text = source.plain(" equals ").code(value);
+                match use_suffix {
+                    Some(UseSuffix::SuffixKey) => text.plain(" (suffix key)"),
+                    Some(UseSuffix::Suffix) => text.plain(" (suffix)"),
+                    _ => text,
+                }
+            }
+            Condition::NotEquals { value, .. } => source.plain(" does not equal ").code(value),
+            Condition::StartsWith { value, .. } => source.plain(" starts with ").code(value),
+            Condition::EndsWith { value, .. } => source.plain(" ends with ").code(value),
+            Condition::LessThan { value, .. } => source.plain(&format!(" is less than {}", value)),
+            Condition::LessThanOrEqual { value, .. } => {
+                source.plain(&format!(" is at most {}", value))
+            }
+            Condition::GreaterThan { value, .. } => {
+                source.plain(&format!(" is greater than {}", value))
+            }
+            Condition::GreaterThanOrEqual { value, .. } => {
+                source.plain(&format!(" is at least {}", value))
+            }
+            Condition::In { values: list, .. } => source.plain(" is one of ").text(values(list)),
+            Condition::NotIn { values: list, .. } => {
+                source.plain(" is none of ").text(values(list))
+            }
+        }
+    }
+    fn action(&self, formula: &Formula, action: &Action) -> Text {
+        match action {
+            Action::SetTextTemplate { trim_spaces, value } => {
+                let text = Text::from("set the text to ").code(&self.template(value));
+                match trim_spaces {
+                    true => text.plain(" with trimmed spaces"),
+                    false => text,
+                }
+            }
+            Action::SetNumberTemplate {
+                precision,
+                round,
+                value,
+            } => Text::from("set the number to ")
+                .code(&self.template(value))
+                .plain(&format!(
+                    " rounded {} to {} decimals",
+                    round.to_string().to_lowercase(),
+                    precision
+                )),
+            Action::SetSelectableOptions { values } => {
+                let field = match formula.attribute_type {
+                    AttributeType::Field => {
+                        self.schema.field(&FieldKey::from(formula.key.as_str()))
+                    }
+                    _ => None,
+                };
+                let options = values
+                    .iter()
+                    .map(|value| {
+                        match field.and_then(|field| field.option(&value.as_str().into())) {
+                            Some(option) => Text::from(self.title(option)),
+                            None => Text::default().code(value),
+                        }
+                    })
+                    .collect();
+                Text::from("restrict the options to ").text(Text::join(options, ", "))
+            }
+        }
+    }
+    // Placeholders {{source("KEY")}} and {{source("KEY","key")}} are replaced by [title]
+    fn template(&self, template: &str) -> String {
+        let mut readable = String::new();
+        let mut rest = template;
+        while let Some(start) = rest.find("{{") {
+            let end = match rest[start..].find("}}") {
+                Some(end) => start + end + 2,
+                None => break,
+            };
+            readable.push_str(&rest[..start]);
+            let placeholder = &rest[start..end];
+            let key = placeholder
+                .strip_prefix("{{source(\"")
+                .and_then(|source| source.split('"').next());
+            match key {
+                Some(key) => {
+                    let title = self
+                        .source(key)
+                        .0
+                        .into_iter()
+                        .map(|span| match span {
+                            Span::Plain(value) | Span::Code(value) => value,
+                        })
+                        .collect::<String>();
+                    readable.push_str(&format!("[{}]", title));
+                }
+                None => readable.push_str(placeholder),
+            }
+            rest = &rest[end..];
+        }
+        readable.push_str(rest);
+        readable
+    }
+
+    fn conditional_formattings(&mut self) {
+        if self.schema.conditional_formattings.is_empty() {
+            return;
+        }
+        self.blocks
+            .push(Block::Heading(2, Text::from("Conditional formattings")));
+        for conditional_formatting in self.schema.conditional_formattings.iter() {
+            self.blocks.push(Block::Heading(
+                3,
+                self.named(
+                    &self.title(conditional_formatting),
+                    &conditional_formatting.key,
+                ),
+            ));
+            let description = self.description(conditional_formatting);
+            if !description.is_empty() {
+                self.blocks.push(Block::Paragraph(description));
+            }
+            let default_status = &conditional_formatting.default_status;
+            self.blocks.push(Block::Paragraph(
+                Text::from("Level: ")
+                    .text(self.level(&conditional_formatting.level))
+                    .plain(". Default status: ")
+                    .plain(&self.title(default_status))
+                    .plain(&format!(" ({}).", default_status.color)),
+            ));
+            let mut statuses = conditional_formatting.statuses.iter().collect::<Vec<_>>();
+            statuses.sort_by_key(|status| status.priority);
+            for status in statuses {
+                self.blocks.push(Block::Heading(
+                    4,
+                    Text::from(format!(
+                        "{} ({}, priority {})",
+                        self.title(status),
+                        status.color,
+                        status.priority
+                    )),
+                ));
+                let mut items = vec![];
+                for rules in status.rules.common.iter() {
+                    let attribute = self.attribute(&rules.attribute_type, &rules.key);
+                    for control in rules.controls.iter() {
+                        items.push((0, attribute.clone().plain(" ").text(self.control(control))));
+                    }
+                }
+                for specific in status.rules.specifics.iter() {
+                    let category = self.category(&specific.category_ref());
+                    for rules in specific.attributes.iter() {
+                        for control in rules.controls.iter() {
+                            items.push((
+                                0,
+                                Text::from("In ")
+                                    .text(category.clone())
+                                    .plain(", ")
+                                    .text(self.field(&rules.key))
+                                    .plain(" ")
+                                    .text(self.control(control)),
+                            ));
+                        }
+                    }
+                }
+                if !items.is_empty() {
+                    self.blocks.push(Block::List(items));
+                }
+            }
+        }
+    }
+    fn control(&self, control: &Control) -> Text {
+        match control {
+            Control::RuleRequired => Text::from("is required"),
+            Control::RuleIsLeaf => Text::from("is a leaf category"),
+            Control::RuleMinLength { min } => {
+                Text::from(format!("has at least {} characters", min))
+            }
+            Control::RuleMaxLength { max } => Text::from(format!("has at most {} characters", max)),
+            Control::RuleRegex { regex } => Text::from("matches ").code(regex),
+            Control::RuleBarcode { barcode_type } => {
+                Text::from(format!("is a valid {} barcode", barcode_type))
+            }
+            Control::RuleLessThan { value } => Text::from(format!("is less than {}", value)),
+            Control::RuleGreaterThan { value } => Text::from(format!("is greater than {}", value)),
+            Control::RuleLessThanOrEqual { value } => Text::from(format!("is at most {}", value)),
+            Control::RuleGreaterThanOrEqual { value } => {
+                Text::from(format!("is at least {}", value))
+            }
+            Control::RuleDecimalPlaces { precision } => {
+                Text::from(format!("has at most {} decimal places", precision))
+            }
+            Control::RuleMinWidthPx { min } => Text::from(format!("is at least {} px wide", min)),
+            Control::RuleMaxWidthPx { max } => Text::from(format!("is at most {} px wide", max)),
+            Control::RuleMinHeightPx { min } => Text::from(format!("is at least {} px high", min)),
+            Control::RuleMaxHeightPx { max } => Text::from(format!("is at most {} px high", max)),
+            Control::RuleMaxSizeKb { max } => Text::from(format!("weighs at most {} KB", max)),
+            Control::RuleExtension { extension } => {
+                Text::from("has the extension ").code(extension)
+            }
+            Control::RuleColorSpace { name } => Text::from("uses the color space ").code(name),
+            Control::RuleColorProfile { name } => Text::from("uses the color profile ").code(name),
+            Control::RuleMinValues { min } => Text::from(format!("has at least {} values", min)),
+            Control::RuleMaxValues { max } => Text::from(format!("has at most {} values", max)),
+            Control::RuleMustBeGreaterThanAnotherField { field } => {
+                Text::from("is greater than ").text(self.field(field))
+            }
+            Control::RuleMustBeGreaterThanOrEqualAnotherField { field } => {
+                Text::from("is greater than or equal to ").text(self.field(field))
+            }
+            Control::RuleMustBeLessThanAnotherField { field } => {
+                Text::from("is less than ").text(self.field(field))
+            }
+            Control::RuleMustBeLessThanOrEqualAnotherField { field } => {
+                Text::from("is less than or equal to ").text(self.field(field))
+            }
+            Control::RuleRequiredIfAnotherFieldIsNotEmpty { field } => {
+                Text::from("is required if ")
+                    .text(self.field(field))
+                    .plain(" is not empty")
+            }
+            Control::RuleRequiredIfAnotherFieldHasOptions { field, options } => {
+                let other = self.schema.field(field);
+                let options = options
+                    .iter()
+                    .map(
+                        |option| match other.and_then(|other| other.option(&option.key)) {
+                            Some(option) => Text::from(self.title(option)),
+                            None => Text::default().code(&option.key),
+                        },
+                    )
+                    .collect();
+                Text::from("is required if ")
+                    .text(self.field(field))
+                    .plain(" is one of ")
+                    .text(Text::join(options, ", "))
+            }
+            Control::RuleRequiredIfAnotherFieldIsGreaterThan { field, value } => {
+                Text::from("is required if ")
+                    .text(self.field(field))
+                    .plain(&format!(" is greater than {}", value))
+            }
+            Control::RuleRequiredIfAnotherFieldIsGreaterThanOrEqual { field, value } => {
+                Text::from("is required if ")
+                    .text(self.field(field))
+                    .plain(&format!(" is at least {}", value))
+            }
+            Control::RuleRequiredIfAnotherFieldIsLessThan { field, value } => {
+                Text::from("is required if ")
+                    .text(self.field(field))
+                    .plain(&format!(" is less than {}", value))
+            }
+            Control::RuleRequiredIfAnotherFieldIsLessThanOrEqual { field, value } => {
+                Text::from("is required if ")
+                    .text(self.field(field))
+                    .plain(&format!(" is at most {}", value))
+            }
+            Control::RuleRequiredIfAnotherFieldIsEqualTo { field, value } => {
+                Text::from("is required if ")
+                    .text(self.field(field))
+                    .plain(" equals ")
+                    .code(value)
+            }
+            Control::RuleCondition {
+                condition_groups,
+                title,
+                title_locals,
+                ..
+            } => {
+                let title = self
+                    .chain
+                    .find(title_locals.as_deref().unwrap_or_default())
+                    .unwrap_or(title);
+                Text::from(format!("meets \"{}\" when ", title))
+                    .text(self.condition_groups(condition_groups))
+            }
+        }
+    }
+
+    fn screens(&mut self) {
+        if self.schema.screens.is_empty() {
+            return;
+        }
+        self.blocks.push(Block::Heading(2, Text::from("Screens")));
+        let mut screens = self.schema.screens.iter().collect::<Vec<_>>();
+        screens.sort_by_key(|screen| screen.position);
+        for screen in screens {
+            self.blocks.push(Block::Heading(
+                3,
+                self.named(&self.title(screen), &screen.key),
+            ));
+            self.blocks.push(Block::Paragraph(
+                Text::from("Level: ")
+                    .text(self.level(&screen.level))
+                    .plain(&format!(". Line height: {}.", screen.grid.line_height)),
+            ));
+            let mut sections = screen.grid.common.iter().collect::<Vec<_>>();
+            sections.sort_by_key(|section| section.position);
+            let rows = sections
+                .iter()
+                .map(|section| {
+                    let mut columns = section.columns.iter().collect::<Vec<_>>();
+                    columns.sort_by_key(|column| self.common_column(column).1);
+                    let columns = columns
+                        .iter()
+                        .map(|column| self.common_column(column).0)
+                        .collect();
+                    vec![self.section(&section.key), Text::join(columns, ", ")]
+                })
+                .collect();
+            self.blocks
+                .push(Block::Table(vec!["Section", "Columns"], rows));
+            for specific in screen.grid.specifics.iter() {
+                self.blocks.push(Block::Paragraph(
+                    Text::from("In ")
+                        .text(self.category(&specific.category_ref()))
+                        .plain(":"),
+                ));
+                let mut sections = specific.sections.iter().collect::<Vec<_>>();
+                sections.sort_by_key(|section| section.position);
+                let rows = sections
+                    .iter()
+                    .map(|section| {
+                        let mut columns = section.columns.iter().collect::<Vec<_>>();
+                        columns.sort_by_key(|column| column.position);
+                        let columns = columns
+                            .iter()
+                            .map(|column| self.column(self.field(&column.key), column))
+                            .collect();
+                        vec![self.section(&section.key), Text::join(columns, ", ")]
+                    })
+                    .collect();
+                self.blocks
+                    .push(Block::Table(vec!["Section", "Columns"], rows));
+            }
+        }
+    }
+    fn section(&self, key: &SectionKey) -> Text {
+        match self.schema.section(key) {
+            Some(section) => Text::from(self.title(section)),
+            None => Text::default().code(key),
+        }
+    }
+    // Text and position of a column of a common section
+    fn common_column(&self, column: &CommonColumn) -> (Text, u32) {
+        match column {
+            CommonColumn::ColumnIdentifier(column) => (
+                self.column(
+                    self.attribute(&AttributeType::Identifier, &column.key),
+                    column,
+                ),
+                column.position,
+            ),
+            CommonColumn::ColumnClassification(column) => (
+                self.column(
+                    self.attribute(&AttributeType::Classification, &column.key),
+                    column,
+                ),
+                column.position,
+            ),
+            CommonColumn::ColumnConditionalFormatting(column) => {
+                let key: &ConditionalFormattingKey = &column.key;
+                let title = match self.schema.conditional_formatting(key) {
+                    Some(conditional_formatting) => Text::from(self.title(conditional_formatting)),
+                    None => Text::default().code(key),
+                };
+                (self.column(title, column), column.position)
+            }
+            CommonColumn::ColumnField(column) => (
+                self.column(self.field(&column.key), column),
+                column.position,
+            ),
+        }
+    }
+    // Title followed by the width and the flags of the column: Color (SMALL, read only)
+    fn column<K>(&self, title: Text, column: &Column<K>) -> Text {
+        let mut details = vec![];
+        if let Some(width) = &column.width {
+            details.push(width.to_string());
+        }
+        if column.read_only == Some(true) {
+            details.push("read only".to_owned());
+        }
+        if column.fixed == Some(true) {
+            details.push("fixed".to_owned());
+        }
+        match details.is_empty() {
+            true => title,
+            false => title.plain(&format!(" ({})", details.join(", "))),
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    table_matrix::{category_fields, lineage, EffectiveField, Inheritance, Source},
+    table_structs::{
+        AttributeType, CategoryRef, CommonColumn, ConditionalFormattingKey, Control, LevelKey,
+        Schema, SectionKey,
+    },
+};
+
+// What a product of a category looks like: its fields, the rules of every status and the
+// sections of every screen, common parts first then the specific parts of the category and,
+// when inherited, of its ancestors
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EffectiveSchema {
+    pub category: CategoryRef,
+    pub fields: Vec<EffectiveField>,
+    pub conditional_formattings: Vec<EffectiveConditionalFormatting>,
+    pub screens: Vec<EffectiveScreen>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EffectiveConditionalFormatting {
+    pub key: ConditionalFormattingKey,
+    pub statuses: Vec<EffectiveStatus>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EffectiveStatus {
+    pub key: String,
+    pub rules: Vec<EffectiveRules>,
+}
+
+// Rules on an attribute, an attribute may have several of them (common and specific), they
+// all apply
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EffectiveRules {
+    pub attribute_type: AttributeType,
+    pub key: String,
+    pub source: Source,
+    pub controls: Vec<Control>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EffectiveScreen {
+    pub key: String,
+    pub level: LevelKey,
+    pub sections: Vec<EffectiveSection>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EffectiveSection {
+    pub key: SectionKey,
+    pub position: usize,
+    pub source: Source,
+    pub columns: Vec<CommonColumn>,
+}
+
+pub fn effective_schema(
+    schema: &Schema,
+    category: &CategoryRef,
+    inheritance: Inheritance,
+) -> Result<EffectiveSchema, Box<dyn Error>> {
+    let classification = schema
+        .classification(&category.classification)
+        .ok_or_else(|| format!("Unknown classification '{}'.", category.classification))?;
+    if classification.category(&category.category).is_none() {
+        return Err(format!(
+            "Unknown category '{}' in classification '{}'.",
+            category.category, category.classification
+        )
+        .into());
+    }
+    let lineage = lineage(classification, &category.category, inheritance);
+    // Specific parts are taken for the category then its ancestors, nearest first
+    let applies = |classification_key: &str, category_key: &str, key: &str| {
+        classification.key == classification_key && category_key == key
+    };
+
+    let conditional_formattings = schema
+        .conditional_formattings
+        .iter()
+        .map(|conditional_formatting| EffectiveConditionalFormatting {
+            key: conditional_formatting.key.to_owned(),
+            statuses: conditional_formatting
+                .statuses
+                .iter()
+                .map(|status| {
+                    let mut rules = status
+                        .rules
+                        .common
+                        .iter()
+                        .map(|rules| EffectiveRules {
+                            attribute_type: rules.attribute_type.to_owned(),
+                            key: rules.key.to_owned(),
+                            source: Source::Common,
+                            controls: rules.controls.to_vec(),
+                        })
+                        .collect::<Vec<_>>();
+                    for (key, source) in lineage.iter() {
+                        for specifics in status.rules.specifics.iter().filter(|specifics| {
+                            applies(&specifics.classification, &specifics.category, key)
+                        }) {
+                            rules.extend(specifics.attributes.iter().map(|attribute| {
+                                EffectiveRules {
+                                    attribute_type: AttributeType::Field,
+                                    key: attribute.key.to_string(),
+                                    source: *source,
+                                    controls: attribute.controls.to_vec(),
+                                }
+                            }));
+                        }
+                    }
+                    EffectiveStatus {
+                        key: status.key.to_owned(),
+                        rules,
+                    }
+                })
+                .collect(),
+        })
+        .collect();
+
+    let screens = schema
+        .screens
+        .iter()
+        .map(|screen| {
+            let mut sections = screen
+                .grid
+                .common
+                .iter()
+                .map(|section| EffectiveSection {
+                    key: section.key.to_owned(),
+                    position: section.position,
+                    source: Source::Common,
+                    columns: section.columns.to_vec(),
+                })
+                .collect::<Vec<_>>();
+            for (key, source) in lineage.iter() {
+                for grid_specific in screen.grid.specifics.iter().filter(|grid_specific| {
+                    applies(&grid_specific.classification, &grid_specific.category, key)
+                }) {
+                    sections.extend(grid_specific.sections.iter().map(|section| {
+                        EffectiveSection {
+                            key: section.key.to_owned(),
+                            position: section.position as usize,
+                            source: *source,
+                            columns: section
+                                .columns
+                                .iter()
+                                .map(|column| CommonColumn::ColumnField(column.clone()))
+                                .collect(),
+                        }
+                    }));
+                }
+            }
+            EffectiveScreen {
+                key: screen.key.to_owned(),
+                level: screen.level.to_owned(),
+                sections,
+            }
+        })
+        .collect();
+
+    Ok(EffectiveSchema {
+        category: category.to_owned(),
+        fields: category_fields(schema, classification, &category.category, inheritance).fields,
+        conditional_formattings,
+        screens,
+    })
+}
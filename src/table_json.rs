@@ -0,0 +1,45 @@
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+
+use crate::table_structs::Table;
+
+// Version of the JSON representation of tables, to increment on breaking changes of
+// the serde attributes of table_structs. The format is described by
+// schemas/table.schema.json.
+pub const JSON_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TableDocument {
+    pub format_version: u32,
+    pub table: Table,
+}
+
+pub fn to_json(table: &Table) -> Result<String, Box<dyn Error>> {
+    let document = TableDocument {
+        format_version: JSON_FORMAT_VERSION,
+        table: table.to_owned(),
+    };
+    Ok(serde_json::to_string_pretty(&document)?)
+}
+
+pub fn from_json(json: &str) -> Result<Table, Box<dyn Error>> {
+    let document: serde_json::Value = serde_json::from_str(json)?;
+    match document
+        .get("formatVersion")
+        .and_then(|version| version.as_u64())
+    {
+        Some(version) if version == JSON_FORMAT_VERSION as u64 => {
+            let document: TableDocument = serde_json::from_value(document)?;
+            Ok(document.table)
+        }
+        Some(version) => Err(format!(
+            "Unsupported JSON format version {} (expected {}).",
+            version, JSON_FORMAT_VERSION
+        )
+        .into()),
+        None => Err("Missing JSON format version.".into()),
+    }
+}
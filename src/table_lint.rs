@@ -0,0 +1,404 @@
+use std::{collections::BTreeMap, error::Error, fmt, fs, path::Path};
+
+use quick_xml::{events::Event, Reader};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    table_structs::{
+        Category, Classification, ConditionalFormatting, Field, Identifier, Level, Partition,
+        Section, SelectOption, Status, Suffix, Table,
+    },
+    table_translation::identical_locals,
+    table_validation::TableValidation,
+    table_visitor::{
+        walk, walk_category, walk_classification, walk_conditional_formatting, walk_field,
+        walk_identifier, walk_level, walk_partition, walk_section, walk_select_option, walk_status,
+        walk_suffix, Visitor,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+    // The rule is disabled
+    Off,
+}
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+            Severity::Off => "off",
+        })
+    }
+}
+
+// Severity of the rules, by rule name. Rules which are not listed keep their default.
+//   [rules]
+//   UNTRANSLATED_LOCAL = "off"
+//   FIELD_DESCRIPTION = "warning"
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LintConfig {
+    #[serde(default)]
+    pub rules: BTreeMap<String, Severity>,
+}
+impl LintConfig {
+    pub fn from_toml(toml: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(toml::from_str(toml)?)
+    }
+    pub fn from_json(json: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_str(json)?)
+    }
+    // The format is given by the extension of the file, .toml or .json
+    pub fn read(path: &str) -> Result<Self, Box<dyn Error>> {
+        let content = fs::read_to_string(path)?;
+        match Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+        {
+            Some("toml") => LintConfig::from_toml(&content),
+            Some("json") => LintConfig::from_json(&content),
+            _ => Err(format!("Lint configuration '{}' is not a toml or json file.", path).into()),
+        }
+    }
+}
+
+// Element breaking a rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Finding {
+    pub message: String,
+    pub xpath: String,
+}
+
+// A named rule. House-style rules implement it and are added to the Linter.
+pub trait LintRule {
+    // Name used by the configuration and the lint-disable comments
+    fn name(&self) -> &str;
+    fn default_severity(&self) -> Severity;
+    fn check(&self, table: &Table) -> Vec<Finding>;
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub rule: String,
+    pub severity: Severity,
+    pub message: String,
+    pub xpath: String,
+}
+
+// A <!-- lint-disable RULE_A, RULE_B --> comment, it disables the rules for the element
+// which follows it and its descendants. Without rule names, all the rules are disabled.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Suppression {
+    pub rules: Vec<String>,
+    pub xpath: String,
+}
+impl Suppression {
+    pub fn applies(&self, diagnostic: &Diagnostic) -> bool {
+        let within = match diagnostic.xpath.strip_prefix(&self.xpath) {
+            Some(rest) => rest.is_empty() || rest.starts_with('/'),
+            None => false,
+        };
+        within && (self.rules.is_empty() || self.rules.contains(&diagnostic.rule))
+    }
+}
+
+// The error codes of TableValidation are rules too, with the error severity. Its warnings
+// are checked by lint rules of the same name (UNTRANSLATED_LOCAL).
+pub struct Linter {
+    config: LintConfig,
+    rules: Vec<Box<dyn LintRule>>,
+}
+impl Linter {
+    // UNTRANSLATED_LOCAL is a warning, the house-style rules are off until enabled in the
+    // configuration
+    pub fn new(config: LintConfig) -> Self {
+        Linter {
+            config,
+            rules: vec![
+                Box::new(UntranslatedLocal),
+                Box::new(UpperSnakeKeys),
+                Box::new(FieldDescription),
+            ],
+        }
+    }
+    pub fn add_rule(&mut self, rule: Box<dyn LintRule>) {
+        self.rules.push(rule);
+    }
+    pub fn rule_names(&self) -> Vec<&str> {
+        self.rules.iter().map(|rule| rule.name()).collect()
+    }
+    pub fn lint(
+        &self,
+        table: &Table,
+        suppressions: &[Suppression],
+    ) -> Result<Vec<Diagnostic>, Box<dyn Error>> {
+        let mut diagnostics = vec![];
+        let mut validation = TableValidation::new();
+        validation.validate(table)?;
+        for error in validation.errors.iter() {
+            diagnostics.push(Diagnostic {
+                rule: error.code().to_owned(),
+                severity: Severity::Error,
+                message: error.message().to_owned(),
+                xpath: error.xpath().to_owned(),
+            });
+        }
+        for rule in self.rules.iter() {
+            for finding in rule.check(table) {
+                diagnostics.push(Diagnostic {
+                    rule: rule.name().to_owned(),
+                    severity: rule.default_severity(),
+                    message: finding.message,
+                    xpath: finding.xpath,
+                });
+            }
+        }
+        for diagnostic in diagnostics.iter_mut() {
+            if let Some(severity) = self.config.rules.get(&diagnostic.rule) {
+                diagnostic.severity = *severity;
+            }
+        }
+        diagnostics.retain(|diagnostic| {
+            diagnostic.severity != Severity::Off
+                && !suppressions
+                    .iter()
+                    .any(|suppression| suppression.applies(diagnostic))
+        });
+        Ok(diagnostics)
+    }
+}
+
+// Suppressions of a table xml file, with the xpaths of the elements as built by the visitor
+pub fn suppressions(xml: &str) -> Result<Vec<Suppression>, Box<dyn Error>> {
+    let mut reader = Reader::from_str(xml);
+    let mut suppressions = vec![];
+    // Rules of each lint-disable comment waiting for the next element
+    let mut pending: Vec<Vec<String>> = vec![];
+    // Xpath of the open elements, and the names of the children already read
+    let mut stack: Vec<(String, Vec<String>)> = vec![];
+    loop {
+        let (event, empty) = match reader.read_event()? {
+            Event::Start(event) => (event, false),
+            Event::Empty(event) => (event, true),
+            Event::End(_) => {
+                stack.pop();
+                pending.clear();
+                continue;
+            }
+            Event::Comment(comment) => {
+                let comment = comment.unescape()?;
+                if let Some(rules) = comment
+                    .trim()
+                    .strip_prefix("lint-disable")
+                    .filter(|rules| rules.is_empty() || rules.starts_with(char::is_whitespace))
+                {
+                    pending.push(
+                        rules
+                            .split(|c: char| c == ',' || c.is_whitespace())
+                            .filter(|rule| !rule.is_empty())
+                            .map(|rule| rule.to_owned())
+                            .collect(),
+                    );
+                }
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+        let name = String::from_utf8_lossy(event.local_name().as_ref()).to_string();
+        let mut attributes = BTreeMap::new();
+        for attribute in event.attributes() {
+            let attribute = attribute?;
+            attributes.insert(
+                String::from_utf8_lossy(attribute.key.as_ref()).to_string(),
+                attribute.unescape_value()?.to_string(),
+            );
+        }
+        let (parent, siblings) = match stack.last_mut() {
+            Some((xpath, siblings)) => (xpath.as_str(), siblings),
+            None => ("", &mut vec![]),
+        };
+        siblings.push(name.to_owned());
+        let xpath = element_xpath(parent, &name, &attributes, siblings);
+        for rules in pending.drain(..) {
+            suppressions.push(Suppression {
+                rules,
+                xpath: xpath.to_owned(),
+            });
+        }
+        if !empty {
+            stack.push((xpath, vec![]));
+        }
+    }
+    Ok(suppressions)
+}
+
+// Same predicates as the visitor: key, classification and category, lang, priority, and
+// the index of conditions and condition groups
+fn element_xpath(
+    parent: &str,
+    name: &str,
+    attributes: &BTreeMap<String, String>,
+    siblings: &[String],
+) -> String {
+    let mut xpath = format!("{}/{}", parent, name);
+    if name == "Condition" || name == "Condition-Group" {
+        let index = siblings.iter().filter(|sibling| *sibling == name).count();
+        xpath.push_str(&format!("[{}]", index));
+        return xpath;
+    }
+    // The visitor does not identify the root and the default status by their key
+    if parent.is_empty() || name == "Default-Status" {
+        return xpath;
+    }
+    for attribute in ["key", "classification", "category", "lang", "priority"] {
+        if let Some(value) = attributes.get(attribute) {
+            xpath.push_str(&format!("[@{}='{}']", attribute, value));
+        }
+    }
+    xpath
+}
+
+// Locals must differ from the text they translate
+struct UntranslatedLocal;
+impl LintRule for UntranslatedLocal {
+    fn name(&self) -> &str {
+        "UNTRANSLATED_LOCAL"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn check(&self, table: &Table) -> Vec<Finding> {
+        identical_locals(table)
+            .into_iter()
+            .map(|local| Finding {
+                message: local.message(),
+                xpath: local.xpath,
+            })
+            .collect()
+    }
+}
+
+// Keys of the elements defined by the schema are in upper snake case (COLOR, EAN_13)
+struct UpperSnakeKeys;
+impl LintRule for UpperSnakeKeys {
+    fn name(&self) -> &str {
+        "UPPER_SNAKE_KEYS"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Off
+    }
+    fn check(&self, table: &Table) -> Vec<Finding> {
+        let mut collector = KeyCollector { keys: vec![] };
+        walk(&mut collector, table);
+        collector
+            .keys
+            .into_iter()
+            .filter(|(_, key)| !is_upper_snake(key))
+            .map(|(xpath, key)| Finding {
+                message: format!("Key '{}' is not in upper snake case.", key),
+                xpath: format!("{}/@key", xpath),
+            })
+            .collect()
+    }
+}
+
+fn is_upper_snake(key: &str) -> bool {
+    key.starts_with(|c: char| c.is_ascii_uppercase())
+        && key
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+// Xpaths and keys of the elements defined by the schema
+struct KeyCollector {
+    keys: Vec<(String, String)>,
+}
+impl KeyCollector {
+    fn add(&mut self, xpath: &str, key: &str) {
+        self.keys.push((xpath.to_owned(), key.to_owned()));
+    }
+}
+impl Visitor for KeyCollector {
+    fn visit_partition(&mut self, partition: &Partition, xpath: &str) {
+        self.add(xpath, &partition.key);
+        walk_partition(self, partition, xpath)
+    }
+    fn visit_level(&mut self, level: &Level, xpath: &str) {
+        self.add(xpath, &level.key);
+        walk_level(self, level, xpath)
+    }
+    fn visit_identifier(&mut self, identifier: &Identifier, xpath: &str) {
+        self.add(xpath, &identifier.key);
+        walk_identifier(self, identifier, xpath)
+    }
+    fn visit_classification(&mut self, classification: &Classification, xpath: &str) {
+        self.add(xpath, &classification.key);
+        walk_classification(self, classification, xpath)
+    }
+    fn visit_category(&mut self, category: &Category, xpath: &str) {
+        self.add(xpath, &category.key);
+        walk_category(self, category, xpath)
+    }
+    fn visit_field(&mut self, field: &Field, xpath: &str) {
+        self.add(xpath, &field.key);
+        walk_field(self, field, xpath)
+    }
+    fn visit_select_option(&mut self, option: &SelectOption, xpath: &str) {
+        self.add(xpath, &option.key);
+        walk_select_option(self, option, xpath)
+    }
+    fn visit_suffix(&mut self, suffix: &Suffix, xpath: &str) {
+        self.add(xpath, &suffix.key);
+        walk_suffix(self, suffix, xpath)
+    }
+    fn visit_conditional_formatting(
+        &mut self,
+        conditional_formatting: &ConditionalFormatting,
+        xpath: &str,
+    ) {
+        self.add(xpath, &conditional_formatting.key);
+        walk_conditional_formatting(self, conditional_formatting, xpath)
+    }
+    fn visit_status(&mut self, status: &Status, xpath: &str) {
+        self.add(xpath, &status.key);
+        walk_status(self, status, xpath)
+    }
+    fn visit_section(&mut self, section: &Section, xpath: &str) {
+        self.add(xpath, &section.key);
+        walk_section(self, section, xpath)
+    }
+}
+
+// Every field has a description
+struct FieldDescription;
+impl LintRule for FieldDescription {
+    fn name(&self) -> &str {
+        "FIELD_DESCRIPTION"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Off
+    }
+    fn check(&self, table: &Table) -> Vec<Finding> {
+        table
+            .schema
+            .fields
+            .iter()
+            .filter(|field| {
+                field
+                    .description
+                    .as_ref()
+                    .is_none_or(|description| description.trim().is_empty())
+            })
+            .map(|field| Finding {
+                message: format!("Field '{}' has no description.", field.key),
+                xpath: format!("/Table/Schema/Fields/Field[@key='{}']", field.key),
+            })
+            .collect()
+    }
+}
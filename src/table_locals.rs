@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+
+use crate::{
+    table_structs::{
+        Category, Classification, ConditionalFormatting, Control, DefaultStatus, Field, Identifier,
+        Level, Local, Partition, Screen, Section, SelectOption, Status, Suffix, Table,
+    },
+    table_visitor::{
+        walk, walk_category, walk_category_mut, walk_classification, walk_classification_mut,
+        walk_conditional_formatting, walk_conditional_formatting_mut, walk_control,
+        walk_control_mut, walk_default_status, walk_default_status_mut, walk_field, walk_field_mut,
+        walk_identifier, walk_identifier_mut, walk_level, walk_level_mut, walk_mut, walk_partition,
+        walk_partition_mut, walk_screen, walk_screen_mut, walk_section, walk_section_mut,
+        walk_select_option, walk_select_option_mut, walk_status, walk_status_mut, walk_suffix,
+        walk_suffix_mut, walk_table, walk_table_mut, Visitor, VisitorMut,
+    },
+};
+
+// Ordered languages to look for in the locals of an element, the title or description
+// of the element is used when none of them is found:
+//   LangChain::new(&["fra", "eng"]) gives the fra local, else the eng local, else the title
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LangChain {
+    langs: Vec<String>,
+}
+impl LangChain {
+    pub fn new(langs: &[&str]) -> Self {
+        LangChain {
+            langs: langs.iter().map(|lang| lang.to_string()).collect(),
+        }
+    }
+    pub fn then(mut self, lang: &str) -> Self {
+        self.langs.push(lang.to_owned());
+        self
+    }
+    pub fn langs(&self) -> &[String] {
+        &self.langs
+    }
+    pub(crate) fn find<'a>(&self, locals: &'a [Local]) -> Option<&'a str> {
+        self.langs.iter().find_map(|lang| {
+            locals
+                .iter()
+                .find(|local| &local.lang == lang)
+                .map(|local| local.value.as_str())
+        })
+    }
+}
+
+// Elements with a title and a description translated by Title-Local and Description-Local
+pub trait Localized {
+    fn title(&self) -> &str;
+    fn description(&self) -> Option<&str>;
+    fn title_locals(&self) -> &[Local];
+    fn description_locals(&self) -> &[Local];
+    fn title_mut(&mut self) -> &mut String;
+    fn description_mut(&mut self) -> &mut Option<String>;
+    fn title_locals_mut(&mut self) -> &mut Option<Vec<Local>>;
+    fn description_locals_mut(&mut self) -> &mut Option<Vec<Local>>;
+
+    fn title_in(&self, lang: &str) -> &str {
+        self.title_with(&LangChain::new(&[lang]))
+    }
+    fn title_with(&self, chain: &LangChain) -> &str {
+        chain
+            .find(self.title_locals())
+            .unwrap_or_else(|| self.title())
+    }
+    fn description_in(&self, lang: &str) -> Option<&str> {
+        self.description_with(&LangChain::new(&[lang]))
+    }
+    fn description_with(&self, chain: &LangChain) -> Option<&str> {
+        chain
+            .find(self.description_locals())
+            .or_else(|| self.description())
+    }
+    fn set_title_local(&mut self, lang: &str, value: &str) {
+        set_local(self.title_locals_mut(), lang, value)
+    }
+    fn set_description_local(&mut self, lang: &str, value: &str) {
+        set_local(self.description_locals_mut(), lang, value)
+    }
+    // Languages of the locals, in order of appearance and without duplicates
+    fn langs(&self) -> Vec<&str> {
+        let mut langs: Vec<&str> = vec![];
+        for local in self.title_locals().iter().chain(self.description_locals()) {
+            if !langs.contains(&local.lang.as_str()) {
+                langs.push(&local.lang);
+            }
+        }
+        langs
+    }
+}
+
+macro_rules! localized {
+    ($($name:ty),*) => {
+        $(
+            impl Localized for $name {
+                fn title(&self) -> &str {
+                    &self.title
+                }
+                fn description(&self) -> Option<&str> {
+                    self.description.as_deref()
+                }
+                fn title_locals(&self) -> &[Local] {
+                    self.title_locals.as_deref().unwrap_or_default()
+                }
+                fn description_locals(&self) -> &[Local] {
+                    self.description_locals.as_deref().unwrap_or_default()
+                }
+                fn title_mut(&mut self) -> &mut String {
+                    &mut self.title
+                }
+                fn description_mut(&mut self) -> &mut Option<String> {
+                    &mut self.description
+                }
+                fn title_locals_mut(&mut self) -> &mut Option<Vec<Local>> {
+                    &mut self.title_locals
+                }
+                fn description_locals_mut(&mut self) -> &mut Option<Vec<Local>> {
+                    &mut self.description_locals
+                }
+            }
+        )*
+    };
+}
+localized!(
+    Table,
+    Partition,
+    Level,
+    Identifier,
+    Classification,
+    Category,
+    Field,
+    SelectOption,
+    Suffix,
+    ConditionalFormatting,
+    DefaultStatus,
+    Status,
+    Section,
+    Screen
+);
+
+// Shape of the ISO 639-3 codes (fra, eng, deu): three lowercase ASCII letters. The code
+// itself is not checked against the ISO 639-3 list.
+pub fn is_three_letter_lang(lang: &str) -> bool {
+    lang.len() == 3 && lang.chars().all(|c| c.is_ascii_lowercase())
+}
+
+// Replaces the value of the local of the lang, or adds it
+pub fn set_local(locals: &mut Option<Vec<Local>>, lang: &str, value: &str) {
+    let locals = locals.get_or_insert_with(Vec::new);
+    match locals.iter_mut().find(|local| local.lang == lang) {
+        Some(local) => local.value = value.to_owned(),
+        None => locals.push(Local {
+            lang: lang.to_owned(),
+            value: value.to_owned(),
+        }),
+    }
+}
+
+// A translatable text of a table with its locals, the xpath points to the Title or
+// Description element holding the text
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalizedText {
+    pub xpath: String,
+    pub text: String,
+    pub locals: Vec<Local>,
+}
+impl LocalizedText {
+    pub fn local(&self, lang: &str) -> Option<&str> {
+        self.locals
+            .iter()
+            .find(|local| local.lang == lang)
+            .map(|local| local.value.as_str())
+    }
+}
+
+// Titles and descriptions of every localized element and titles of condition rules,
+// in the order of the xml file
+pub fn localized_texts(table: &Table) -> Vec<LocalizedText> {
+    let mut collector = TextsCollector { texts: vec![] };
+    walk(&mut collector, table);
+    collector.texts
+}
+
+// Sets the locals of a lang from values keyed by the xpaths of localized_texts, returns
+// the xpaths which were found in the table
+pub fn set_locals(table: &mut Table, lang: &str, values: &HashMap<String, String>) -> Vec<String> {
+    let mut setter = LocalsSetter {
+        lang: lang.to_owned(),
+        values,
+        xpaths: vec![],
+    };
+    walk_mut(&mut setter, table);
+    setter.xpaths
+}
+
+struct TextsCollector {
+    texts: Vec<LocalizedText>,
+}
+impl TextsCollector {
+    fn add<L: Localized>(&mut self, element: &L, xpath: &str) {
+        self.texts.push(LocalizedText {
+            xpath: format!("{}/Title", xpath),
+            text: element.title().to_owned(),
+            locals: element.title_locals().to_vec(),
+        });
+        if let Some(description) = element.description() {
+            self.texts.push(LocalizedText {
+                xpath: format!("{}/Description", xpath),
+                text: description.to_owned(),
+                locals: element.description_locals().to_vec(),
+            });
+        }
+    }
+}
+macro_rules! visit_localized {
+    ($($visit:ident, $walk:ident, $name:ty;)*) => {
+        $(
+            fn $visit(&mut self, element: $name, xpath: &str) {
+                self.add(element, xpath);
+                $walk(self, element, xpath)
+            }
+        )*
+    };
+}
+impl Visitor for TextsCollector {
+    visit_localized!(
+        visit_table, walk_table, &Table;
+        visit_partition, walk_partition, &Partition;
+        visit_level, walk_level, &Level;
+        visit_identifier, walk_identifier, &Identifier;
+        visit_classification, walk_classification, &Classification;
+        visit_category, walk_category, &Category;
+        visit_field, walk_field, &Field;
+        visit_select_option, walk_select_option, &SelectOption;
+        visit_suffix, walk_suffix, &Suffix;
+        visit_conditional_formatting, walk_conditional_formatting, &ConditionalFormatting;
+        visit_default_status, walk_default_status, &DefaultStatus;
+        visit_status, walk_status, &Status;
+        visit_section, walk_section, &Section;
+        visit_screen, walk_screen, &Screen;
+    );
+    fn visit_control(&mut self, control: &Control, xpath: &str) {
+        if let Control::RuleCondition {
+            title,
+            title_locals,
+            ..
+        } = control
+        {
+            self.texts.push(LocalizedText {
+                xpath: format!("{}/Title", xpath),
+                text: title.to_owned(),
+                locals: title_locals.to_owned().unwrap_or_default(),
+            });
+        }
+        walk_control(self, control, xpath)
+    }
+}
+
+struct LocalsSetter<'a> {
+    lang: String,
+    values: &'a HashMap<String, String>,
+    xpaths: Vec<String>,
+}
+impl LocalsSetter<'_> {
+    fn set(&mut self, locals: &mut Option<Vec<Local>>, xpath: String) {
+        if let Some(value) = self.values.get(&xpath) {
+            set_local(locals, &self.lang, value);
+            self.xpaths.push(xpath);
+        }
+    }
+    fn add<L: Localized>(&mut self, element: &mut L, xpath: &str) {
+        self.set(element.title_locals_mut(), format!("{}/Title", xpath));
+        if element.description().is_some() {
+            self.set(
+                element.description_locals_mut(),
+                format!("{}/Description", xpath),
+            );
+        }
+    }
+}
+impl VisitorMut for LocalsSetter<'_> {
+    visit_localized!(
+        visit_table_mut, walk_table_mut, &mut Table;
+        visit_partition_mut, walk_partition_mut, &mut Partition;
+        visit_level_mut, walk_level_mut, &mut Level;
+        visit_identifier_mut, walk_identifier_mut, &mut Identifier;
+        visit_classification_mut, walk_classification_mut, &mut Classification;
+        visit_category_mut, walk_category_mut, &mut Category;
+        visit_field_mut, walk_field_mut, &mut Field;
+        visit_select_option_mut, walk_select_option_mut, &mut SelectOption;
+        visit_suffix_mut, walk_suffix_mut, &mut Suffix;
+        visit_conditional_formatting_mut, walk_conditional_formatting_mut, &mut ConditionalFormatting;
+        visit_default_status_mut, walk_default_status_mut, &mut DefaultStatus;
+        visit_status_mut, walk_status_mut, &mut Status;
+        visit_section_mut, walk_section_mut, &mut Section;
+        visit_screen_mut, walk_screen_mut, &mut Screen;
+    );
+    fn visit_control_mut(&mut self, control: &mut Control, xpath: &str) {
+        if let Control::RuleCondition { title_locals, .. } = control {
+            self.set(title_locals, format!("{}/Title", xpath));
+        }
+        walk_control_mut(self, control, xpath)
+    }
+}
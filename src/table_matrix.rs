@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    table_spreadsheet::Sheet,
+    table_structs::{CategoryKey, CategoryRef, Classification, FieldKey, Schema},
+};
+
+// Whether a category also gets the specific fields of its ancestors (Category.parent)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Inheritance {
+    #[default]
+    None,
+    Parents,
+}
+
+// Where an element applying to a category comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Source {
+    // The common part (Matrix.common, Rules.common, ScreenGrid.common)
+    Common,
+    // A specific part of the category
+    Specific,
+    // A specific part of an ancestor of the category
+    Inherited,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EffectiveField {
+    pub key: FieldKey,
+    pub source: Source,
+}
+
+// Fields which apply to a category, common fields first then specific ones from the
+// category up to its root. A field is listed once, with its first source.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CategoryFields {
+    pub category: CategoryRef,
+    pub fields: Vec<EffectiveField>,
+}
+impl CategoryFields {
+    pub fn has_specific_fields(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|field| field.source != Source::Common)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatrixCoverage {
+    pub categories_without_specific_fields: Vec<CategoryRef>,
+    // Fields of the schema which are neither common nor specific to a category
+    pub unused_fields: Vec<FieldKey>,
+}
+
+// Effective fields of every category of every classification, in schema order
+pub fn effective_fields(schema: &Schema, inheritance: Inheritance) -> Vec<CategoryFields> {
+    schema
+        .classifications
+        .iter()
+        .flat_map(|classification| {
+            classification.categories.iter().map(move |category| {
+                category_fields(schema, classification, &category.key, inheritance)
+            })
+        })
+        .collect()
+}
+
+pub fn category_fields(
+    schema: &Schema,
+    classification: &Classification,
+    category: &CategoryKey,
+    inheritance: Inheritance,
+) -> CategoryFields {
+    let mut fields: Vec<EffectiveField> = vec![];
+    let mut add = |key: &FieldKey, source: Source| {
+        if !fields.iter().any(|field| &field.key == key) {
+            fields.push(EffectiveField {
+                key: key.to_owned(),
+                source,
+            });
+        }
+    };
+    for field in schema.matrix.common.iter() {
+        add(&field.key, Source::Common);
+    }
+    for (key, source) in lineage(classification, category, inheritance) {
+        for specific in schema.matrix.specifics.iter().filter(|specific| {
+            specific.classification == classification.key && &specific.category == key
+        }) {
+            for field in specific.fields.iter() {
+                add(&field.key, source);
+            }
+        }
+    }
+    CategoryFields {
+        category: CategoryRef::new(classification.key.to_owned(), category.to_owned()),
+        fields,
+    }
+}
+
+// The category then its ancestors when inherited, a cycle in the parents stops the walk
+pub fn lineage<'a>(
+    classification: &'a Classification,
+    category: &'a CategoryKey,
+    inheritance: Inheritance,
+) -> Vec<(&'a CategoryKey, Source)> {
+    let mut lineage = vec![];
+    let mut visited = HashSet::new();
+    let mut current = Some(category);
+    while let Some(key) = current.filter(|key| visited.insert(*key)) {
+        let source = match key == category {
+            true => Source::Specific,
+            false => Source::Inherited,
+        };
+        lineage.push((key, source));
+        current = match inheritance {
+            Inheritance::None => None,
+            Inheritance::Parents => classification
+                .category(key)
+                .and_then(|category| category.parent.as_ref()),
+        };
+    }
+    lineage
+}
+
+pub fn coverage(schema: &Schema, inheritance: Inheritance) -> MatrixCoverage {
+    let used = schema
+        .matrix
+        .common
+        .iter()
+        .chain(
+            schema
+                .matrix
+                .specifics
+                .iter()
+                .flat_map(|specific| specific.fields.iter()),
+        )
+        .map(|field| &field.key)
+        .collect::<HashSet<&FieldKey>>();
+    MatrixCoverage {
+        categories_without_specific_fields: effective_fields(schema, inheritance)
+            .into_iter()
+            .filter(|category| !category.has_specific_fields())
+            .map(|category| category.category)
+            .collect(),
+        unused_fields: schema
+            .fields
+            .iter()
+            .filter(|field| !used.contains(&field.key))
+            .map(|field| field.key.to_owned())
+            .collect(),
+    }
+}
+
+// Categories in rows and fields of the schema in columns, a cell holds the source of the
+// field for the category (common, specific or inherited) or is empty
+pub fn pivot(schema: &Schema, inheritance: Inheritance) -> Sheet {
+    let mut headers = vec!["classification".to_owned(), "category".to_owned()];
+    headers.extend(schema.fields.iter().map(|field| field.key.to_string()));
+    let mut rows = vec![headers];
+    for category in effective_fields(schema, inheritance) {
+        let mut row = vec![
+            category.category.classification.to_string(),
+            category.category.category.to_string(),
+        ];
+        row.extend(schema.fields.iter().map(|field| {
+            match category
+                .fields
+                .iter()
+                .find(|effective| effective.key == field.key)
+            {
+                Some(effective) => match effective.source {
+                    Source::Common => "common",
+                    Source::Specific => "specific",
+                    Source::Inherited => "inherited",
+                }
+                .to_owned(),
+                None => String::new(),
+            }
+        }));
+        rows.push(row);
+    }
+    Sheet {
+        name: "Matrix".to_owned(),
+        rows,
+    }
+}
@@ -0,0 +1,273 @@
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{
+    table_diff::{identities, join, tag},
+    table_structs::{Screen, Table},
+};
+
+// A value changed on both sides in different ways. The merged table keeps our value,
+// the editor can then replace it with the one chosen by the user. A list reordered
+// differently on both sides is a conflict whose values are the keys in each order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MergeConflict {
+    pub path: String,
+    pub base: Option<Value>,
+    pub ours: Option<Value>,
+    pub theirs: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MergeResult {
+    pub table: Table,
+    pub conflicts: Vec<MergeConflict>,
+}
+impl MergeResult {
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+// Three-way merge of two versions of a table edited from the same base. Items of
+// collections are matched by key, so edits of different items never conflict.
+pub fn merge(base: &Table, ours: &Table, theirs: &Table) -> Result<MergeResult, Box<dyn Error>> {
+    let mut conflicts = vec![];
+    let merged = merge_value(
+        "",
+        &serde_json::to_value(base)?,
+        &serde_json::to_value(ours)?,
+        &serde_json::to_value(theirs)?,
+        &mut conflicts,
+    );
+    let mut table: Table = serde_json::from_value(merged)?;
+    // Positions are merged one by one, columns inserted on both sides can share a position.
+    // Grids combining the edits of both sides are renumbered.
+    for screen in table.schema.screens.iter_mut() {
+        let grid_of = |table: &Table| {
+            table
+                .schema
+                .screens
+                .iter()
+                .find(|other: &&Screen| other.key == screen.key)
+                .map(|other| other.grid.to_owned())
+        };
+        let grid = Some(screen.grid.to_owned());
+        if grid != grid_of(ours) && grid != grid_of(theirs) {
+            screen.grid.normalize_positions();
+        }
+    }
+    Ok(MergeResult { table, conflicts })
+}
+
+fn as_option(value: &Value) -> Option<Value> {
+    match value {
+        Value::Null => None,
+        _ => Some(value.to_owned()),
+    }
+}
+
+fn conflict(
+    path: &str,
+    base: &Value,
+    ours: &Value,
+    theirs: &Value,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Value {
+    conflicts.push(MergeConflict {
+        path: path.to_owned(),
+        base: as_option(base),
+        ours: as_option(ours),
+        theirs: as_option(theirs),
+    });
+    ours.to_owned()
+}
+
+fn merge_value(
+    path: &str,
+    base: &Value,
+    ours: &Value,
+    theirs: &Value,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Value {
+    if ours == theirs || base == theirs {
+        return ours.to_owned();
+    }
+    if base == ours {
+        return theirs.to_owned();
+    }
+    let empty_map = Map::new();
+    let empty_items = vec![];
+    match (ours, theirs) {
+        (Value::Object(our_map), Value::Object(their_map)) => {
+            let base_map = match base {
+                Value::Object(base_map) => base_map,
+                _ => &empty_map,
+            };
+            match (tag(our_map), tag(their_map)) {
+                (Some(our_tag), Some(their_tag)) if our_tag != their_tag => {
+                    conflict(path, base, ours, theirs, conflicts)
+                }
+                _ => Value::Object(merge_object(path, base_map, our_map, their_map, conflicts)),
+            }
+        }
+        (Value::Array(our_items), Value::Array(their_items)) => {
+            let base_items = match base {
+                Value::Array(base_items) => base_items,
+                _ => &empty_items,
+            };
+            match merge_array(path, base_items, our_items, their_items, conflicts) {
+                Some(merged) => Value::Array(merged),
+                None => conflict(path, base, ours, theirs, conflicts),
+            }
+        }
+        _ => conflict(path, base, ours, theirs, conflicts),
+    }
+}
+
+fn merge_object(
+    path: &str,
+    base: &Map<String, Value>,
+    ours: &Map<String, Value>,
+    theirs: &Map<String, Value>,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Map<String, Value> {
+    let mut merged = Map::new();
+    let names = ours
+        .keys()
+        .chain(theirs.keys().filter(|name| !ours.contains_key(*name)))
+        .collect::<Vec<&String>>();
+    for name in names {
+        let value = merge_value(
+            &join(path, name),
+            base.get(name).unwrap_or(&Value::Null),
+            ours.get(name).unwrap_or(&Value::Null),
+            theirs.get(name).unwrap_or(&Value::Null),
+            conflicts,
+        );
+        merged.insert(name.to_owned(), value);
+    }
+    merged
+}
+
+fn find<'a>(keys: &[String], items: &'a [Value], key: &str) -> Option<&'a Value> {
+    keys.iter()
+        .position(|item_key| item_key == key)
+        .map(|index| &items[index])
+}
+
+// Keys of the items kept from a list, in the order of the list
+fn common_order(keys: &[String], kept: &[String]) -> Vec<String> {
+    keys.iter()
+        .filter(|key| kept.contains(key))
+        .map(|key| key.to_owned())
+        .collect()
+}
+
+// Merges lists of keyed items, None when items can't be matched by key
+fn merge_array(
+    path: &str,
+    base: &[Value],
+    ours: &[Value],
+    theirs: &[Value],
+    conflicts: &mut Vec<MergeConflict>,
+) -> Option<Vec<Value>> {
+    let base_keys = identities(base)?;
+    let our_keys = identities(ours)?;
+    let their_keys = identities(theirs)?;
+    let mut merged_items: Vec<(String, Value)> = vec![];
+    let mut all_keys = base_keys.to_owned();
+    for key in our_keys.iter().chain(their_keys.iter()) {
+        if !all_keys.contains(key) {
+            all_keys.push(key.to_owned());
+        }
+    }
+    for key in all_keys.iter() {
+        let item_path = format!("{}[{}]", path, key);
+        let base_item = find(&base_keys, base, key);
+        let our_item = find(&our_keys, ours, key);
+        let their_item = find(&their_keys, theirs, key);
+        let merged = match (base_item, our_item, their_item) {
+            (_, Some(our_item), Some(their_item)) => Some(merge_value(
+                &item_path,
+                base_item.unwrap_or(&Value::Null),
+                our_item,
+                their_item,
+                conflicts,
+            )),
+            // Deleted on one side, kept only if modified on the other side
+            (Some(base_item), Some(item), None) | (Some(base_item), None, Some(item)) => {
+                if base_item == item {
+                    None
+                } else {
+                    Some(conflict(
+                        &item_path,
+                        base_item,
+                        our_item.unwrap_or(&Value::Null),
+                        their_item.unwrap_or(&Value::Null),
+                        conflicts,
+                    ))
+                    .filter(|merged| !merged.is_null())
+                }
+            }
+            (None, Some(item), None) | (None, None, Some(item)) => Some(item.to_owned()),
+            _ => None,
+        };
+        if let Some(merged) = merged {
+            merged_items.push((key.to_owned(), merged));
+        }
+    }
+    let kept = merged_items
+        .iter()
+        .map(|(key, _)| key.to_owned())
+        .collect::<Vec<String>>();
+    // Both sides reordered the items they share in different ways
+    let shared = kept
+        .iter()
+        .filter(|key| base_keys.contains(key) && our_keys.contains(key) && their_keys.contains(key))
+        .map(|key| key.to_owned())
+        .collect::<Vec<String>>();
+    let orders = [&base_keys, &our_keys, &their_keys].map(|keys| common_order(keys, &shared));
+    if orders[1] != orders[0] && orders[2] != orders[0] && orders[1] != orders[2] {
+        let [base_order, our_order, their_order] = orders.map(Value::from);
+        conflict(path, &base_order, &our_order, &their_order, conflicts);
+    }
+    // Order of the side which reordered the list, ours if both did
+    let base_order = common_order(&base_keys, &kept);
+    let (leading_keys, other_keys) = if common_order(&our_keys, &kept) == base_order {
+        (&their_keys, &our_keys)
+    } else {
+        (&our_keys, &their_keys)
+    };
+    let mut order = common_order(leading_keys, &kept);
+    // Items only known by the other side are inserted after their predecessor
+    for (index, key) in other_keys.iter().enumerate() {
+        if order.contains(key) || !kept.contains(key) {
+            continue;
+        }
+        let position = other_keys[..index]
+            .iter()
+            .rev()
+            .find_map(|previous| order.iter().position(|ordered| ordered == previous))
+            .map(|position| position + 1)
+            .unwrap_or(0);
+        order.insert(position, key.to_owned());
+    }
+    for key in kept.iter() {
+        if !order.contains(key) {
+            order.push(key.to_owned());
+        }
+    }
+    Some(
+        order
+            .iter()
+            .filter_map(|key| {
+                merged_items
+                    .iter()
+                    .find(|(item_key, _)| item_key == key)
+                    .map(|(_, item)| item.to_owned())
+            })
+            .collect(),
+    )
+}
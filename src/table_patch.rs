@@ -0,0 +1,324 @@
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    table_diff::{identities, identity},
+    table_structs::Table,
+};
+
+// Operations are addressed by key path: /schema/fields[COLOR]/options[RED]/title.
+// Items of lists are found by key (lang for locals, priority for rules...), lists
+// without keys (condition groups, conditions) use the position starting at 1.
+// Removed and replaced values are kept so that a patch can be inverted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    Add {
+        path: String,
+        value: Value,
+        // Position in the list, the item is appended when None
+        index: Option<usize>,
+    },
+    Remove {
+        path: String,
+        old_value: Value,
+        // Position of the item in the list, recorded when the patch is applied
+        index: Option<usize>,
+    },
+    Replace {
+        path: String,
+        old_value: Value,
+        value: Value,
+    },
+}
+impl PatchOperation {
+    pub fn path(&self) -> &str {
+        match self {
+            PatchOperation::Add { path, .. } => path,
+            PatchOperation::Remove { path, .. } => path,
+            PatchOperation::Replace { path, .. } => path,
+        }
+    }
+    pub fn invert(&self) -> PatchOperation {
+        match self {
+            PatchOperation::Add { path, value, index } => PatchOperation::Remove {
+                path: path.to_owned(),
+                old_value: value.to_owned(),
+                index: index.to_owned(),
+            },
+            PatchOperation::Remove {
+                path,
+                old_value,
+                index,
+            } => PatchOperation::Add {
+                path: path.to_owned(),
+                value: old_value.to_owned(),
+                index: index.to_owned(),
+            },
+            PatchOperation::Replace {
+                path,
+                old_value,
+                value,
+            } => PatchOperation::Replace {
+                path: path.to_owned(),
+                old_value: value.to_owned(),
+                value: old_value.to_owned(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Patch {
+    pub operations: Vec<PatchOperation>,
+}
+impl Patch {
+    pub fn new() -> Self {
+        Patch { operations: vec![] }
+    }
+    pub fn add_operation(&mut self, operation: PatchOperation) {
+        self.operations.push(operation);
+    }
+    // Operations are applied in order, nothing is applied if one of them fails. Returns
+    // the applied patch, with the positions of the removed items, whose inverse puts them
+    // back in place.
+    pub fn apply(&self, table: &mut Table) -> Result<Patch, Box<dyn Error>> {
+        let mut root = serde_json::to_value(&*table)?;
+        let mut applied = Patch::new();
+        for operation in self.operations.iter() {
+            applied.add_operation(apply_operation(&mut root, operation).map_err(|error| {
+                format!("Can't apply patch on '{}': {}", operation.path(), error)
+            })?);
+        }
+        *table = serde_json::from_value(root)?;
+        Ok(applied)
+    }
+    pub fn invert(&self) -> Patch {
+        Patch {
+            operations: self
+                .operations
+                .iter()
+                .rev()
+                .map(|operation| operation.invert())
+                .collect(),
+        }
+    }
+    // Patch equivalent to this patch followed by the other one. Consecutive replacements
+    // of the same path are merged (e.g. each key stroke of a title in the editor).
+    pub fn compose(&self, other: &Patch) -> Patch {
+        let mut operations: Vec<PatchOperation> = vec![];
+        for operation in self.operations.iter().chain(other.operations.iter()) {
+            if let (
+                Some(PatchOperation::Replace {
+                    path: last_path,
+                    value: last_value,
+                    ..
+                }),
+                PatchOperation::Replace { path, value, .. },
+            ) = (operations.last_mut(), operation)
+            {
+                if last_path == path {
+                    *last_value = value.to_owned();
+                    continue;
+                }
+            }
+            operations.push(operation.to_owned());
+        }
+        Patch { operations }
+    }
+}
+
+// Segment of a key path: name or name[KEY]
+type Segment = (String, Option<String>);
+
+fn parse_path(path: &str) -> Result<Vec<Segment>, Box<dyn Error>> {
+    let mut segments = vec![];
+    let mut current = String::new();
+    let mut depth = 0;
+    for c in path.chars() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            _ => (),
+        }
+        if c == '/' && depth == 0 {
+            if !current.is_empty() {
+                segments.push(current.to_owned());
+            }
+            current.clear();
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        segments.push(current);
+    }
+    if depth != 0 || segments.is_empty() {
+        return Err(format!("invalid path '{}'.", path).into());
+    }
+    segments
+        .iter()
+        .map(|segment| match segment.find('[') {
+            Some(start) if segment.ends_with(']') => Ok((
+                segment[..start].to_owned(),
+                Some(segment[start + 1..segment.len() - 1].to_owned()),
+            )),
+            Some(_) => Err(format!("invalid segment '{}'.", segment).into()),
+            None => Ok((segment.to_owned(), None)),
+        })
+        .collect()
+}
+
+fn field_mut<'a>(value: &'a mut Value, name: &str) -> Result<&'a mut Value, Box<dyn Error>> {
+    value
+        .as_object_mut()
+        .ok_or("not an object.")?
+        .get_mut(name)
+        .ok_or_else(|| format!("unknown field '{}'.", name).into())
+}
+
+// Empty fields are omitted from the JSON, they are created when a value is added
+fn entry_mut<'a>(value: &'a mut Value, name: &str) -> Result<&'a mut Value, Box<dyn Error>> {
+    Ok(value
+        .as_object_mut()
+        .ok_or("not an object.")?
+        .entry(name)
+        .or_insert(Value::Null))
+}
+
+fn remove_field(value: &mut Value, name: &str) {
+    if let Some(map) = value.as_object_mut() {
+        map.shift_remove(name);
+    }
+}
+
+fn position(items: &[Value], key: &str) -> Option<usize> {
+    match identities(items) {
+        Some(keys) => keys.iter().position(|item_key| item_key == key),
+        None => key
+            .parse::<usize>()
+            .ok()
+            .filter(|index| *index >= 1 && *index <= items.len())
+            .map(|index| index - 1),
+    }
+}
+
+fn item_mut<'a>(
+    value: &'a mut Value,
+    name: &str,
+    key: &str,
+) -> Result<&'a mut Value, Box<dyn Error>> {
+    let items = field_mut(value, name)?
+        .as_array_mut()
+        .ok_or_else(|| format!("'{}' is not a list.", name))?;
+    let index = position(items, key).ok_or_else(|| format!("'{}[{}]' not found.", name, key))?;
+    Ok(&mut items[index])
+}
+
+fn check_old_value(current: &Value, old_value: &Value) -> Result<(), Box<dyn Error>> {
+    if current == old_value {
+        Ok(())
+    } else {
+        Err("the current value is not the expected old value.".into())
+    }
+}
+
+fn apply_operation(
+    root: &mut Value,
+    operation: &PatchOperation,
+) -> Result<PatchOperation, Box<dyn Error>> {
+    let mut segments = parse_path(operation.path())?;
+    let (name, key) = segments.pop().ok_or("empty path.")?;
+    let mut parent = root;
+    for (segment_name, segment_key) in segments.iter() {
+        parent = match segment_key {
+            Some(segment_key) => item_mut(parent, segment_name, segment_key)?,
+            None => field_mut(parent, segment_name)?,
+        };
+    }
+    match (operation, key) {
+        (
+            PatchOperation::Replace {
+                old_value, value, ..
+            },
+            key,
+        ) => {
+            let target = match key {
+                Some(key) => item_mut(parent, &name, &key)?,
+                None => field_mut(parent, &name)?,
+            };
+            check_old_value(target, old_value)?;
+            *target = value.to_owned();
+        }
+        (PatchOperation::Add { value, .. }, None) => {
+            let target = entry_mut(parent, &name)?;
+            if !target.is_null() {
+                return Err(format!("'{}' already has a value.", name).into());
+            }
+            *target = value.to_owned();
+        }
+        (PatchOperation::Remove { old_value, .. }, None) => {
+            check_old_value(field_mut(parent, &name)?, old_value)?;
+            remove_field(parent, &name);
+        }
+        (PatchOperation::Add { value, index, .. }, Some(key)) => {
+            let target = entry_mut(parent, &name)?;
+            if target.is_null() {
+                *target = Value::Array(vec![]);
+            }
+            let items = target
+                .as_array_mut()
+                .ok_or_else(|| format!("'{}' is not a list.", name))?;
+            match identity(value) {
+                Some(value_key) => {
+                    if value_key != key {
+                        return Err(format!("the key of the value is not '{}'.", key).into());
+                    }
+                    if position(items, &key).is_some() {
+                        return Err(format!("'{}[{}]' already exists.", name, key).into());
+                    }
+                    let index = index.unwrap_or(items.len()).min(items.len());
+                    items.insert(index, value.to_owned());
+                }
+                None => {
+                    let index = key
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|index| *index >= 1 && *index <= items.len() + 1)
+                        .ok_or_else(|| format!("invalid position '{}'.", key))?;
+                    items.insert(index - 1, value.to_owned());
+                }
+            }
+        }
+        (
+            PatchOperation::Remove {
+                path,
+                old_value,
+                index: expected_index,
+            },
+            Some(key),
+        ) => {
+            let items = field_mut(parent, &name)?
+                .as_array_mut()
+                .ok_or_else(|| format!("'{}' is not a list.", name))?;
+            let index =
+                position(items, &key).ok_or_else(|| format!("'{}[{}]' not found.", name, key))?;
+            if expected_index.is_some_and(|expected_index| expected_index != index) {
+                return Err(format!("'{}[{}]' is not at the expected position.", name, key).into());
+            }
+            check_old_value(&items[index], old_value)?;
+            items.remove(index);
+            if items.is_empty() {
+                remove_field(parent, &name);
+            }
+            return Ok(PatchOperation::Remove {
+                path: path.to_owned(),
+                old_value: old_value.to_owned(),
+                index: Some(index),
+            });
+        }
+    }
+    Ok(operation.to_owned())
+}
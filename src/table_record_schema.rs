@@ -0,0 +1,429 @@
+use std::error::Error;
+
+use serde_json::{json, Map, Value};
+
+use serde::{Deserialize, Serialize};
+
+use crate::table_structs::{
+    BarcodeType, CategoryKey, Classification, ConditionalFormattingKey, Control, DataType, Field,
+    LevelKey, SelectOption, Status, Table,
+};
+
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+// JSON Schema (draft 2020-12) of one product record of a level. The record is an object
+// whose properties are the keys of the identifiers, classifications and fields:
+//   { "EAN_13": "3700000000000", "TYPOLOGY": "PHONES", "COLOR": "RED", "STORAGE_GB": 64 }
+// The statuses of a conditional formatting are alternative outcomes, a record matching the
+// rules of a low priority status is still accepted by the table. So the controls of one
+// status only are mapped to keywords: the given status, or by default the status with the
+// highest priority (the lowest Priority) of each conditional formatting of the level, the
+// one blocking the products. Controls JSON Schema can't express, rules comparing fields and
+// conditions are ignored. Decimal places are only described in a $comment: validators
+// compute multipleOf with floats and reject 0.07 as a multiple of 0.01.
+pub fn record_schema(
+    table: &Table,
+    level: &LevelKey,
+    status: Option<&StatusRef>,
+) -> Result<Value, Box<dyn Error>> {
+    let level = table
+        .schema
+        .level(level)
+        .ok_or_else(|| format!("Unknown level '{}'.", level))?;
+    let conditional_formattings = table
+        .schema
+        .conditional_formattings
+        .iter()
+        .filter(|conditional_formatting| conditional_formatting.level == level.key);
+    let statuses: Vec<&Status> = match status {
+        Some(status_ref) => {
+            let conditional_formatting = table
+                .schema
+                .conditional_formattings
+                .iter()
+                .find(|item| item.key == status_ref.conditional_formatting)
+                .ok_or_else(|| {
+                    format!(
+                        "Unknown conditional formatting '{}'.",
+                        status_ref.conditional_formatting
+                    )
+                })?;
+            if conditional_formatting.level != level.key {
+                return Err(format!(
+                    "Conditional formatting '{}' is not of level '{}'.",
+                    conditional_formatting.key, level.key
+                )
+                .into());
+            }
+            let status = conditional_formatting
+                .statuses
+                .iter()
+                .find(|item| item.key == status_ref.status)
+                .ok_or_else(|| {
+                    format!(
+                        "Unknown status '{}' in conditional formatting '{}'.",
+                        status_ref.status, conditional_formatting.key
+                    )
+                })?;
+            vec![status]
+        }
+        None => conditional_formattings
+            .filter_map(|conditional_formatting| {
+                conditional_formatting
+                    .statuses
+                    .iter()
+                    .min_by_key(|status| status.priority)
+            })
+            .collect(),
+    };
+    let mut properties = Map::new();
+    let mut required = vec![];
+    for identifier in table
+        .schema
+        .identifiers
+        .iter()
+        .filter(|identifier| identifier.level == level.key)
+    {
+        properties.insert(
+            identifier.key.to_string(),
+            described(
+                json!({ "type": "string" }),
+                &identifier.title,
+                &identifier.description,
+            ),
+        );
+        required.push(identifier.key.to_string());
+    }
+    for classification in table.schema.classifications.iter() {
+        properties.insert(
+            classification.key.to_string(),
+            described(
+                json!({ "type": "string", "enum": category_keys(classification, false) }),
+                &classification.title,
+                &classification.description,
+            ),
+        );
+    }
+    for field in table
+        .schema
+        .fields
+        .iter()
+        .filter(|field| field.level == level.key)
+    {
+        properties.insert(
+            field.key.to_string(),
+            described(field_schema(field), &field.title, &field.description),
+        );
+    }
+    let mut record = RecordSchema {
+        properties,
+        required,
+        dependent_required: Map::new(),
+    };
+    let mut conditionals = vec![];
+    for status in statuses {
+        for rules in status.rules.common.iter() {
+            record.apply_controls(table, &rules.key, &rules.controls);
+        }
+        // Rules of a category also apply to its sub categories
+        for specific in status.rules.specifics.iter() {
+            let categories = match table
+                .schema
+                .classifications
+                .iter()
+                .find(|classification| classification.key == specific.classification)
+            {
+                Some(classification) => descendant_keys(classification, &specific.category),
+                None => continue,
+            };
+            let mut specific_record = RecordSchema::default();
+            for attribute in specific.attributes.iter() {
+                if let Some(property) = record.properties.get(attribute.key.as_str()) {
+                    // The type is kept to know how to apply the controls
+                    specific_record.properties.insert(
+                        attribute.key.to_string(),
+                        json!({ "type": property["type"] }),
+                    );
+                    specific_record.apply_controls(table, &attribute.key, &attribute.controls);
+                }
+            }
+            specific_record.properties.retain(|_, property| {
+                property
+                    .as_object()
+                    .is_some_and(|property| property.len() > 1)
+            });
+            for property in specific_record.properties.values_mut() {
+                if let Some(property) = property.as_object_mut() {
+                    property.shift_remove("type");
+                }
+            }
+            let then = specific_record.to_value();
+            if then != json!({}) {
+                let mut condition = Map::new();
+                condition.insert(
+                    specific.classification.to_string(),
+                    json!({ "enum": categories }),
+                );
+                conditionals.push(json!({
+                    "if": { "properties": condition, "required": [specific.classification] },
+                    "then": then,
+                }));
+            }
+        }
+    }
+    let mut schema = json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "title": format!("{} - {}", table.title, level.title),
+        "type": "object",
+    });
+    if let (Some(schema), Value::Object(record)) = (schema.as_object_mut(), record.to_value()) {
+        schema.extend(record);
+        if !conditionals.is_empty() {
+            schema.insert("allOf".to_owned(), Value::Array(conditionals));
+        }
+        schema.insert("additionalProperties".to_owned(), json!(false));
+    }
+    Ok(schema)
+}
+
+// A status, identified by its conditional formatting and its key
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusRef {
+    pub conditional_formatting: ConditionalFormattingKey,
+    pub status: String,
+}
+impl StatusRef {
+    pub fn new(conditional_formatting: ConditionalFormattingKey, status: &str) -> Self {
+        StatusRef {
+            conditional_formatting,
+            status: status.to_owned(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct RecordSchema {
+    properties: Map<String, Value>,
+    required: Vec<String>,
+    dependent_required: Map<String, Value>,
+}
+impl RecordSchema {
+    // Empty parts are omitted
+    fn to_value(&self) -> Value {
+        let mut schema = Map::new();
+        if !self.properties.is_empty() {
+            schema.insert(
+                "properties".to_owned(),
+                Value::Object(self.properties.to_owned()),
+            );
+        }
+        if !self.required.is_empty() {
+            schema.insert("required".to_owned(), json!(self.required));
+        }
+        if !self.dependent_required.is_empty() {
+            schema.insert(
+                "dependentRequired".to_owned(),
+                Value::Object(self.dependent_required.to_owned()),
+            );
+        }
+        Value::Object(schema)
+    }
+    fn apply_controls(&mut self, table: &Table, key: &str, controls: &[Control]) {
+        let property = match self.properties.get_mut(key) {
+            Some(property) => property,
+            None => return,
+        };
+        // The number of values only constrains lists of options, the decimal places only
+        // single numbers
+        let is_list = property["type"] == "array";
+        for control in controls.iter() {
+            match control {
+                Control::RuleRequired if !self.required.iter().any(|item| item == key) => {
+                    self.required.push(key.to_owned())
+                }
+                Control::RuleIsLeaf => {
+                    if let Some(classification) = table
+                        .schema
+                        .classifications
+                        .iter()
+                        .find(|classification| classification.key == key)
+                    {
+                        property["enum"] = json!(category_keys(classification, true));
+                    }
+                }
+                Control::RuleMinLength { min } => constrain(property, "minLength", json!(min)),
+                Control::RuleMaxLength { max } => constrain(property, "maxLength", json!(max)),
+                Control::RuleRegex { regex } => property["pattern"] = json!(regex),
+                Control::RuleBarcode { barcode_type } => {
+                    if let Some(pattern) = barcode_pattern(barcode_type) {
+                        property["pattern"] = json!(pattern);
+                    }
+                }
+                Control::RuleLessThan { value } => {
+                    constrain(property, "exclusiveMaximum", json!(value))
+                }
+                Control::RuleGreaterThan { value } => {
+                    constrain(property, "exclusiveMinimum", json!(value))
+                }
+                Control::RuleLessThanOrEqual { value } => {
+                    constrain(property, "maximum", json!(value))
+                }
+                Control::RuleGreaterThanOrEqual { value } => {
+                    constrain(property, "minimum", json!(value))
+                }
+                Control::RuleDecimalPlaces { precision } if !is_list => {
+                    property["$comment"] = decimal_places(*precision)
+                }
+                Control::RuleMinValues { min } if is_list => {
+                    constrain(property, "minItems", json!(min))
+                }
+                Control::RuleMaxValues { max } if is_list => {
+                    constrain(property, "maxItems", json!(max))
+                }
+                Control::RuleRequiredIfAnotherFieldIsNotEmpty { field } => {
+                    let dependents = self
+                        .dependent_required
+                        .entry(field.to_owned())
+                        .or_insert(json!([]));
+                    if let Some(dependents) = dependents.as_array_mut() {
+                        if !dependents.contains(&json!(key)) {
+                            dependents.push(json!(key));
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+// Title and description come first to keep the schema readable
+fn described(schema: Value, title: &str, description: &Option<String>) -> Value {
+    let mut described = Map::new();
+    described.insert("title".to_owned(), json!(title));
+    if let Some(description) = description {
+        described.insert("description".to_owned(), json!(description));
+    }
+    if let Value::Object(schema) = schema {
+        described.extend(schema);
+    }
+    Value::Object(described)
+}
+
+fn option_keys(options: &Option<Vec<SelectOption>>) -> Vec<String> {
+    options
+        .iter()
+        .flatten()
+        .map(|option| option.key.to_string())
+        .collect()
+}
+
+// Keys of the categories, only the ones without sub categories when leaf is true
+fn category_keys(classification: &Classification, leaf: bool) -> Vec<String> {
+    classification
+        .categories
+        .iter()
+        .filter(|category| {
+            !leaf
+                || !classification
+                    .categories
+                    .iter()
+                    .any(|child| child.parent.as_ref() == Some(&category.key))
+        })
+        .map(|category| category.key.to_string())
+        .collect()
+}
+
+fn descendant_keys(classification: &Classification, category: &CategoryKey) -> Vec<CategoryKey> {
+    let mut keys = vec![category.to_owned()];
+    let mut index = 0;
+    while index < keys.len() {
+        for child in classification.categories.iter() {
+            if child.parent.as_ref() == Some(&keys[index]) && !keys.contains(&child.key) {
+                keys.push(child.key.to_owned());
+            }
+        }
+        index += 1;
+    }
+    keys
+}
+
+fn decimal_places(precision: usize) -> Value {
+    json!(format!("Maximum number of decimal places: {}.", precision))
+}
+
+// Quantified options are lists of { "key": "RED", "quantity": 2, "comment": "..." }
+fn field_schema(field: &Field) -> Value {
+    let options = json!({ "type": "string", "enum": option_keys(&field.options) });
+    match field.data_type {
+        DataType::SingleLineText | DataType::LongText | DataType::HtmlText => {
+            json!({ "type": "string" })
+        }
+        DataType::Number => match field.precision {
+            Some(0) => json!({ "type": "integer" }),
+            Some(precision) => json!({ "type": "number", "$comment": decimal_places(precision) }),
+            None => json!({ "type": "number" }),
+        },
+        DataType::SingleSelect => options,
+        DataType::MultipleSelect => json!({
+            "type": "array",
+            "items": options,
+            "uniqueItems": true,
+        }),
+        DataType::MultipleSelectQuantified => json!({
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": { "key": options, "quantity": { "type": "number" } },
+                "required": ["key", "quantity"],
+                "additionalProperties": false,
+            },
+        }),
+        DataType::MultipleSelectQuantifiedWithComments => json!({
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "key": options,
+                    "quantity": { "type": "number" },
+                    "comment": { "type": "string" },
+                },
+                "required": ["key", "quantity"],
+                "additionalProperties": false,
+            },
+        }),
+        DataType::Date => json!({ "type": "string", "format": "date" }),
+        DataType::DateTime => json!({ "type": "string", "format": "date-time" }),
+        DataType::Image | DataType::Attachment => json!({ "type": "string", "format": "uri" }),
+    }
+}
+
+fn barcode_pattern(barcode_type: &BarcodeType) -> Option<&'static str> {
+    match barcode_type {
+        BarcodeType::Ean8 => Some("^[0-9]{8}$"),
+        BarcodeType::Upca => Some("^[0-9]{12}$"),
+        BarcodeType::Ean13 => Some("^[0-9]{13}$"),
+        BarcodeType::Gtin14 => Some("^[0-9]{14}$"),
+        BarcodeType::Unknown(_) => None,
+    }
+}
+
+// Sets a keyword, keeping the most restrictive bound when several controls set it
+fn constrain(schema: &mut Value, keyword: &str, value: Value) {
+    let lower_bound = matches!(
+        keyword,
+        "minLength" | "minimum" | "exclusiveMinimum" | "minItems"
+    );
+    let keep_current = match (
+        schema.get(keyword).and_then(|current| current.as_f64()),
+        value.as_f64(),
+    ) {
+        (Some(current), Some(new)) if lower_bound => current >= new,
+        (Some(current), Some(new)) => current <= new,
+        _ => false,
+    };
+    if !keep_current {
+        schema[keyword] = value;
+    }
+}
@@ -0,0 +1,1149 @@
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    table_structs::{
+        Action, AttributeType, CategoryKey, Classification, ClassificationKey,
+        CommonAttributeRules, CommonColumn, CommonSection, Condition, ConditionGroup,
+        ConditionalFormatting, Control, Field, FieldKey, Formula, GridSpecific, Identifier, Level,
+        Matrix, MatrixField, MatrixSpecific, Rule, Rules, Schema, Screen, ScreenGrid, Section,
+        SpecificAttributeRules, SpecificRules, SpecificSection, Table, UseSuffix,
+    },
+    table_visitor::{
+        control_xpath, keyed, specific, walk_classification_mut, walk_common_attribute_rules_mut,
+        walk_common_section_mut, walk_conditional_formatting_mut, walk_control_mut, walk_field_mut,
+        walk_formula_mut, walk_grid_specific_mut, walk_identifier_mut, walk_matrix_mut,
+        walk_matrix_specific_mut, walk_mut, walk_rule_mut, walk_rules_mut, walk_schema_mut,
+        walk_screen_grid_mut, walk_screen_mut, walk_specific_attribute_rules_mut,
+        walk_specific_rules_mut, VisitorMut,
+    },
+};
+
+// Kind of key to refactor. Keys of categories, options and suffixes are only unique
+// inside their classification or field, so the owner key is part of the kind.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum KeyKind {
+    Level,
+    Identifier,
+    Classification,
+    Category { classification: ClassificationKey },
+    Field,
+    Option { field: FieldKey },
+    Suffix { field: FieldKey },
+    ConditionalFormatting,
+    Section,
+}
+impl KeyKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            KeyKind::Level => "Level",
+            KeyKind::Identifier => "Identifier",
+            KeyKind::Classification => "Classification",
+            KeyKind::Category { .. } => "Category",
+            KeyKind::Field => "Field",
+            KeyKind::Option { .. } => "Option",
+            KeyKind::Suffix { .. } => "Suffix",
+            KeyKind::ConditionalFormatting => "Conditional-Formatting",
+            KeyKind::Section => "Section",
+        }
+    }
+    fn matches_attribute_type(&self, attribute_type: &AttributeType) -> bool {
+        matches!(
+            (self, attribute_type),
+            (KeyKind::Identifier, AttributeType::Identifier)
+                | (KeyKind::Classification, AttributeType::Classification)
+                | (KeyKind::Field, AttributeType::Field)
+        )
+    }
+    // Identifiers, classifications and fields can be used as condition or template sources
+    fn is_source(&self) -> bool {
+        matches!(
+            self,
+            KeyKind::Identifier | KeyKind::Classification | KeyKind::Field
+        )
+    }
+}
+
+// One value rewritten by a refactoring. The xpath points to the element in the
+// table as it was before the refactoring.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyChange {
+    pub xpath: String,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+impl Table {
+    pub fn rename_key(
+        &mut self,
+        kind: KeyKind,
+        old: &str,
+        new: &str,
+    ) -> Result<Vec<KeyChange>, Box<dyn Error>> {
+        if new.trim().is_empty() {
+            return Err(format!("{} key can't be renamed to an empty key.", kind.label()).into());
+        }
+        if old == new {
+            return Ok(vec![]);
+        }
+        if !self.contains_key(&kind, old) {
+            return Err(format!("{} '{}' does not exist.", kind.label(), old).into());
+        }
+        if self.contains_key(&kind, new) {
+            return Err(format!("{} '{}' already exists.", kind.label(), new).into());
+        }
+        // Sources are only renamed when they point to the renamed key
+        let renames_sources = self
+            .schema
+            .source_type(old)
+            .is_some_and(|attribute_type| kind.matches_attribute_type(&attribute_type));
+        let mut renamer = KeyRenamer {
+            kind,
+            renames_sources,
+            old: old.to_owned(),
+            new: new.to_owned(),
+            changes: vec![],
+        };
+        walk_mut(&mut renamer, self);
+        Ok(renamer.changes)
+    }
+    pub fn contains_key(&self, kind: &KeyKind, key: &str) -> bool {
+        let schema = &self.schema;
+        match kind {
+            KeyKind::Level => schema.levels.iter().any(|level| level.key == key),
+            KeyKind::Identifier => schema
+                .identifiers
+                .iter()
+                .any(|identifier| identifier.key == key),
+            KeyKind::Classification => schema
+                .classifications
+                .iter()
+                .any(|classification| classification.key == key),
+            KeyKind::Category { classification } => schema
+                .classifications
+                .iter()
+                .filter(|c| &c.key == classification)
+                .any(|c| c.categories.iter().any(|category| category.key == key)),
+            KeyKind::Field => schema.fields.iter().any(|field| field.key == key),
+            KeyKind::Option { field } => schema
+                .fields
+                .iter()
+                .filter(|f| &f.key == field)
+                .filter_map(|f| f.options.as_ref())
+                .any(|options| options.iter().any(|option| option.key == key)),
+            KeyKind::Suffix { field } => schema
+                .fields
+                .iter()
+                .filter(|f| &f.key == field)
+                .filter_map(|f| f.suffixes.as_ref())
+                .any(|suffixes| suffixes.iter().any(|suffix| suffix.key == key)),
+            KeyKind::ConditionalFormatting => schema
+                .conditional_formattings
+                .iter()
+                .any(|conditional_formatting| conditional_formatting.key == key),
+            KeyKind::Section => schema.sections.iter().any(|section| section.key == key),
+        }
+    }
+}
+
+struct KeyRenamer {
+    kind: KeyKind,
+    renames_sources: bool,
+    old: String,
+    new: String,
+    changes: Vec<KeyChange>,
+}
+impl KeyRenamer {
+    // Keys and plain values (condition values, option lists) are renamed the same way
+    fn rename<T: AsRef<str> + From<String>>(&mut self, value: &mut T, xpath: String) {
+        if value.as_ref() == self.old {
+            *value = T::from(self.new.to_owned());
+            self.changes.push(KeyChange {
+                xpath,
+                old_value: self.old.to_owned(),
+                new_value: self.new.to_owned(),
+            });
+        }
+    }
+    fn rename_if<T: AsRef<str> + From<String>>(
+        &mut self,
+        condition: bool,
+        value: &mut T,
+        xpath: String,
+    ) {
+        if condition {
+            self.rename(value, xpath)
+        }
+    }
+    // Templates reference sources with {{source("KEY")}} or {{source("KEY","key")}}
+    fn rename_template(&mut self, template: &mut String, xpath: String) {
+        let old_pattern = format!("source(\"{}\"", self.old);
+        if template.contains(&old_pattern) {
+            let new_template = template.replace(&old_pattern, &format!("source(\"{}\"", self.new));
+            self.changes.push(KeyChange {
+                xpath,
+                old_value: template.to_owned(),
+                new_value: new_template.to_owned(),
+            });
+            *template = new_template;
+        }
+    }
+    fn is_option_of(&self, field_key: &str) -> bool {
+        matches!(&self.kind, KeyKind::Option { field } if field == field_key)
+    }
+    fn is_category_of(&self, classification_key: &str) -> bool {
+        matches!(&self.kind, KeyKind::Category { classification } if classification == classification_key)
+    }
+    fn rename_specific(
+        &mut self,
+        classification: &mut ClassificationKey,
+        category: &mut CategoryKey,
+        xpath: &str,
+    ) {
+        let is_category = self.is_category_of(classification);
+        self.rename_if(is_category, category, format!("{}/@category", xpath));
+        let is_classification = self.kind == KeyKind::Classification;
+        self.rename_if(
+            is_classification,
+            classification,
+            format!("{}/@classification", xpath),
+        );
+    }
+}
+impl VisitorMut for KeyRenamer {
+    fn visit_level_mut(&mut self, level: &mut Level, xpath: &str) {
+        let is_level = self.kind == KeyKind::Level;
+        self.rename_if(is_level, &mut level.key, format!("{}/@key", xpath));
+    }
+    fn visit_identifier_mut(&mut self, identifier: &mut Identifier, xpath: &str) {
+        let is_identifier = self.kind == KeyKind::Identifier;
+        self.rename_if(
+            is_identifier,
+            &mut identifier.key,
+            format!("{}/@key", xpath),
+        );
+        let is_level = self.kind == KeyKind::Level;
+        self.rename_if(is_level, &mut identifier.level, format!("{}/@level", xpath));
+        walk_identifier_mut(self, identifier, xpath);
+    }
+    fn visit_classification_mut(&mut self, classification: &mut Classification, xpath: &str) {
+        if self.is_category_of(&classification.key) {
+            for category in classification.categories.iter_mut() {
+                let category_xpath =
+                    format!("{}/Categories/Category[@key='{}']", xpath, category.key);
+                self.rename(&mut category.key, format!("{}/@key", category_xpath));
+                if let Some(parent) = &mut category.parent {
+                    self.rename(parent, format!("{}/@parent", category_xpath));
+                }
+            }
+        }
+        let is_classification = self.kind == KeyKind::Classification;
+        self.rename_if(
+            is_classification,
+            &mut classification.key,
+            format!("{}/@key", xpath),
+        );
+        walk_classification_mut(self, classification, xpath);
+    }
+    fn visit_field_mut(&mut self, field: &mut Field, xpath: &str) {
+        if self.is_option_of(&field.key) {
+            if let Some(options) = &mut field.options {
+                for option in options.iter_mut() {
+                    let option_xpath =
+                        format!("{}/Options/Option[@key='{}']/@key", xpath, option.key);
+                    self.rename(&mut option.key, option_xpath);
+                }
+            }
+        }
+        if matches!(&self.kind, KeyKind::Suffix { field: key } if key == &field.key) {
+            if let Some(suffixes) = &mut field.suffixes {
+                for suffix in suffixes.iter_mut() {
+                    let suffix_xpath =
+                        format!("{}/Suffixes/Suffix[@key='{}']/@key", xpath, suffix.key);
+                    self.rename(&mut suffix.key, suffix_xpath);
+                }
+            }
+        }
+        let is_field = self.kind == KeyKind::Field;
+        self.rename_if(is_field, &mut field.key, format!("{}/@key", xpath));
+        let is_level = self.kind == KeyKind::Level;
+        self.rename_if(is_level, &mut field.level, format!("{}/@level", xpath));
+        walk_field_mut(self, field, xpath);
+    }
+    fn visit_formula_mut(&mut self, formula: &mut Formula, xpath: &str) {
+        if formula.attribute_type == AttributeType::Field && self.is_option_of(&formula.key) {
+            for rule in formula.rules.iter_mut() {
+                if let Action::SetSelectableOptions { values } = &mut rule.action {
+                    let action_xpath =
+                        format!("{}/Rule[@priority='{}']/Action/Value", xpath, rule.priority);
+                    for value in values.iter_mut() {
+                        self.rename(value, action_xpath.to_owned());
+                    }
+                }
+            }
+        }
+        let is_attribute = self.kind.matches_attribute_type(&formula.attribute_type);
+        self.rename_if(is_attribute, &mut formula.key, format!("{}/@key", xpath));
+        walk_formula_mut(self, formula, xpath);
+    }
+    fn visit_action_mut(&mut self, action: &mut Action, xpath: &str) {
+        if !self.renames_sources {
+            return;
+        }
+        match action {
+            Action::SetTextTemplate { value, .. } | Action::SetNumberTemplate { value, .. } => {
+                self.rename_template(value, format!("{}/Template", xpath))
+            }
+            Action::SetSelectableOptions { .. } => (),
+        }
+    }
+    fn visit_condition_mut(&mut self, condition: &mut Condition, xpath: &str) {
+        let source = condition.source().to_owned();
+        let renames_values = self.is_option_of(&source) || self.is_category_of(&source);
+        match condition {
+            Condition::Equals {
+                value, use_suffix, ..
+            } => {
+                let renames_suffix = matches!(&self.kind, KeyKind::Suffix { field } if field == &source)
+                    && use_suffix == &Some(UseSuffix::SuffixKey);
+                let renames_value =
+                    (renames_values && use_suffix != &Some(UseSuffix::SuffixKey)) || renames_suffix;
+                self.rename_if(renames_value, value, format!("{}/Value", xpath));
+            }
+            Condition::NotEquals { value, .. } => {
+                self.rename_if(renames_values, value, format!("{}/Value", xpath))
+            }
+            Condition::In { values, .. } | Condition::NotIn { values, .. } => {
+                for value in values.iter_mut() {
+                    self.rename_if(renames_values, value, format!("{}/Value", xpath));
+                }
+            }
+            _ => (),
+        }
+        let renames_source = self.renames_sources;
+        self.rename_if(
+            renames_source,
+            condition.source_mut(),
+            format!("{}/@source", xpath),
+        );
+    }
+    fn visit_matrix_field_mut(&mut self, matrix_field: &mut MatrixField, xpath: &str) {
+        let is_field = self.kind == KeyKind::Field;
+        self.rename_if(is_field, &mut matrix_field.key, format!("{}/@key", xpath));
+    }
+    fn visit_matrix_specific_mut(&mut self, matrix_specific: &mut MatrixSpecific, xpath: &str) {
+        self.rename_specific(
+            &mut matrix_specific.classification,
+            &mut matrix_specific.category,
+            xpath,
+        );
+        walk_matrix_specific_mut(self, matrix_specific, xpath);
+    }
+    fn visit_conditional_formatting_mut(
+        &mut self,
+        conditional_formatting: &mut ConditionalFormatting,
+        xpath: &str,
+    ) {
+        let is_conditional_formatting = self.kind == KeyKind::ConditionalFormatting;
+        self.rename_if(
+            is_conditional_formatting,
+            &mut conditional_formatting.key,
+            format!("{}/@key", xpath),
+        );
+        let is_level = self.kind == KeyKind::Level;
+        self.rename_if(
+            is_level,
+            &mut conditional_formatting.level,
+            format!("{}/@level", xpath),
+        );
+        walk_conditional_formatting_mut(self, conditional_formatting, xpath);
+    }
+    fn visit_common_attribute_rules_mut(&mut self, rules: &mut CommonAttributeRules, xpath: &str) {
+        let is_attribute = self.kind.matches_attribute_type(&rules.attribute_type);
+        self.rename_if(is_attribute, &mut rules.key, format!("{}/@key", xpath));
+        walk_common_attribute_rules_mut(self, rules, xpath);
+    }
+    fn visit_specific_rules_mut(&mut self, rules: &mut SpecificRules, xpath: &str) {
+        self.rename_specific(&mut rules.classification, &mut rules.category, xpath);
+        walk_specific_rules_mut(self, rules, xpath);
+    }
+    fn visit_specific_attribute_rules_mut(
+        &mut self,
+        rules: &mut SpecificAttributeRules,
+        xpath: &str,
+    ) {
+        let is_field = self.kind == KeyKind::Field;
+        self.rename_if(is_field, &mut rules.key, format!("{}/@key", xpath));
+        walk_specific_attribute_rules_mut(self, rules, xpath);
+    }
+    fn visit_control_mut(&mut self, control: &mut Control, xpath: &str) {
+        match control {
+            Control::RuleRequiredIfAnotherFieldHasOptions { field, options }
+                if self.is_option_of(field) =>
+            {
+                for option in options.iter_mut() {
+                    let option_xpath = format!("{}/Option[@key='{}']/@key", xpath, option.key);
+                    self.rename(&mut option.key, option_xpath);
+                }
+            }
+            Control::RuleRequiredIfAnotherFieldIsEqualTo { field, value } => {
+                let is_option = self.is_option_of(field);
+                self.rename_if(is_option, value, format!("{}/@value", xpath));
+            }
+            _ => (),
+        }
+        let is_field = self.kind == KeyKind::Field;
+        if let Some(field) = control.field_mut() {
+            self.rename_if(is_field, field, format!("{}/@field", xpath));
+        }
+        walk_control_mut(self, control, xpath);
+    }
+    fn visit_section_mut(&mut self, section: &mut Section, xpath: &str) {
+        let is_section = self.kind == KeyKind::Section;
+        self.rename_if(is_section, &mut section.key, format!("{}/@key", xpath));
+    }
+    fn visit_screen_mut(&mut self, screen: &mut Screen, xpath: &str) {
+        let is_level = self.kind == KeyKind::Level;
+        self.rename_if(is_level, &mut screen.level, format!("{}/@level", xpath));
+        walk_screen_mut(self, screen, xpath);
+    }
+    fn visit_common_section_mut(&mut self, section: &mut CommonSection, xpath: &str) {
+        let is_section = self.kind == KeyKind::Section;
+        self.rename_if(is_section, &mut section.key, format!("{}/@key", xpath));
+        walk_common_section_mut(self, section, xpath);
+    }
+    fn visit_common_column_mut(&mut self, column: &mut CommonColumn, xpath: &str) {
+        let xpath = format!("{}/@key", xpath);
+        match (&self.kind, column) {
+            (KeyKind::Identifier, CommonColumn::ColumnIdentifier(column)) => {
+                self.rename(&mut column.key, xpath)
+            }
+            (KeyKind::Classification, CommonColumn::ColumnClassification(column)) => {
+                self.rename(&mut column.key, xpath)
+            }
+            (KeyKind::ConditionalFormatting, CommonColumn::ColumnConditionalFormatting(column)) => {
+                self.rename(&mut column.key, xpath)
+            }
+            (KeyKind::Field, CommonColumn::ColumnField(column)) => {
+                self.rename(&mut column.key, xpath)
+            }
+            _ => (),
+        }
+    }
+    fn visit_grid_specific_mut(&mut self, grid_specific: &mut GridSpecific, xpath: &str) {
+        self.rename_specific(
+            &mut grid_specific.classification,
+            &mut grid_specific.category,
+            xpath,
+        );
+        walk_grid_specific_mut(self, grid_specific, xpath);
+    }
+    fn visit_specific_section_mut(&mut self, section: &mut SpecificSection, xpath: &str) {
+        let is_section = self.kind == KeyKind::Section;
+        self.rename_if(is_section, &mut section.key, format!("{}/@key", xpath));
+        let is_field = self.kind == KeyKind::Field;
+        for column in section.columns.iter_mut() {
+            let column_xpath = format!("{}/Column-Field[@key='{}']/@key", xpath, column.key);
+            self.rename_if(is_field, &mut column.key, column_xpath);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DeleteMode {
+    // Fails if anything still references the key
+    Refuse,
+    // Removes the key and every element referencing it
+    Cascade,
+}
+
+// One element removed (or that would be removed) by a cascade deletion. The xpath
+// points to the element in the table as it was before the deletion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeleteImpact {
+    pub xpath: String,
+    pub reason: String,
+}
+
+impl Table {
+    pub fn delete_impact(
+        &self,
+        kind: &KeyKind,
+        key: &str,
+    ) -> Result<Vec<DeleteImpact>, Box<dyn Error>> {
+        if !self.contains_key(kind, key) {
+            return Err(format!("{} '{}' does not exist.", kind.label(), key).into());
+        }
+        let mut table = self.clone();
+        Ok(table.cascade_delete(kind, key))
+    }
+    pub fn delete_key(
+        &mut self,
+        kind: KeyKind,
+        key: &str,
+        mode: DeleteMode,
+    ) -> Result<Vec<DeleteImpact>, Box<dyn Error>> {
+        let impacts = self.delete_impact(&kind, key)?;
+        if mode == DeleteMode::Refuse && !impacts.is_empty() {
+            return Err(format!(
+                "{} '{}' can't be deleted, {} element(s) depend on it.",
+                kind.label(),
+                key,
+                impacts.len()
+            )
+            .into());
+        }
+        self.cascade_delete(&kind, key);
+        Ok(impacts)
+    }
+    fn cascade_delete(&mut self, kind: &KeyKind, key: &str) -> Vec<DeleteImpact> {
+        let mut deleter = KeyDeleter {
+            targets: delete_targets(self, kind, key),
+            impacts: vec![],
+        };
+        walk_mut(&mut deleter, self);
+        deleter.impacts
+    }
+}
+
+struct DeleteTarget {
+    kind: KeyKind,
+    key: String,
+    // Reason of the parent target for keys deleted in cascade, None for the deleted key
+    cause: Option<String>,
+}
+impl DeleteTarget {
+    fn reason(&self) -> String {
+        format!("{} '{}' is deleted.", self.kind.label(), self.key)
+    }
+}
+
+// Deleting a level deletes its identifiers, fields and conditional formattings,
+// deleting a category deletes its sub-categories.
+fn delete_targets(table: &Table, kind: &KeyKind, key: &str) -> Vec<DeleteTarget> {
+    let root = DeleteTarget {
+        kind: kind.to_owned(),
+        key: key.to_owned(),
+        cause: None,
+    };
+    let cause = Some(root.reason());
+    let mut targets = vec![root];
+    let schema = &table.schema;
+    match kind {
+        KeyKind::Level => {
+            for identifier in schema.identifiers.iter().filter(|i| i.level == key) {
+                targets.push(DeleteTarget {
+                    kind: KeyKind::Identifier,
+                    key: identifier.key.to_string(),
+                    cause: cause.to_owned(),
+                });
+            }
+            for field in schema.fields.iter().filter(|f| f.level == key) {
+                targets.push(DeleteTarget {
+                    kind: KeyKind::Field,
+                    key: field.key.to_string(),
+                    cause: cause.to_owned(),
+                });
+            }
+            for conditional_formatting in schema
+                .conditional_formattings
+                .iter()
+                .filter(|c| c.level == key)
+            {
+                targets.push(DeleteTarget {
+                    kind: KeyKind::ConditionalFormatting,
+                    key: conditional_formatting.key.to_string(),
+                    cause: cause.to_owned(),
+                });
+            }
+        }
+        KeyKind::Category { classification } => {
+            let categories = schema
+                .classifications
+                .iter()
+                .filter(|c| &c.key == classification)
+                .flat_map(|c| c.categories.iter())
+                .collect::<Vec<_>>();
+            let mut index = 0;
+            while index < targets.len() {
+                let parent_key = targets[index].key.to_owned();
+                let parent_reason = targets[index].reason();
+                for category in categories.iter() {
+                    if category.parent.as_deref() == Some(parent_key.as_str())
+                        && !targets.iter().any(|t| t.key == category.key)
+                    {
+                        targets.push(DeleteTarget {
+                            kind: kind.to_owned(),
+                            key: category.key.to_string(),
+                            cause: Some(parent_reason.to_owned()),
+                        });
+                    }
+                }
+                index += 1;
+            }
+        }
+        _ => (),
+    }
+    targets
+}
+
+struct KeyDeleter {
+    targets: Vec<DeleteTarget>,
+    impacts: Vec<DeleteImpact>,
+}
+impl KeyDeleter {
+    fn target(&self, kind: &KeyKind, key: &str) -> Option<&DeleteTarget> {
+        self.targets
+            .iter()
+            .find(|target| &target.kind == kind && target.key == key)
+    }
+    fn reference(&self, kind: &KeyKind, key: &str) -> Option<String> {
+        self.target(kind, key).map(|target| target.reason())
+    }
+    fn source_reference(&self, source: &str) -> Option<String> {
+        self.reference(&KeyKind::Field, source)
+            .or_else(|| self.reference(&KeyKind::Identifier, source))
+            .or_else(|| self.reference(&KeyKind::Classification, source))
+    }
+    fn attribute_reference(&self, attribute_type: &AttributeType, key: &str) -> Option<String> {
+        match attribute_type {
+            AttributeType::Identifier => self.reference(&KeyKind::Identifier, key),
+            AttributeType::Classification => self.reference(&KeyKind::Classification, key),
+            AttributeType::Field => self.reference(&KeyKind::Field, key),
+        }
+    }
+    fn specific_reference(&self, classification: &str, category: &str) -> Option<String> {
+        self.reference(&KeyKind::Classification, classification)
+            .or_else(|| {
+                self.reference(
+                    &KeyKind::Category {
+                        classification: classification.into(),
+                    },
+                    category,
+                )
+            })
+    }
+    // Values compared to the options of a field or to the categories of a classification
+    fn value_reference(&self, source: &str, value: &str) -> Option<String> {
+        self.reference(
+            &KeyKind::Option {
+                field: source.into(),
+            },
+            value,
+        )
+        .or_else(|| {
+            self.reference(
+                &KeyKind::Category {
+                    classification: source.into(),
+                },
+                value,
+            )
+        })
+    }
+    fn template_reference(&self, template: &str) -> Option<String> {
+        self.targets
+            .iter()
+            .filter(|target| target.kind.is_source())
+            .find(|target| template.contains(&format!("source(\"{}\"", target.key)))
+            .map(|target| target.reason())
+    }
+    // Removes the elements defining a deleted key, the deleted key itself is not an impact
+    fn remove_definitions<T>(
+        &mut self,
+        items: &mut Vec<T>,
+        kind: impl Fn(&T) -> (KeyKind, String),
+        xpath: impl Fn(&T) -> String,
+    ) {
+        let mut kept = vec![];
+        for item in items.drain(..) {
+            let (item_kind, item_key) = kind(&item);
+            match self.target(&item_kind, &item_key) {
+                Some(target) => {
+                    if let Some(cause) = target.cause.to_owned() {
+                        self.impacts.push(DeleteImpact {
+                            xpath: xpath(&item),
+                            reason: cause,
+                        })
+                    }
+                }
+                None => kept.push(item),
+            }
+        }
+        *items = kept;
+    }
+    fn remove_references<T>(
+        &mut self,
+        items: &mut Vec<T>,
+        reference: impl Fn(&Self, &T) -> Option<String>,
+        xpath: impl Fn(&T) -> String,
+    ) {
+        let mut kept = vec![];
+        for item in items.drain(..) {
+            match reference(self, &item) {
+                Some(reason) => self.impacts.push(DeleteImpact {
+                    xpath: xpath(&item),
+                    reason,
+                }),
+                None => kept.push(item),
+            }
+        }
+        *items = kept;
+    }
+    // Removes the elements left empty by the removal of their children, with the reason of
+    // the first child removed. Elements which were already empty are kept.
+    fn remove_emptied<T>(
+        &mut self,
+        items: &mut Vec<T>,
+        is_empty: impl Fn(&T) -> bool,
+        xpath: impl Fn(&T) -> String,
+    ) {
+        let mut kept = vec![];
+        for item in items.drain(..) {
+            let item_xpath = xpath(&item);
+            let reason = match is_empty(&item) {
+                true => self
+                    .impacts
+                    .iter()
+                    .find(|impact| impact.xpath.starts_with(&format!("{}/", item_xpath)))
+                    .map(|impact| impact.reason.to_owned()),
+                false => None,
+            };
+            match reason {
+                Some(reason) => self.impacts.push(DeleteImpact {
+                    xpath: item_xpath,
+                    reason,
+                }),
+                None => kept.push(item),
+            }
+        }
+        *items = kept;
+    }
+    fn condition_reference(&self, condition: &Condition) -> Option<String> {
+        let source = condition.source();
+        match condition {
+            Condition::Equals {
+                value,
+                use_suffix: Some(UseSuffix::SuffixKey),
+                ..
+            } => self.reference(
+                &KeyKind::Suffix {
+                    field: source.as_str().into(),
+                },
+                value,
+            ),
+            Condition::Equals { value, .. } | Condition::NotEquals { value, .. } => {
+                self.value_reference(source, value)
+            }
+            Condition::In { values, .. } | Condition::NotIn { values, .. } => values
+                .iter()
+                .find_map(|value| self.value_reference(source, value)),
+            _ => None,
+        }
+        .or_else(|| self.source_reference(source))
+    }
+    // The conditions of a group are ANDed, removing one of them would widen the group: the
+    // whole group is removed
+    fn remove_condition_groups(&mut self, condition_groups: &mut Vec<ConditionGroup>, xpath: &str) {
+        let mut kept = vec![];
+        for (index, condition_group) in condition_groups.drain(..).enumerate() {
+            let reason = condition_group
+                .conditions
+                .iter()
+                .find_map(|condition| self.condition_reference(condition));
+            match reason {
+                Some(reason) => self.impacts.push(DeleteImpact {
+                    xpath: format!("{}/Conditions/Condition-Group[{}]", xpath, index + 1),
+                    reason,
+                }),
+                None => kept.push(condition_group),
+            }
+        }
+        *condition_groups = kept;
+    }
+    fn remove_controls(&mut self, controls: &mut Vec<Control>, xpath: &str) {
+        self.remove_references(
+            controls,
+            |deleter, control| match control {
+                Control::RuleRequiredIfAnotherFieldIsEqualTo { field, value } => deleter
+                    .reference(&KeyKind::Field, field)
+                    .or_else(|| deleter.value_reference(field, value)),
+                _ => control
+                    .field()
+                    .and_then(|field| deleter.reference(&KeyKind::Field, field)),
+            },
+            |control| control_xpath(xpath, control),
+        );
+        for control in controls.iter_mut() {
+            let control_xpath = control_xpath(xpath, control);
+            self.visit_control_mut(control, &control_xpath);
+        }
+        // Same as the parser, controls left without conditions or options are dropped
+        self.remove_emptied(
+            controls,
+            |control| match control {
+                Control::RuleCondition {
+                    condition_groups, ..
+                } => condition_groups.is_empty(),
+                Control::RuleRequiredIfAnotherFieldHasOptions { options, .. } => options.is_empty(),
+                _ => false,
+            },
+            |control| control_xpath(xpath, control),
+        );
+    }
+}
+impl VisitorMut for KeyDeleter {
+    fn visit_schema_mut(&mut self, schema: &mut Schema, xpath: &str) {
+        self.remove_definitions(
+            &mut schema.levels,
+            |level| (KeyKind::Level, level.key.to_string()),
+            |level| keyed(&format!("{}/Levels", xpath), "Level", &level.key),
+        );
+        self.remove_definitions(
+            &mut schema.identifiers,
+            |identifier| (KeyKind::Identifier, identifier.key.to_string()),
+            |identifier| {
+                keyed(
+                    &format!("{}/Identifiers", xpath),
+                    "Identifier",
+                    &identifier.key,
+                )
+            },
+        );
+        self.remove_definitions(
+            &mut schema.classifications,
+            |classification| (KeyKind::Classification, classification.key.to_string()),
+            |classification| {
+                keyed(
+                    &format!("{}/Classifications", xpath),
+                    "Classification",
+                    &classification.key,
+                )
+            },
+        );
+        self.remove_definitions(
+            &mut schema.fields,
+            |field| (KeyKind::Field, field.key.to_string()),
+            |field| keyed(&format!("{}/Fields", xpath), "Field", &field.key),
+        );
+        self.remove_definitions(
+            &mut schema.conditional_formattings,
+            |conditional_formatting| {
+                (
+                    KeyKind::ConditionalFormatting,
+                    conditional_formatting.key.to_string(),
+                )
+            },
+            |conditional_formatting| {
+                keyed(
+                    &format!("{}/Conditional-Formattings", xpath),
+                    "Conditional-Formatting",
+                    &conditional_formatting.key,
+                )
+            },
+        );
+        self.remove_definitions(
+            &mut schema.sections,
+            |section| (KeyKind::Section, section.key.to_string()),
+            |section| keyed(&format!("{}/Sections", xpath), "Section", &section.key),
+        );
+        self.remove_references(
+            &mut schema.formulas,
+            |deleter, formula| deleter.attribute_reference(&formula.attribute_type, &formula.key),
+            |formula| {
+                keyed(
+                    &format!("{}/Formulas", xpath),
+                    formula.attribute_type.element_name(),
+                    &formula.key,
+                )
+            },
+        );
+        self.remove_references(
+            &mut schema.screens,
+            |deleter, screen| deleter.reference(&KeyKind::Level, &screen.level),
+            |screen| keyed(&format!("{}/Screens", xpath), "Screen", &screen.key),
+        );
+        walk_schema_mut(self, schema, xpath);
+        // Same as the parser, formulas left without rules are dropped
+        self.remove_emptied(
+            &mut schema.formulas,
+            |formula| formula.rules.is_empty(),
+            |formula| {
+                keyed(
+                    &format!("{}/Formulas", xpath),
+                    formula.attribute_type.element_name(),
+                    &formula.key,
+                )
+            },
+        );
+    }
+    fn visit_classification_mut(&mut self, classification: &mut Classification, xpath: &str) {
+        let classification_key = classification.key.to_owned();
+        self.remove_definitions(
+            &mut classification.categories,
+            |category| {
+                (
+                    KeyKind::Category {
+                        classification: classification_key.to_owned(),
+                    },
+                    category.key.to_string(),
+                )
+            },
+            |category| keyed(&format!("{}/Categories", xpath), "Category", &category.key),
+        );
+        walk_classification_mut(self, classification, xpath);
+    }
+    fn visit_field_mut(&mut self, field: &mut Field, xpath: &str) {
+        let field_key = field.key.to_owned();
+        if let Some(options) = &mut field.options {
+            self.remove_definitions(
+                options,
+                |option| {
+                    (
+                        KeyKind::Option {
+                            field: field_key.to_owned(),
+                        },
+                        option.key.to_string(),
+                    )
+                },
+                |option| keyed(&format!("{}/Options", xpath), "Option", &option.key),
+            );
+            if options.is_empty() {
+                field.options = None;
+            }
+        }
+        if let Some(suffixes) = &mut field.suffixes {
+            self.remove_definitions(
+                suffixes,
+                |suffix| {
+                    (
+                        KeyKind::Suffix {
+                            field: field_key.to_owned(),
+                        },
+                        suffix.key.to_string(),
+                    )
+                },
+                |suffix| keyed(&format!("{}/Suffixes", xpath), "Suffix", &suffix.key),
+            );
+            if suffixes.is_empty() {
+                field.suffixes = None;
+            }
+        }
+        walk_field_mut(self, field, xpath);
+    }
+    fn visit_formula_mut(&mut self, formula: &mut Formula, xpath: &str) {
+        let formula_key = formula.key.to_owned();
+        let rule_xpath = |rule: &Rule| format!("{}/Rule[@priority='{}']", xpath, rule.priority);
+        self.remove_references(
+            &mut formula.rules,
+            |deleter, rule| match &rule.action {
+                Action::SetTextTemplate { value, .. } | Action::SetNumberTemplate { value, .. } => {
+                    deleter.template_reference(value)
+                }
+                Action::SetSelectableOptions { .. } => None,
+            },
+            rule_xpath,
+        );
+        walk_formula_mut(self, formula, xpath);
+        if formula.attribute_type == AttributeType::Field {
+            for rule in formula.rules.iter_mut() {
+                if let Action::SetSelectableOptions { values } = &mut rule.action {
+                    let value_xpath =
+                        format!("{}/Rule[@priority='{}']/Action/Value", xpath, rule.priority);
+                    self.remove_references(
+                        values,
+                        |deleter, value| deleter.value_reference(&formula_key, value),
+                        |_| value_xpath.to_owned(),
+                    );
+                }
+            }
+        }
+        // Same as the parser, rules left without conditions or selectable options are dropped
+        self.remove_emptied(
+            &mut formula.rules,
+            |rule| match &rule.action {
+                Action::SetSelectableOptions { values } => {
+                    rule.conditions.is_empty() || values.is_empty()
+                }
+                _ => rule.conditions.is_empty(),
+            },
+            rule_xpath,
+        );
+    }
+    fn visit_rule_mut(&mut self, rule: &mut Rule, xpath: &str) {
+        self.remove_condition_groups(&mut rule.conditions, xpath);
+        walk_rule_mut(self, rule, xpath);
+    }
+    fn visit_matrix_mut(&mut self, matrix: &mut Matrix, xpath: &str) {
+        self.remove_references(
+            &mut matrix.common,
+            |deleter, matrix_field| deleter.reference(&KeyKind::Field, &matrix_field.key),
+            |matrix_field| keyed(&format!("{}/Common", xpath), "Field", &matrix_field.key),
+        );
+        self.remove_references(
+            &mut matrix.specifics,
+            |deleter, matrix_specific| {
+                deleter
+                    .specific_reference(&matrix_specific.classification, &matrix_specific.category)
+            },
+            |matrix_specific| {
+                specific(
+                    xpath,
+                    &matrix_specific.classification,
+                    &matrix_specific.category,
+                )
+            },
+        );
+        walk_matrix_mut(self, matrix, xpath);
+    }
+    fn visit_matrix_specific_mut(&mut self, matrix_specific: &mut MatrixSpecific, xpath: &str) {
+        self.remove_references(
+            &mut matrix_specific.fields,
+            |deleter, matrix_field| deleter.reference(&KeyKind::Field, &matrix_field.key),
+            |matrix_field| keyed(xpath, "Field", &matrix_field.key),
+        );
+    }
+    fn visit_rules_mut(&mut self, rules: &mut Rules, xpath: &str) {
+        self.remove_references(
+            &mut rules.common,
+            |deleter, common| deleter.attribute_reference(&common.attribute_type, &common.key),
+            |common| {
+                keyed(
+                    &format!("{}/Common", xpath),
+                    common.attribute_type.element_name(),
+                    &common.key,
+                )
+            },
+        );
+        self.remove_references(
+            &mut rules.specifics,
+            |deleter, specific_rules| {
+                deleter.specific_reference(&specific_rules.classification, &specific_rules.category)
+            },
+            |specific_rules| {
+                specific(
+                    xpath,
+                    &specific_rules.classification,
+                    &specific_rules.category,
+                )
+            },
+        );
+        walk_rules_mut(self, rules, xpath);
+        // Same as the parser, attributes left without controls are dropped
+        self.remove_emptied(
+            &mut rules.common,
+            |common| common.controls.is_empty(),
+            |common| {
+                keyed(
+                    &format!("{}/Common", xpath),
+                    common.attribute_type.element_name(),
+                    &common.key,
+                )
+            },
+        );
+        self.remove_emptied(
+            &mut rules.specifics,
+            |specific_rules| specific_rules.attributes.is_empty(),
+            |specific_rules| {
+                specific(
+                    xpath,
+                    &specific_rules.classification,
+                    &specific_rules.category,
+                )
+            },
+        );
+    }
+    fn visit_common_attribute_rules_mut(&mut self, rules: &mut CommonAttributeRules, xpath: &str) {
+        self.remove_controls(&mut rules.controls, xpath);
+    }
+    fn visit_specific_rules_mut(&mut self, rules: &mut SpecificRules, xpath: &str) {
+        self.remove_references(
+            &mut rules.attributes,
+            |deleter, attribute| deleter.reference(&KeyKind::Field, &attribute.key),
+            |attribute| keyed(xpath, "Field", &attribute.key),
+        );
+        walk_specific_rules_mut(self, rules, xpath);
+        self.remove_emptied(
+            &mut rules.attributes,
+            |attribute| attribute.controls.is_empty(),
+            |attribute| keyed(xpath, "Field", &attribute.key),
+        );
+    }
+    fn visit_specific_attribute_rules_mut(
+        &mut self,
+        rules: &mut SpecificAttributeRules,
+        xpath: &str,
+    ) {
+        self.remove_controls(&mut rules.controls, xpath);
+    }
+    fn visit_control_mut(&mut self, control: &mut Control, xpath: &str) {
+        match control {
+            Control::RuleRequiredIfAnotherFieldHasOptions { field, options } => {
+                let field_key = field.to_owned();
+                self.remove_references(
+                    options,
+                    |deleter, option| deleter.value_reference(&field_key, &option.key),
+                    |option| keyed(xpath, "Option", &option.key),
+                );
+            }
+            Control::RuleCondition {
+                condition_groups, ..
+            } => {
+                self.remove_condition_groups(condition_groups, xpath);
+                walk_control_mut(self, control, xpath);
+            }
+            _ => (),
+        }
+    }
+    fn visit_screen_grid_mut(&mut self, grid: &mut ScreenGrid, xpath: &str) {
+        self.remove_references(
+            &mut grid.common,
+            |deleter, section| deleter.reference(&KeyKind::Section, &section.key),
+            |section| keyed(&format!("{}/Common", xpath), "Section", &section.key),
+        );
+        self.remove_references(
+            &mut grid.specifics,
+            |deleter, grid_specific| {
+                deleter.specific_reference(&grid_specific.classification, &grid_specific.category)
+            },
+            |grid_specific| {
+                specific(
+                    xpath,
+                    &grid_specific.classification,
+                    &grid_specific.category,
+                )
+            },
+        );
+        walk_screen_grid_mut(self, grid, xpath);
+    }
+    fn visit_common_section_mut(&mut self, section: &mut CommonSection, xpath: &str) {
+        self.remove_references(
+            &mut section.columns,
+            |deleter, column| {
+                let kind = match column {
+                    CommonColumn::ColumnIdentifier(_) => KeyKind::Identifier,
+                    CommonColumn::ColumnClassification(_) => KeyKind::Classification,
+                    CommonColumn::ColumnConditionalFormatting(_) => KeyKind::ConditionalFormatting,
+                    CommonColumn::ColumnField(_) => KeyKind::Field,
+                };
+                deleter.reference(&kind, column.key())
+            },
+            |column| keyed(xpath, column.element_name(), column.key()),
+        );
+    }
+    fn visit_grid_specific_mut(&mut self, grid_specific: &mut GridSpecific, xpath: &str) {
+        self.remove_references(
+            &mut grid_specific.sections,
+            |deleter, section| deleter.reference(&KeyKind::Section, &section.key),
+            |section| keyed(xpath, "Section", &section.key),
+        );
+        walk_grid_specific_mut(self, grid_specific, xpath);
+    }
+    fn visit_specific_section_mut(&mut self, section: &mut SpecificSection, xpath: &str) {
+        self.remove_references(
+            &mut section.columns,
+            |deleter, column| deleter.reference(&KeyKind::Field, &column.key),
+            |column| keyed(xpath, "Column-Field", &column.key),
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::table_structs::{
+    Column, ColumnWidth, CommonColumn, CommonSection, FieldKey, GridSpecific, Level, LineHeight,
+    Schema, Screen, ScreenGrid, Section, SpecificSection, Table,
+};
+
+// Section of the identifiers, classifications, conditional formattings and common fields
+pub const PROPERTIES_SECTION: &str = "PROPERTIES";
+// Section of the fields specific to a category
+pub const SPECIFIC_PROPERTIES_SECTION: &str = "SPECIFIC_PROPERTIES";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScreenOptions {
+    pub line_height: LineHeight,
+    pub identifier_width: Option<ColumnWidth>,
+    pub classification_width: Option<ColumnWidth>,
+    pub conditional_formatting_width: Option<ColumnWidth>,
+    pub field_width: Option<ColumnWidth>,
+    // Identifier columns stay visible when scrolling
+    pub fixed_identifiers: bool,
+}
+impl Default for ScreenOptions {
+    fn default() -> Self {
+        ScreenOptions {
+            line_height: LineHeight::Medium,
+            identifier_width: None,
+            classification_width: None,
+            conditional_formatting_width: None,
+            field_width: None,
+            fixed_identifiers: true,
+        }
+    }
+}
+
+// One "All properties" screen per level, in the order of the levels. The common section
+// holds the identifiers of the level, the classifications, the conditional formattings of
+// the level then its fields, except the fields which are only specific to categories in the
+// matrix: they go in a section of the Specific of each category.
+pub fn default_screens(schema: &Schema, options: &ScreenOptions) -> Vec<Screen> {
+    schema
+        .levels
+        .iter()
+        .enumerate()
+        .map(|(index, level)| Screen {
+            key: screen_key(level),
+            level: level.key.to_owned(),
+            position: index + 1,
+            title: "All properties".to_owned(),
+            grid: grid(schema, level, options),
+            ..Screen::new()
+        })
+        .collect()
+}
+
+impl Table {
+    // Adds the default screens whose key is not used yet, after the existing screens, and the
+    // sections they use when missing. Returns the keys of the added screens.
+    pub fn add_default_screens(&mut self, options: &ScreenOptions) -> Vec<String> {
+        let schema = &mut self.schema;
+        let mut position = schema
+            .screens
+            .iter()
+            .map(|screen| screen.position)
+            .max()
+            .unwrap_or(0);
+        let mut added = vec![];
+        let mut sections = vec![];
+        for mut screen in default_screens(schema, options) {
+            if schema.screens.iter().any(|other| other.key == screen.key) {
+                continue;
+            }
+            if !screen.grid.common.is_empty() {
+                sections.push((PROPERTIES_SECTION, "Properties"));
+            }
+            if !screen.grid.specifics.is_empty() {
+                sections.push((SPECIFIC_PROPERTIES_SECTION, "Specific properties"));
+            }
+            position += 1;
+            screen.position = position;
+            added.push(screen.key.to_owned());
+            schema.screens.push(screen);
+        }
+        for (key, title) in sections {
+            if !schema.sections.iter().any(|section| section.key == key) {
+                schema.sections.push(Section {
+                    key: key.into(),
+                    title: title.to_owned(),
+                    ..Section::new()
+                });
+            }
+        }
+        added
+    }
+}
+
+fn screen_key(level: &Level) -> String {
+    format!("ALL_PROPERTIES_{}", level.key)
+}
+
+fn grid(schema: &Schema, level: &Level, options: &ScreenOptions) -> ScreenGrid {
+    let common_fields = schema
+        .matrix
+        .common
+        .iter()
+        .map(|field| &field.key)
+        .collect::<HashSet<&FieldKey>>();
+    let specific_fields = schema
+        .matrix
+        .specifics
+        .iter()
+        .flat_map(|specific| specific.fields.iter())
+        .map(|field| &field.key)
+        .filter(|key| !common_fields.contains(key))
+        .collect::<HashSet<&FieldKey>>();
+    let mut columns = vec![];
+    for identifier in schema
+        .identifiers
+        .iter()
+        .filter(|identifier| identifier.level == level.key)
+    {
+        columns.push(CommonColumn::ColumnIdentifier(column(
+            identifier.key.to_owned(),
+            &options.identifier_width,
+            options.fixed_identifiers,
+        )));
+    }
+    for classification in schema.classifications.iter() {
+        columns.push(CommonColumn::ColumnClassification(column(
+            classification.key.to_owned(),
+            &options.classification_width,
+            false,
+        )));
+    }
+    for conditional_formatting in schema
+        .conditional_formattings
+        .iter()
+        .filter(|conditional_formatting| conditional_formatting.level == level.key)
+    {
+        columns.push(CommonColumn::ColumnConditionalFormatting(column(
+            conditional_formatting.key.to_owned(),
+            &options.conditional_formatting_width,
+            false,
+        )));
+    }
+    for field in schema
+        .fields
+        .iter()
+        .filter(|field| field.level == level.key && !specific_fields.contains(&field.key))
+    {
+        columns.push(CommonColumn::ColumnField(column(
+            field.key.to_owned(),
+            &options.field_width,
+            false,
+        )));
+    }
+    let mut common = vec![];
+    if !columns.is_empty() {
+        common.push(CommonSection {
+            key: PROPERTIES_SECTION.into(),
+            position: 1,
+            columns,
+        });
+    }
+
+    let mut specifics = vec![];
+    for specific in schema.matrix.specifics.iter() {
+        let columns = specific
+            .fields
+            .iter()
+            .filter(|field| specific_fields.contains(&field.key))
+            .filter(|field| {
+                schema
+                    .field(&field.key)
+                    .is_some_and(|field| field.level == level.key)
+            })
+            .map(|field| column(field.key.to_owned(), &options.field_width, false))
+            .collect::<Vec<Column<FieldKey>>>();
+        if !columns.is_empty() {
+            specifics.push(GridSpecific {
+                classification: specific.classification.to_owned(),
+                category: specific.category.to_owned(),
+                sections: vec![SpecificSection {
+                    key: SPECIFIC_PROPERTIES_SECTION.into(),
+                    position: 1,
+                    columns,
+                }],
+            });
+        }
+    }
+
+    let mut grid = ScreenGrid {
+        line_height: options.line_height.to_owned(),
+        common,
+        specifics,
+    };
+    // Columns are numbered in the order they were added
+    grid.normalize_positions();
+    grid
+}
+
+// Positions are set once the grid is complete
+fn column<K>(key: K, width: &Option<ColumnWidth>, fixed: bool) -> Column<K> {
+    Column {
+        key,
+        position: 0,
+        width: width.to_owned(),
+        read_only: None,
+        fixed: fixed.then_some(true),
+    }
+}
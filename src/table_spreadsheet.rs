@@ -0,0 +1,870 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    io::{Cursor, Read, Write},
+};
+
+use quick_xml::{
+    escape::escape,
+    events::{BytesStart, Event},
+    Reader,
+};
+use zip::{write::SimpleFileOptions, ZipArchive, ZipWriter};
+
+use crate::{
+    table_diff::{diff, SchemaDiff},
+    table_locals::{set_local, Localized},
+    table_structs::{
+        Category, CategoryKey, Classification, ClassificationKey, DataType, Field, FieldKey, Local,
+        Metadata, OptionKey, SelectOption, Suffix, SuffixKey, Table,
+    },
+};
+
+// Field dictionary of a table as sheets which product managers can edit in a spreadsheet:
+//   Fields, Options, Suffixes, Classifications and Categories
+// Locals and metadata have a column each, named title:{lang}, description:{lang} and
+// metadata:{key}. An empty cell means no value.
+pub const FIELDS: &str = "Fields";
+pub const OPTIONS: &str = "Options";
+pub const SUFFIXES: &str = "Suffixes";
+pub const CLASSIFICATIONS: &str = "Classifications";
+pub const CATEGORIES: &str = "Categories";
+
+// A sheet of a workbook, the first row holds the headers
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sheet {
+    pub name: String,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Sheet {
+    // RFC 4180 with a byte order mark, so that spreadsheets open it as UTF-8
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("\u{feff}");
+        for row in self.rows.iter() {
+            let cells = row
+                .iter()
+                .map(|cell| {
+                    if cell.contains([',', '"', '\n', '\r']) {
+                        format!("\"{}\"", cell.replace('"', "\"\""))
+                    } else {
+                        cell.to_owned()
+                    }
+                })
+                .collect::<Vec<String>>();
+            csv.push_str(&cells.join(","));
+            csv.push_str("\r\n");
+        }
+        csv
+    }
+    pub fn from_csv(name: &str, csv: &str) -> Result<Self, Box<dyn Error>> {
+        let mut rows = vec![];
+        let mut row = vec![];
+        let mut cell = String::new();
+        let mut quoted = false;
+        let mut chars = csv.trim_start_matches('\u{feff}').chars().peekable();
+        while let Some(c) = chars.next() {
+            match (quoted, c) {
+                (true, '"') if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    cell.push('"');
+                }
+                (true, '"') => quoted = false,
+                (true, _) => cell.push(c),
+                (false, '"') if cell.is_empty() => quoted = true,
+                (false, ',') => row.push(std::mem::take(&mut cell)),
+                (false, '\r') if chars.peek() == Some(&'\n') => (),
+                (false, '\n') | (false, '\r') => {
+                    row.push(std::mem::take(&mut cell));
+                    rows.push(std::mem::take(&mut row));
+                }
+                (false, _) => cell.push(c),
+            }
+        }
+        if quoted {
+            return Err(format!("Sheet '{}' has an unterminated quoted cell.", name).into());
+        }
+        if !cell.is_empty() || !row.is_empty() {
+            row.push(cell);
+            rows.push(row);
+        }
+        Ok(Sheet {
+            name: name.to_owned(),
+            rows,
+        })
+    }
+}
+
+pub fn to_sheets(table: &Table) -> Vec<Sheet> {
+    let schema = &table.schema;
+    let fields = schema
+        .fields
+        .iter()
+        .map(|field| {
+            let mut cells = vec![
+                cell("key", &field.key),
+                cell("level", &field.level),
+                cell("type", &field.data_type.to_string()),
+            ];
+            cells.append(&mut texts(field));
+            cells.push(cell("prefix", field.prefix.as_deref().unwrap_or_default()));
+            cells.push(cell("suffix", field.suffix.as_deref().unwrap_or_default()));
+            cells.push(cell(
+                "precision",
+                &field
+                    .precision
+                    .map(|precision| precision.to_string())
+                    .unwrap_or_default(),
+            ));
+            cells.append(&mut locals(field));
+            cells.append(&mut metadata(&field.metadata));
+            cells
+        })
+        .collect();
+    let options = schema
+        .fields
+        .iter()
+        .flat_map(|field| {
+            field.options.iter().flatten().map(|option| {
+                let mut cells = vec![cell("field", &field.key), cell("key", &option.key)];
+                cells.append(&mut texts(option));
+                cells.push(cell(
+                    "color",
+                    &option
+                        .color
+                        .as_ref()
+                        .map(|color| color.to_string())
+                        .unwrap_or_default(),
+                ));
+                cells.append(&mut locals(option));
+                cells.append(&mut metadata(&option.metadata));
+                cells
+            })
+        })
+        .collect();
+    let suffixes = schema
+        .fields
+        .iter()
+        .flat_map(|field| {
+            field.suffixes.iter().flatten().map(|suffix| {
+                let mut cells = vec![cell("field", &field.key), cell("key", &suffix.key)];
+                cells.append(&mut texts(suffix));
+                cells.push(cell(
+                    "default",
+                    &suffix
+                        .default
+                        .map(|default| default.to_string())
+                        .unwrap_or_default(),
+                ));
+                cells.append(&mut locals(suffix));
+                cells.append(&mut metadata(&suffix.metadata));
+                cells
+            })
+        })
+        .collect();
+    let classifications = schema
+        .classifications
+        .iter()
+        .map(|classification| {
+            let mut cells = vec![cell("key", &classification.key)];
+            cells.append(&mut texts(classification));
+            cells.append(&mut locals(classification));
+            cells.append(&mut metadata(&classification.metadata));
+            cells
+        })
+        .collect();
+    let categories = schema
+        .classifications
+        .iter()
+        .flat_map(|classification| {
+            classification.categories.iter().map(|category| {
+                let mut cells = vec![
+                    cell("classification", &classification.key),
+                    cell("key", &category.key),
+                    cell("parent", category.parent.as_deref().unwrap_or_default()),
+                ];
+                cells.append(&mut texts(category));
+                cells.append(&mut locals(category));
+                cells.append(&mut metadata(&category.metadata));
+                cells
+            })
+        })
+        .collect();
+    vec![
+        sheet(
+            FIELDS,
+            &[
+                "key",
+                "level",
+                "type",
+                "title",
+                "description",
+                "prefix",
+                "suffix",
+                "precision",
+            ],
+            fields,
+        ),
+        sheet(
+            OPTIONS,
+            &["field", "key", "title", "description", "color"],
+            options,
+        ),
+        sheet(
+            SUFFIXES,
+            &["field", "key", "title", "description", "default"],
+            suffixes,
+        ),
+        sheet(
+            CLASSIFICATIONS,
+            &["key", "title", "description"],
+            classifications,
+        ),
+        sheet(
+            CATEGORIES,
+            &["classification", "key", "parent", "title", "description"],
+            categories,
+        ),
+    ]
+}
+
+// Merges the sheets into the table by key, elements missing from the sheets are kept and
+// sheets with another name are ignored. Returns the changes made to the table.
+pub fn from_sheets(table: &mut Table, sheets: &[Sheet]) -> Result<SchemaDiff, Box<dyn Error>> {
+    let old = table.clone();
+    let mut merged = table.clone();
+    for row in rows(sheets, FIELDS) {
+        merge_field(&mut merged, &row)?;
+    }
+    for row in rows(sheets, OPTIONS) {
+        merge_option(&mut merged, &row)?;
+    }
+    for row in rows(sheets, SUFFIXES) {
+        merge_suffix(&mut merged, &row)?;
+    }
+    for row in rows(sheets, CLASSIFICATIONS) {
+        merge_classification(&mut merged, &row)?;
+    }
+    for row in rows(sheets, CATEGORIES) {
+        merge_category(&mut merged, &row)?;
+    }
+    // The table is only changed when every row is valid
+    *table = merged;
+    Ok(diff(&old, table))
+}
+
+fn cell(header: &str, value: &str) -> (String, String) {
+    (header.to_owned(), value.to_owned())
+}
+
+fn texts<L: Localized>(element: &L) -> Vec<(String, String)> {
+    vec![
+        cell("title", element.title()),
+        cell("description", element.description().unwrap_or_default()),
+    ]
+}
+
+fn locals<L: Localized>(element: &L) -> Vec<(String, String)> {
+    let title_locals = element
+        .title_locals()
+        .iter()
+        .map(|local| cell(&format!("title:{}", local.lang), &local.value));
+    let description_locals = element
+        .description_locals()
+        .iter()
+        .map(|local| cell(&format!("description:{}", local.lang), &local.value));
+    title_locals.chain(description_locals).collect()
+}
+
+fn metadata(metadata: &Option<Vec<Metadata>>) -> Vec<(String, String)> {
+    metadata
+        .iter()
+        .flatten()
+        .map(|metadata| cell(&format!("metadata:{}", metadata.key), &metadata.value))
+        .collect()
+}
+
+// Headers are the fixed ones followed by the locals and metadata in order of appearance
+fn sheet(name: &str, headers: &[&str], records: Vec<Vec<(String, String)>>) -> Sheet {
+    let mut headers = headers
+        .iter()
+        .map(|header| header.to_string())
+        .collect::<Vec<String>>();
+    for (header, _) in records.iter().flatten() {
+        if !headers.contains(header) {
+            headers.push(header.to_owned());
+        }
+    }
+    let mut rows = vec![headers.clone()];
+    for record in records.iter() {
+        rows.push(
+            headers
+                .iter()
+                .map(|header| {
+                    record
+                        .iter()
+                        .find(|(name, _)| name == header)
+                        .map(|(_, value)| value.to_owned())
+                        .unwrap_or_default()
+                })
+                .collect(),
+        );
+    }
+    Sheet {
+        name: name.to_owned(),
+        rows,
+    }
+}
+
+// A row of a sheet, cells are read by header
+struct Row<'a> {
+    sheet: &'a str,
+    number: usize,
+    headers: &'a [String],
+    cells: &'a [String],
+}
+
+impl<'a> Row<'a> {
+    // None when the sheet has no such column
+    fn get(&self, header: &str) -> Option<&'a str> {
+        let index = self.headers.iter().position(|name| name == header)?;
+        Some(self.cells.get(index).map_or("", |cell| cell.trim()))
+    }
+    fn required(&self, header: &str) -> Result<&'a str, Box<dyn Error>> {
+        match self.get(header) {
+            Some(value) if !value.is_empty() => Ok(value),
+            _ => Err(self.error(&format!("column '{}' is empty", header))),
+        }
+    }
+    fn error(&self, message: &str) -> Box<dyn Error> {
+        format!("Sheet '{}' row {}: {}.", self.sheet, self.number, message).into()
+    }
+    fn set_text(&self, header: &str, value: &mut String) -> Result<(), Box<dyn Error>> {
+        if self.get(header).is_some() {
+            *value = self.required(header)?.to_owned();
+        }
+        Ok(())
+    }
+    fn set_optional(&self, header: &str, value: &mut Option<String>) {
+        if let Some(cell) = self.get(header) {
+            *value = Some(cell.to_owned()).filter(|cell| !cell.is_empty());
+        }
+    }
+    fn set_texts<L: Localized>(&self, element: &mut L) -> Result<(), Box<dyn Error>> {
+        self.set_text("title", element.title_mut())?;
+        self.set_optional("description", element.description_mut());
+        for (header, value) in self.headers.iter().zip(self.cells.iter()) {
+            if let Some(lang) = header.strip_prefix("title:") {
+                set_cell_local(element.title_locals_mut(), lang, value.trim());
+            }
+            if let Some(lang) = header.strip_prefix("description:") {
+                set_cell_local(element.description_locals_mut(), lang, value.trim());
+            }
+        }
+        Ok(())
+    }
+    fn set_metadata(&self, metadata: &mut Option<Vec<Metadata>>) {
+        for (header, value) in self.headers.iter().zip(self.cells.iter()) {
+            if let Some(key) = header.strip_prefix("metadata:") {
+                let list = metadata.get_or_insert_with(Vec::new);
+                let value = value.trim();
+                match list.iter_mut().find(|metadata| metadata.key == key) {
+                    Some(_) if value.is_empty() => list.retain(|metadata| metadata.key != key),
+                    Some(metadata) => metadata.value = value.to_owned(),
+                    None if value.is_empty() => (),
+                    None => list.push(Metadata {
+                        key: key.to_owned(),
+                        value: value.to_owned(),
+                    }),
+                }
+                if list.is_empty() {
+                    *metadata = None;
+                }
+            }
+        }
+    }
+}
+
+fn set_cell_local(locals: &mut Option<Vec<Local>>, lang: &str, value: &str) {
+    if !value.is_empty() {
+        return set_local(locals, lang, value);
+    }
+    if let Some(list) = locals {
+        list.retain(|local| local.lang != lang);
+        if list.is_empty() {
+            *locals = None;
+        }
+    }
+}
+
+// Rows of the sheets with the name, without headers and empty rows
+fn rows<'a>(sheets: &'a [Sheet], name: &'a str) -> Vec<Row<'a>> {
+    let mut rows = vec![];
+    for sheet in sheets.iter().filter(|sheet| sheet.name == name) {
+        let Some((headers, cells)) = sheet.rows.split_first() else {
+            continue;
+        };
+        for (index, cells) in cells.iter().enumerate() {
+            if cells.iter().all(|cell| cell.trim().is_empty()) {
+                continue;
+            }
+            rows.push(Row {
+                sheet: &sheet.name,
+                number: index + 2,
+                headers,
+                cells,
+            });
+        }
+    }
+    rows
+}
+
+fn data_type(row: &Row, value: &str) -> Result<DataType, Box<dyn Error>> {
+    serde_json::from_value(serde_json::Value::String(value.to_owned()))
+        .map_err(|_| row.error(&format!("unknown type '{}'", value)))
+}
+
+fn merge_field(table: &mut Table, row: &Row) -> Result<(), Box<dyn Error>> {
+    let key = FieldKey::from(row.required("key")?);
+    let fields = &mut table.schema.fields;
+    let index = match fields.iter().position(|field| field.key == key) {
+        Some(index) => index,
+        None => {
+            let level = row.required("level")?;
+            let data_type = data_type(row, row.required("type")?)?;
+            row.required("title")?;
+            fields.push(Field::new(key, level.into(), data_type));
+            fields.len() - 1
+        }
+    };
+    let field = &mut fields[index];
+    if let Some(level) = row.get("level").filter(|level| !level.is_empty()) {
+        field.level = level.into();
+    }
+    if let Some(value) = row.get("type").filter(|value| !value.is_empty()) {
+        field.data_type = data_type(row, value)?;
+    }
+    row.set_texts(field)?;
+    row.set_optional("prefix", &mut field.prefix);
+    row.set_optional("suffix", &mut field.suffix);
+    if let Some(precision) = row.get("precision") {
+        field.precision =
+            match precision {
+                "" => None,
+                _ => Some(precision.parse().map_err(|_| {
+                    row.error(&format!("precision '{}' is not a number", precision))
+                })?),
+            };
+    }
+    row.set_metadata(&mut field.metadata);
+    Ok(())
+}
+
+fn merge_option(table: &mut Table, row: &Row) -> Result<(), Box<dyn Error>> {
+    let field_key = FieldKey::from(row.required("field")?);
+    let key = OptionKey::from(row.required("key")?);
+    let field = table
+        .schema
+        .fields
+        .iter_mut()
+        .find(|field| field.key == field_key)
+        .ok_or_else(|| row.error(&format!("unknown field '{}'", field_key)))?;
+    let options = field.options.get_or_insert_with(Vec::new);
+    let index = match options.iter().position(|option| option.key == key) {
+        Some(index) => index,
+        None => {
+            row.required("title")?;
+            options.push(SelectOption {
+                key,
+                ..SelectOption::new()
+            });
+            options.len() - 1
+        }
+    };
+    let option = &mut options[index];
+    row.set_texts(option)?;
+    if let Some(color) = row.get("color") {
+        option.color = Some(color)
+            .filter(|color| !color.is_empty())
+            .map(Into::into);
+    }
+    row.set_metadata(&mut option.metadata);
+    Ok(())
+}
+
+fn merge_suffix(table: &mut Table, row: &Row) -> Result<(), Box<dyn Error>> {
+    let field_key = FieldKey::from(row.required("field")?);
+    let key = SuffixKey::from(row.required("key")?);
+    let field = table
+        .schema
+        .fields
+        .iter_mut()
+        .find(|field| field.key == field_key)
+        .ok_or_else(|| row.error(&format!("unknown field '{}'", field_key)))?;
+    let suffixes = field.suffixes.get_or_insert_with(Vec::new);
+    let index = match suffixes.iter().position(|suffix| suffix.key == key) {
+        Some(index) => index,
+        None => {
+            row.required("title")?;
+            suffixes.push(Suffix {
+                key,
+                ..Suffix::new()
+            });
+            suffixes.len() - 1
+        }
+    };
+    let suffix = &mut suffixes[index];
+    row.set_texts(suffix)?;
+    if let Some(default) = row.get("default") {
+        suffix.default = match default.to_lowercase().as_str() {
+            "" => None,
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => return Err(row.error(&format!("default '{}' is not a boolean", default))),
+        };
+    }
+    row.set_metadata(&mut suffix.metadata);
+    Ok(())
+}
+
+fn merge_classification(table: &mut Table, row: &Row) -> Result<(), Box<dyn Error>> {
+    let key = ClassificationKey::from(row.required("key")?);
+    let classifications = &mut table.schema.classifications;
+    let index = match classifications
+        .iter()
+        .position(|classification| classification.key == key)
+    {
+        Some(index) => index,
+        None => {
+            row.required("title")?;
+            classifications.push(Classification {
+                key,
+                ..Classification::new()
+            });
+            classifications.len() - 1
+        }
+    };
+    let classification = &mut classifications[index];
+    row.set_texts(classification)?;
+    row.set_metadata(&mut classification.metadata);
+    Ok(())
+}
+
+fn merge_category(table: &mut Table, row: &Row) -> Result<(), Box<dyn Error>> {
+    let classification_key = ClassificationKey::from(row.required("classification")?);
+    let key = CategoryKey::from(row.required("key")?);
+    let classification = table
+        .schema
+        .classifications
+        .iter_mut()
+        .find(|classification| classification.key == classification_key)
+        .ok_or_else(|| row.error(&format!("unknown classification '{}'", classification_key)))?;
+    let categories = &mut classification.categories;
+    let index = match categories.iter().position(|category| category.key == key) {
+        Some(index) => index,
+        None => {
+            row.required("title")?;
+            categories.push(Category {
+                key,
+                ..Category::new()
+            });
+            categories.len() - 1
+        }
+    };
+    let category = &mut categories[index];
+    if let Some(parent) = row.get("parent") {
+        category.parent = Some(parent)
+            .filter(|parent| !parent.is_empty())
+            .map(Into::into);
+    }
+    row.set_texts(category)?;
+    row.set_metadata(&mut category.metadata);
+    Ok(())
+}
+
+// XLSX
+// Workbook with one worksheet per sheet and inline strings, the smallest package that
+// spreadsheets open.
+pub fn to_xlsx(sheets: &[Sheet]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    let mut content_types = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\"><Default Extension=\"rels\" ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/><Default Extension=\"xml\" ContentType=\"application/xml\"/><Override PartName=\"/xl/workbook.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml\"/>",
+    );
+    let mut workbook = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<workbook xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\" xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\"><sheets>",
+    );
+    let mut relationships = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">",
+    );
+    for (index, sheet) in sheets.iter().enumerate() {
+        let number = index + 1;
+        content_types.push_str(&format!("<Override PartName=\"/xl/worksheets/sheet{}.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml\"/>", number));
+        workbook.push_str(&format!(
+            "<sheet name=\"{}\" sheetId=\"{}\" r:id=\"rId{}\"/>",
+            escape(sheet.name.as_str()),
+            number,
+            number
+        ));
+        relationships.push_str(&format!("<Relationship Id=\"rId{}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet\" Target=\"worksheets/sheet{}.xml\"/>", number, number));
+        zip.start_file(format!("xl/worksheets/sheet{}.xml", number), options)?;
+        zip.write_all(worksheet(sheet).as_bytes())?;
+    }
+    content_types.push_str("</Types>");
+    workbook.push_str("</sheets></workbook>");
+    relationships.push_str("</Relationships>");
+    zip.start_file("[Content_Types].xml", options)?;
+    zip.write_all(content_types.as_bytes())?;
+    zip.start_file("_rels/.rels", options)?;
+    zip.write_all(b"<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\"><Relationship Id=\"rId1\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument\" Target=\"xl/workbook.xml\"/></Relationships>")?;
+    zip.start_file("xl/workbook.xml", options)?;
+    zip.write_all(workbook.as_bytes())?;
+    zip.start_file("xl/_rels/workbook.xml.rels", options)?;
+    zip.write_all(relationships.as_bytes())?;
+    Ok(zip.finish()?.into_inner())
+}
+
+fn worksheet(sheet: &Sheet) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<worksheet xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\"><sheetData>",
+    );
+    for (row_index, row) in sheet.rows.iter().enumerate() {
+        xml.push_str(&format!("<row r=\"{}\">", row_index + 1));
+        for (column_index, value) in row.iter().enumerate() {
+            if value.is_empty() {
+                continue;
+            }
+            xml.push_str(&format!(
+                "<c r=\"{}{}\" t=\"inlineStr\"><is><t xml:space=\"preserve\">{}</t></is></c>",
+                column_name(column_index),
+                row_index + 1,
+                escape(value.as_str())
+            ));
+        }
+        xml.push_str("</row>");
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+// 0 is A, 25 is Z, 26 is AA
+fn column_name(index: usize) -> String {
+    let mut name = String::new();
+    let mut index = index + 1;
+    while index > 0 {
+        let remainder = (index - 1) % 26;
+        name.insert(0, (b'A' + remainder as u8) as char);
+        index = (index - 1) / 26;
+    }
+    name
+}
+
+// Index of the column of a cell reference: B3 is 1
+fn column_index(reference: &str) -> Option<usize> {
+    let letters = reference
+        .chars()
+        .take_while(|c| c.is_ascii_uppercase())
+        .collect::<String>();
+    if letters.is_empty() {
+        return None;
+    }
+    let number = letters.bytes().fold(0usize, |number, letter| {
+        number
+            .saturating_mul(26)
+            .saturating_add((letter - b'A' + 1) as usize)
+    });
+    Some(number - 1)
+}
+
+// Reads the worksheets of a workbook saved by a spreadsheet, with shared or inline strings
+pub fn from_xlsx(xlsx: &[u8]) -> Result<Vec<Sheet>, Box<dyn Error>> {
+    let mut archive = ZipArchive::new(Cursor::new(xlsx))?;
+    let workbook = read_part(&mut archive, "xl/workbook.xml")?
+        .ok_or("Workbook has no xl/workbook.xml part.")?;
+    let relationships = read_part(&mut archive, "xl/_rels/workbook.xml.rels")?
+        .ok_or("Workbook has no xl/_rels/workbook.xml.rels part.")?;
+    let shared_strings = match read_part(&mut archive, "xl/sharedStrings.xml")? {
+        Some(xml) => read_shared_strings(&xml)?,
+        None => vec![],
+    };
+    let mut targets = HashMap::new();
+    let mut reader = Reader::from_str(&relationships);
+    loop {
+        match reader.read_event()? {
+            Event::Start(ev) | Event::Empty(ev) if ev.local_name().as_ref() == b"Relationship" => {
+                if let (Some(id), Some(target)) = (attribute(&ev, "Id")?, attribute(&ev, "Target")?)
+                {
+                    let target = match target.strip_prefix('/') {
+                        Some(target) => target.to_owned(),
+                        None => format!("xl/{}", target),
+                    };
+                    targets.insert(id, target);
+                }
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    let mut sheets = vec![];
+    let mut reader = Reader::from_str(&workbook);
+    loop {
+        match reader.read_event()? {
+            Event::Start(ev) | Event::Empty(ev) if ev.local_name().as_ref() == b"sheet" => {
+                let name = attribute(&ev, "name")?.unwrap_or_default();
+                let target = attribute(&ev, "r:id")?
+                    .and_then(|id| targets.get(&id))
+                    .ok_or_else(|| format!("Sheet '{}' has no worksheet.", name))?;
+                let xml = read_part(&mut archive, target)?
+                    .ok_or_else(|| format!("Workbook has no {} part.", target))?;
+                sheets.push(Sheet {
+                    rows: read_worksheet(&xml, &shared_strings)?,
+                    name,
+                });
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    Ok(sheets)
+}
+
+fn read_part(
+    archive: &mut ZipArchive<Cursor<&[u8]>>,
+    name: &str,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let mut file = match archive.by_name(name) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(error) => return Err(error.into()),
+    };
+    let mut content = String::new();
+    file.read_to_string(&mut content)?;
+    Ok(Some(content))
+}
+
+fn attribute(ev: &BytesStart, name: &str) -> Result<Option<String>, Box<dyn Error>> {
+    Ok(match ev.try_get_attribute(name)? {
+        Some(attribute) => Some(attribute.unescape_value()?.to_string()),
+        None => None,
+    })
+}
+
+// Texts of the si elements, rich texts are flattened
+fn read_shared_strings(xml: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut strings = vec![];
+    let mut text: Option<String> = None;
+    let mut in_t = false;
+    let mut reader = Reader::from_str(xml);
+    loop {
+        match reader.read_event()? {
+            Event::Start(ev) => match ev.local_name().as_ref() {
+                b"si" => text = Some(String::new()),
+                b"t" => in_t = true,
+                _ => (),
+            },
+            Event::Text(ev) if in_t => {
+                if let Some(text) = text.as_mut() {
+                    text.push_str(&ev.unescape()?);
+                }
+            }
+            Event::End(ev) => match ev.local_name().as_ref() {
+                b"si" => strings.extend(text.take()),
+                b"t" => in_t = false,
+                _ => (),
+            },
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    Ok(strings)
+}
+
+// Size of a worksheet, larger references come from a corrupted or forged file
+const MAX_ROWS: usize = 1_048_576;
+const MAX_COLUMNS: usize = 16_384;
+
+fn read_worksheet(
+    xml: &str,
+    shared_strings: &[String],
+) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+    let mut rows: Vec<Vec<String>> = vec![];
+    // Type and column of the cell being read
+    let mut cell: Option<(Option<String>, usize)> = None;
+    let mut value = String::new();
+    let mut in_value = false;
+    let mut reader = Reader::from_str(xml);
+    loop {
+        match reader.read_event()? {
+            Event::Start(ev) | Event::Empty(ev) if ev.local_name().as_ref() == b"row" => {
+                // Rows left empty by the spreadsheet are not written
+                let number = attribute(&ev, "r")?
+                    .and_then(|number| number.parse::<usize>().ok())
+                    .unwrap_or(rows.len() + 1);
+                if number > MAX_ROWS {
+                    return Err(format!(
+                        "Row {} is beyond the last row of a worksheet ({}).",
+                        number, MAX_ROWS
+                    )
+                    .into());
+                }
+                while rows.len() < number {
+                    rows.push(vec![]);
+                }
+            }
+            Event::Start(ev) if ev.local_name().as_ref() == b"c" => {
+                let row = rows.last().map_or(0, |row| row.len());
+                let column = attribute(&ev, "r")?
+                    .and_then(|reference| column_index(&reference))
+                    .unwrap_or(row);
+                if column >= MAX_COLUMNS {
+                    return Err(format!(
+                        "Column {} is beyond the last column of a worksheet ({}).",
+                        column + 1,
+                        MAX_COLUMNS
+                    )
+                    .into());
+                }
+                cell = Some((attribute(&ev, "t")?, column));
+                value.clear();
+            }
+            Event::Start(ev) if matches!(ev.local_name().as_ref(), b"v" | b"t") => in_value = true,
+            Event::Text(ev) if in_value => value.push_str(&ev.unescape()?),
+            Event::End(ev) => match ev.local_name().as_ref() {
+                b"v" | b"t" => in_value = false,
+                b"c" => {
+                    let (cell_type, column) = match cell.take() {
+                        Some(cell) => cell,
+                        None => continue,
+                    };
+                    let text = match cell_type.as_deref() {
+                        Some("s") => value
+                            .trim()
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|index| shared_strings.get(index))
+                            .cloned()
+                            .ok_or_else(|| format!("Unknown shared string '{}'.", value))?,
+                        Some("b") => match value.trim() {
+                            "1" => "true".to_owned(),
+                            _ => "false".to_owned(),
+                        },
+                        _ => value.to_owned(),
+                    };
+                    if rows.is_empty() {
+                        rows.push(vec![]);
+                    }
+                    if let Some(row) = rows.last_mut() {
+                        while row.len() < column {
+                            row.push(String::new());
+                        }
+                        row.push(text);
+                    }
+                }
+                _ => (),
+            },
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    Ok(rows)
+}
@@ -0,0 +1,429 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    table_structs::{CategoryKey, Classification, DataType, Table},
+    table_translation::completeness,
+};
+
+// Size and complexity metrics of a table, to track the growth of a schema over time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableStats {
+    pub entities: EntityCounts,
+    // Keyed by data type, in the order of the first field of each type
+    pub fields_by_type: Vec<Count>,
+    // Keyed by field, for the single and multiple select fields
+    pub options_per_select_field: Vec<Count>,
+    pub category_trees: Vec<CategoryTree>,
+    pub rules_per_status: Vec<StatusRules>,
+    // Keyed by control element name (Rule-Required...), for the rules of all statuses
+    pub controls_by_type: Vec<Count>,
+    pub formulas: FormulaStats,
+    pub locales: Vec<LocaleCoverage>,
+    // Keyed by screen, common and specific columns together
+    pub columns_per_screen: Vec<Count>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityCounts {
+    pub partitions: usize,
+    pub levels: usize,
+    pub identifiers: usize,
+    pub classifications: usize,
+    pub categories: usize,
+    pub fields: usize,
+    pub options: usize,
+    pub suffixes: usize,
+    pub formulas: usize,
+    pub conditional_formattings: usize,
+    pub statuses: usize,
+    pub sections: usize,
+    pub screens: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Count {
+    pub key: String,
+    pub count: usize,
+}
+
+// Depth is the number of categories of the longest path from a root category (0 without
+// categories), breadth the greatest number of categories on the same depth
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CategoryTree {
+    pub classification: String,
+    pub categories: usize,
+    pub roots: usize,
+    pub leaves: usize,
+    pub depth: usize,
+    pub breadth: usize,
+}
+
+// Attributes having rules and controls of the common and specific rules of a status
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusRules {
+    pub conditional_formatting: String,
+    pub status: String,
+    pub attributes: usize,
+    pub controls: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormulaStats {
+    pub formulas: usize,
+    pub rules: usize,
+    // Greatest number of conditions in a condition group of a formula rule
+    pub max_condition_group_size: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocaleCoverage {
+    pub lang: String,
+    pub translated: usize,
+    pub total: usize,
+    pub percentage: f64,
+}
+
+impl Table {
+    pub fn stats(&self) -> TableStats {
+        let schema = &self.schema;
+        let statuses = schema
+            .conditional_formattings
+            .iter()
+            .flat_map(|conditional_formatting| {
+                conditional_formatting
+                    .statuses
+                    .iter()
+                    .map(move |status| (conditional_formatting, status))
+            })
+            .collect::<Vec<_>>();
+
+        let mut fields_by_type = vec![];
+        for field in schema.fields.iter() {
+            increment(&mut fields_by_type, &field.data_type.to_string());
+        }
+        let mut controls_by_type = vec![];
+        for (_, status) in statuses.iter() {
+            let controls = status
+                .rules
+                .common
+                .iter()
+                .flat_map(|rules| rules.controls.iter())
+                .chain(
+                    status
+                        .rules
+                        .specifics
+                        .iter()
+                        .flat_map(|specific| specific.attributes.iter())
+                        .flat_map(|attribute| attribute.controls.iter()),
+                );
+            for control in controls {
+                increment(&mut controls_by_type, control.element_name());
+            }
+        }
+
+        TableStats {
+            entities: EntityCounts {
+                partitions: schema.partitions.len(),
+                levels: schema.levels.len(),
+                identifiers: schema.identifiers.len(),
+                classifications: schema.classifications.len(),
+                categories: schema
+                    .classifications
+                    .iter()
+                    .map(|classification| classification.categories.len())
+                    .sum(),
+                fields: schema.fields.len(),
+                options: schema
+                    .fields
+                    .iter()
+                    .filter_map(|field| field.options.as_ref())
+                    .map(|options| options.len())
+                    .sum(),
+                suffixes: schema
+                    .fields
+                    .iter()
+                    .filter_map(|field| field.suffixes.as_ref())
+                    .map(|suffixes| suffixes.len())
+                    .sum(),
+                formulas: schema.formulas.len(),
+                conditional_formattings: schema.conditional_formattings.len(),
+                statuses: statuses.len(),
+                sections: schema.sections.len(),
+                screens: schema.screens.len(),
+            },
+            fields_by_type,
+            options_per_select_field: schema
+                .fields
+                .iter()
+                .filter(|field| {
+                    matches!(
+                        field.data_type,
+                        DataType::SingleSelect
+                            | DataType::MultipleSelect
+                            | DataType::MultipleSelectQuantified
+                            | DataType::MultipleSelectQuantifiedWithComments
+                    )
+                })
+                .map(|field| Count {
+                    key: field.key.to_string(),
+                    count: field.options.as_ref().map_or(0, |options| options.len()),
+                })
+                .collect(),
+            category_trees: schema.classifications.iter().map(category_tree).collect(),
+            rules_per_status: statuses
+                .iter()
+                .map(|(conditional_formatting, status)| StatusRules {
+                    conditional_formatting: conditional_formatting.key.to_string(),
+                    status: status.key.to_owned(),
+                    attributes: status.rules.common.len()
+                        + status
+                            .rules
+                            .specifics
+                            .iter()
+                            .map(|specific| specific.attributes.len())
+                            .sum::<usize>(),
+                    controls: status
+                        .rules
+                        .common
+                        .iter()
+                        .map(|rules| rules.controls.len())
+                        .chain(
+                            status
+                                .rules
+                                .specifics
+                                .iter()
+                                .flat_map(|specific| specific.attributes.iter())
+                                .map(|attribute| attribute.controls.len()),
+                        )
+                        .sum(),
+                })
+                .collect(),
+            controls_by_type,
+            formulas: FormulaStats {
+                formulas: schema.formulas.len(),
+                rules: schema
+                    .formulas
+                    .iter()
+                    .map(|formula| formula.rules.len())
+                    .sum(),
+                max_condition_group_size: schema
+                    .formulas
+                    .iter()
+                    .flat_map(|formula| formula.rules.iter())
+                    .flat_map(|rule| rule.conditions.iter())
+                    .map(|group| group.conditions.len())
+                    .max()
+                    .unwrap_or(0),
+            },
+            locales: completeness(self)
+                .into_iter()
+                .map(|lang| LocaleCoverage {
+                    lang: lang.lang,
+                    translated: lang.translated,
+                    total: lang.total,
+                    percentage: lang.percentage,
+                })
+                .collect(),
+            columns_per_screen: schema
+                .screens
+                .iter()
+                .map(|screen| Count {
+                    key: screen.key.to_owned(),
+                    count: screen
+                        .grid
+                        .common
+                        .iter()
+                        .map(|section| section.columns.len())
+                        .chain(
+                            screen
+                                .grid
+                                .specifics
+                                .iter()
+                                .flat_map(|specific| specific.sections.iter())
+                                .map(|section| section.columns.len()),
+                        )
+                        .sum(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TableStats {
+    // Plain text report, one titled block per metric with aligned values
+    pub fn to_text(&self) -> String {
+        let entities = &self.entities;
+        let mut blocks = vec![(
+            "Entities",
+            [
+                ("partitions", entities.partitions),
+                ("levels", entities.levels),
+                ("identifiers", entities.identifiers),
+                ("classifications", entities.classifications),
+                ("categories", entities.categories),
+                ("fields", entities.fields),
+                ("options", entities.options),
+                ("suffixes", entities.suffixes),
+                ("formulas", entities.formulas),
+                ("conditional formattings", entities.conditional_formattings),
+                ("statuses", entities.statuses),
+                ("sections", entities.sections),
+                ("screens", entities.screens),
+            ]
+            .iter()
+            .map(|(name, count)| (name.to_string(), count.to_string()))
+            .collect::<Vec<_>>(),
+        )];
+        blocks.push(("Fields by type", counts(&self.fields_by_type)));
+        blocks.push((
+            "Options per select field",
+            counts(&self.options_per_select_field),
+        ));
+        blocks.push((
+            "Category trees",
+            self.category_trees
+                .iter()
+                .map(|tree| {
+                    (
+                        tree.classification.to_owned(),
+                        format!(
+                            "{} categories, {} roots, {} leaves, depth {}, breadth {}",
+                            tree.categories, tree.roots, tree.leaves, tree.depth, tree.breadth
+                        ),
+                    )
+                })
+                .collect(),
+        ));
+        blocks.push((
+            "Rules per status",
+            self.rules_per_status
+                .iter()
+                .map(|rules| {
+                    (
+                        format!("{}/{}", rules.conditional_formatting, rules.status),
+                        format!(
+                            "{} attributes, {} controls",
+                            rules.attributes, rules.controls
+                        ),
+                    )
+                })
+                .collect(),
+        ));
+        blocks.push(("Controls by type", counts(&self.controls_by_type)));
+        blocks.push((
+            "Formulas",
+            vec![
+                ("formulas".to_owned(), self.formulas.formulas.to_string()),
+                ("rules".to_owned(), self.formulas.rules.to_string()),
+                (
+                    "max condition group size".to_owned(),
+                    self.formulas.max_condition_group_size.to_string(),
+                ),
+            ],
+        ));
+        blocks.push((
+            "Locales",
+            self.locales
+                .iter()
+                .map(|locale| {
+                    (
+                        locale.lang.to_owned(),
+                        format!(
+                            "{}/{} ({:.1}%)",
+                            locale.translated, locale.total, locale.percentage
+                        ),
+                    )
+                })
+                .collect(),
+        ));
+        blocks.push(("Columns per screen", counts(&self.columns_per_screen)));
+
+        let width = blocks
+            .iter()
+            .flat_map(|(_, rows)| rows.iter())
+            .map(|(name, _)| name.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut text = String::new();
+        for (title, rows) in blocks.iter().filter(|(_, rows)| !rows.is_empty()) {
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            text.push_str(title);
+            text.push('\n');
+            for (name, value) in rows {
+                text.push_str(&format!("  {:<width$}  {}\n", name, value, width = width));
+            }
+        }
+        text
+    }
+}
+
+fn increment(counts: &mut Vec<Count>, key: &str) {
+    match counts.iter_mut().find(|count| count.key == key) {
+        Some(count) => count.count += 1,
+        None => counts.push(Count {
+            key: key.to_owned(),
+            count: 1,
+        }),
+    }
+}
+
+fn counts(counts: &[Count]) -> Vec<(String, String)> {
+    counts
+        .iter()
+        .map(|count| (count.key.to_owned(), count.count.to_string()))
+        .collect()
+}
+
+fn category_tree(classification: &Classification) -> CategoryTree {
+    let categories = &classification.categories;
+    // A category whose parent is unknown is a root
+    let is_root = |key: &Option<CategoryKey>| {
+        key.as_ref()
+            .is_none_or(|parent| classification.category(parent).is_none())
+    };
+    let mut breadths = vec![];
+    let mut visited = HashSet::new();
+    let mut level = categories
+        .iter()
+        .filter(|category| is_root(&category.parent))
+        .map(|category| &category.key)
+        .collect::<Vec<&CategoryKey>>();
+    let roots = level.len();
+    // Breadth-first walk, a cycle in the parents stops it
+    while !level.is_empty() {
+        level.retain(|key| visited.insert(*key));
+        if level.is_empty() {
+            break;
+        }
+        breadths.push(level.len());
+        level = categories
+            .iter()
+            .filter(|category| {
+                category
+                    .parent
+                    .as_ref()
+                    .is_some_and(|parent| level.contains(&parent))
+            })
+            .map(|category| &category.key)
+            .collect();
+    }
+    CategoryTree {
+        classification: classification.key.to_string(),
+        categories: categories.len(),
+        roots,
+        leaves: categories
+            .iter()
+            .filter(|category| {
+                !categories
+                    .iter()
+                    .any(|child| child.parent.as_ref() == Some(&category.key))
+            })
+            .count(),
+        depth: breadths.len(),
+        breadth: breadths.into_iter().max().unwrap_or(0),
+    }
+}
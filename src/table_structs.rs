@@ -1,13 +1,20 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct Table {
     pub key: String,
     pub title: String,
     pub position: usize,
-    pub color: String,
+    pub color: Color,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub title_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description_locals: Option<Vec<Local>>,
     pub schema: Schema,
 }
@@ -17,7 +24,7 @@ impl Table {
             key: "UNKNOWN".to_owned(),
             title: "UNKNOWN".to_owned(),
             position: 0,
-            color: "BLUE".to_owned(),
+            color: Color::Blue,
             description: None,
             title_locals: None,
             description_locals: None,
@@ -38,6 +45,12 @@ impl Table {
             },
         }
     }
+    // Renumbers the sections and columns of every screen from 1, see ScreenGrid
+    pub fn normalize_positions(&mut self) {
+        for screen in self.schema.screens.iter_mut() {
+            screen.grid.normalize_positions();
+        }
+    }
     pub fn add_title_local(&mut self, local: Option<Local>) {
         match local {
             Some(local) => match self.title_locals.as_ref() {
@@ -68,28 +81,92 @@ impl Table {
 
 // Schema
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct Schema {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub partitions: Vec<Partition>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub levels: Vec<Level>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub identifiers: Vec<Identifier>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub classifications: Vec<Classification>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub fields: Vec<Field>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub formulas: Vec<Formula>,
     pub matrix: Matrix,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub conditional_formattings: Vec<ConditionalFormatting>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub sections: Vec<Section>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub screens: Vec<Screen>,
 }
+// Resolution of the references between the elements of the schema
+impl Schema {
+    pub fn level(&self, key: &LevelKey) -> Option<&Level> {
+        self.levels.iter().find(|level| &level.key == key)
+    }
+    pub fn identifier(&self, key: &IdentifierKey) -> Option<&Identifier> {
+        self.identifiers
+            .iter()
+            .find(|identifier| &identifier.key == key)
+    }
+    pub fn classification(&self, key: &ClassificationKey) -> Option<&Classification> {
+        self.classifications
+            .iter()
+            .find(|classification| &classification.key == key)
+    }
+    pub fn category(&self, reference: &CategoryRef) -> Option<&Category> {
+        self.classification(&reference.classification)
+            .and_then(|classification| classification.category(&reference.category))
+    }
+    pub fn field(&self, key: &FieldKey) -> Option<&Field> {
+        self.fields.iter().find(|field| &field.key == key)
+    }
+    pub fn conditional_formatting(
+        &self,
+        key: &ConditionalFormattingKey,
+    ) -> Option<&ConditionalFormatting> {
+        self.conditional_formattings
+            .iter()
+            .find(|conditional_formatting| &conditional_formatting.key == key)
+    }
+    pub fn section(&self, key: &SectionKey) -> Option<&Section> {
+        self.sections.iter().find(|section| &section.key == key)
+    }
+    // Condition and template sources are fields, identifiers or classifications, looked up
+    // in this order when they share a key
+    pub fn source_type(&self, key: &str) -> Option<AttributeType> {
+        if self.field(&key.into()).is_some() {
+            Some(AttributeType::Field)
+        } else if self.identifier(&key.into()).is_some() {
+            Some(AttributeType::Identifier)
+        } else if self.classification(&key.into()).is_some() {
+            Some(AttributeType::Classification)
+        } else {
+            None
+        }
+    }
+}
 
 // Partition
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct Partition {
     pub key: String,
     pub title: String,
     pub position: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub title_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Vec<Metadata>>,
 }
 impl Partition {
@@ -147,19 +224,25 @@ impl Partition {
 
 // Level
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct Level {
-    pub key: String,
+    pub key: LevelKey,
     pub index: usize,
     pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub title_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Vec<Metadata>>,
 }
 impl Level {
     pub fn new() -> Self {
         Level {
-            key: "UNKNOWN".to_owned(),
+            key: "UNKNOWN".into(),
             index: 0,
             title: "UNKNOWN".to_owned(),
             description: None,
@@ -211,22 +294,28 @@ impl Level {
 
 // Identifier
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct Identifier {
-    pub key: String,
+    pub key: IdentifierKey,
     pub index: usize,
-    pub level: String,
+    pub level: LevelKey,
     pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub title_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Vec<Metadata>>,
 }
 impl Identifier {
     pub fn new() -> Self {
         Identifier {
-            key: "UNKNOWN".to_owned(),
+            key: "UNKNOWN".into(),
             index: 0,
-            level: "UNKNOWN".to_owned(),
+            level: "UNKNOWN".into(),
             title: "UNKNOWN".to_owned(),
             description: None,
             title_locals: None,
@@ -277,19 +366,29 @@ impl Identifier {
 
 // Classification
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct Classification {
-    pub key: String,
+    pub key: ClassificationKey,
     pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub title_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Vec<Metadata>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub categories: Vec<Category>,
 }
 impl Classification {
+    pub fn category(&self, key: &CategoryKey) -> Option<&Category> {
+        self.categories.iter().find(|category| &category.key == key)
+    }
     pub fn new() -> Self {
         Classification {
-            key: "UNKNOWN".to_owned(),
+            key: "UNKNOWN".into(),
             title: "UNKNOWN".to_owned(),
             description: None,
             title_locals: None,
@@ -340,19 +439,26 @@ impl Classification {
 }
 // Category
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct Category {
-    pub key: String,
-    pub parent: Option<String>,
+    pub key: CategoryKey,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<CategoryKey>,
     pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub title_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Vec<Metadata>>,
 }
 impl Category {
     pub fn new() -> Self {
         Category {
-            key: "UNKNOWN".to_owned(),
+            key: "UNKNOWN".into(),
             parent: None,
             title: "UNKNOWN".to_owned(),
             description: None,
@@ -404,6 +510,8 @@ impl Category {
 
 // Field
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING-KEBAB-CASE")]
 pub enum DataType {
     SingleLineText,
     LongText,
@@ -437,43 +545,81 @@ impl DataType {
     }
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct Field {
-    pub key: String,
-    pub level: String,
+    pub key: FieldKey,
+    pub level: LevelKey,
+    #[serde(rename = "type")]
     pub data_type: DataType,
     pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub title_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Vec<Metadata>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub suffix: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub precision: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub suffixes: Option<Vec<Suffix>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub options: Option<Vec<SelectOption>>,
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct SelectOption {
-    pub key: String,
+    pub key: OptionKey,
     pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub title_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description_locals: Option<Vec<Local>>,
-    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Vec<Metadata>>,
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct Suffix {
-    pub key: String,
+    pub key: SuffixKey,
     pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub title_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Vec<Metadata>>,
 }
 impl Field {
-    pub fn new(key: String, level: String, data_type: DataType) -> Self {
+    pub fn option(&self, key: &OptionKey) -> Option<&SelectOption> {
+        self.options
+            .iter()
+            .flatten()
+            .find(|option| &option.key == key)
+    }
+    pub fn suffix(&self, key: &SuffixKey) -> Option<&Suffix> {
+        self.suffixes
+            .iter()
+            .flatten()
+            .find(|suffix| &suffix.key == key)
+    }
+    pub fn new(key: FieldKey, level: LevelKey, data_type: DataType) -> Self {
         Field {
             key: key,
             data_type: data_type,
@@ -533,7 +679,7 @@ impl Field {
 impl SelectOption {
     pub fn new() -> Self {
         SelectOption {
-            key: "UNKNOWN".to_owned(),
+            key: "UNKNOWN".into(),
             title: "UNKNOWN".to_owned(),
             color: None,
             description: None,
@@ -585,7 +731,7 @@ impl SelectOption {
 impl Suffix {
     pub fn new() -> Self {
         Suffix {
-            key: "UNKNOWN".to_owned(),
+            key: "UNKNOWN".into(),
             title: "UNKNOWN".to_owned(),
             default: None,
             description: None,
@@ -635,38 +781,59 @@ impl Suffix {
     }
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct Matrix {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub common: Vec<MatrixField>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub specifics: Vec<MatrixSpecific>,
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct MatrixField {
-    pub key: String,
+    pub key: FieldKey,
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct MatrixSpecific {
-    pub classification: String,
-    pub category: String,
+    pub classification: ClassificationKey,
+    pub category: CategoryKey,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub fields: Vec<MatrixField>,
 }
+impl MatrixSpecific {
+    pub fn category_ref(&self) -> CategoryRef {
+        CategoryRef::new(self.classification.to_owned(), self.category.to_owned())
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct ConditionalFormatting {
-    pub key: String,
-    pub level: String,
+    pub key: ConditionalFormattingKey,
+    pub level: LevelKey,
     pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub title_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Vec<Metadata>>,
     pub default_status: DefaultStatus,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub statuses: Vec<Status>,
 }
 impl ConditionalFormatting {
     pub fn new() -> Self {
         ConditionalFormatting {
-            key: "UNKNOWN".to_owned(),
-            level: "UNKNOWN".to_owned(),
+            key: "UNKNOWN".into(),
+            level: "UNKNOWN".into(),
             title: "UNKNOWN".to_owned(),
             description: None,
             title_locals: None,
@@ -717,13 +884,19 @@ impl ConditionalFormatting {
     }
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct DefaultStatus {
     pub key: String,
     pub title: String,
-    pub color: String,
+    pub color: Color,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub title_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Vec<Metadata>>,
 }
 impl DefaultStatus {
@@ -731,7 +904,7 @@ impl DefaultStatus {
         DefaultStatus {
             key: "UNKNOWN".to_owned(),
             title: "UNKNOWN".to_owned(),
-            color: "NONE".to_owned(),
+            color: Color::None,
             description: None,
             title_locals: None,
             description_locals: None,
@@ -779,14 +952,20 @@ impl DefaultStatus {
     }
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct Status {
     pub key: String,
     pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub title_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Vec<Metadata>>,
-    pub color: String,
+    pub color: Color,
     pub priority: usize,
     pub rules: Rules,
 }
@@ -795,7 +974,7 @@ impl Status {
         Status {
             key: "UNKNOWN".to_owned(),
             title: "UNKNOWN".to_owned(),
-            color: "NONE".to_owned(),
+            color: Color::None,
             description: None,
             title_locals: None,
             description_locals: None,
@@ -848,32 +1027,55 @@ impl Status {
     }
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct Rules {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub common: Vec<CommonAttributeRules>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub specifics: Vec<SpecificRules>,
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
 pub enum AttributeType {
     Identifier,
     Classification,
     Field,
 }
+impl AttributeType {
+    pub fn element_name(&self) -> &'static str {
+        match self {
+            AttributeType::Identifier => "Identifier",
+            AttributeType::Classification => "Classification",
+            AttributeType::Field => "Field",
+        }
+    }
+}
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct CommonAttributeRules {
     pub attribute_type: AttributeType,
     pub key: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub controls: Vec<Control>,
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct SpecificRules {
-    pub classification: String,
-    pub category: String,
+    pub classification: ClassificationKey,
+    pub category: CategoryKey,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub attributes: Vec<SpecificAttributeRules>,
 }
 impl SpecificRules {
+    pub fn category_ref(&self) -> CategoryRef {
+        CategoryRef::new(self.classification.to_owned(), self.category.to_owned())
+    }
     pub fn new(
-        classification: String,
-        category: String,
+        classification: ClassificationKey,
+        category: CategoryKey,
         attributes: Vec<SpecificAttributeRules>,
     ) -> Self {
         SpecificRules {
@@ -884,115 +1086,156 @@ impl SpecificRules {
     }
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct SpecificAttributeRules {
-    pub key: String,
+    pub key: FieldKey,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub controls: Vec<Control>,
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(tag = "type", rename_all_fields = "camelCase")]
 pub enum Control {
+    #[serde(rename = "Rule-Required")]
     RuleRequired,
+    #[serde(rename = "Rule-Is-Leaf")]
     RuleIsLeaf,
+    #[serde(rename = "Rule-Min-Length")]
     RuleMinLength {
         min: usize,
     },
+    #[serde(rename = "Rule-Max-Length")]
     RuleMaxLength {
         max: usize,
     },
+    #[serde(rename = "Rule-Regex")]
     RuleRegex {
         regex: String,
     },
+    #[serde(rename = "Rule-Barcode")]
     RuleBarcode {
-        barcode_type: String,
+        barcode_type: BarcodeType,
     },
+    #[serde(rename = "Rule-Less-Than")]
     RuleLessThan {
         value: usize,
     },
+    #[serde(rename = "Rule-Greater-Than")]
     RuleGreaterThan {
         value: usize,
     },
+    #[serde(rename = "Rule-Less-Than-Or-Equal")]
     RuleLessThanOrEqual {
         value: usize,
     },
+    #[serde(rename = "Rule-Greater-Than-Or-Equal")]
     RuleGreaterThanOrEqual {
         value: usize,
     },
+    #[serde(rename = "Rule-Decimal-Places")]
     RuleDecimalPlaces {
         precision: usize,
     },
+    #[serde(rename = "Rule-Min-Width-Px")]
     RuleMinWidthPx {
         min: usize,
     },
+    #[serde(rename = "Rule-Max-Width-Px")]
     RuleMaxWidthPx {
         max: usize,
     },
+    #[serde(rename = "Rule-Min-Height-Px")]
     RuleMinHeightPx {
         min: usize,
     },
+    #[serde(rename = "Rule-Max-Height-Px")]
     RuleMaxHeightPx {
         max: usize,
     },
+    #[serde(rename = "Rule-Max-Size-Kb")]
     RuleMaxSizeKb {
         max: usize,
     },
+    #[serde(rename = "Rule-Extension")]
     RuleExtension {
         extension: String,
     },
+    #[serde(rename = "Rule-Color-Space")]
     RuleColorSpace {
         name: String,
     },
+    #[serde(rename = "Rule-Color-Profile")]
     RuleColorProfile {
         name: String,
     },
+    #[serde(rename = "Rule-Min-Values")]
     RuleMinValues {
         min: usize,
     },
+    #[serde(rename = "Rule-Max-Values")]
     RuleMaxValues {
         max: usize,
     },
     // Inter fields
+    #[serde(rename = "Rule-Must-Be-Greater-Than-Another-Field")]
     RuleMustBeGreaterThanAnotherField {
-        field: String,
+        field: FieldKey,
     },
+    #[serde(rename = "Rule-Must-Be-Greater-Than-Or-Equal-Another-Field")]
     RuleMustBeGreaterThanOrEqualAnotherField {
-        field: String,
+        field: FieldKey,
     },
+    #[serde(rename = "Rule-Must-Be-Less-Than-Another-Field")]
     RuleMustBeLessThanAnotherField {
-        field: String,
+        field: FieldKey,
     },
+    #[serde(rename = "Rule-Must-Be-Less-Than-Or-Equal-Another-Field")]
     RuleMustBeLessThanOrEqualAnotherField {
-        field: String,
+        field: FieldKey,
     },
+    #[serde(rename = "Rule-Required-If-Another-Field-Is-Not-Empty")]
     RuleRequiredIfAnotherFieldIsNotEmpty {
-        field: String,
+        field: FieldKey,
     },
+    #[serde(rename = "Rule-Required-If-Another-Field-Has-Options")]
     RuleRequiredIfAnotherFieldHasOptions {
-        field: String,
+        field: FieldKey,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
         options: Vec<OptionRule>,
     },
+    #[serde(rename = "Rule-Required-If-Another-Field-Is-Greater-Than")]
     RuleRequiredIfAnotherFieldIsGreaterThan {
-        field: String,
+        field: FieldKey,
         value: String,
     },
+    #[serde(rename = "Rule-Required-If-Another-Field-Is-Greater-Than-Or-Equal")]
     RuleRequiredIfAnotherFieldIsGreaterThanOrEqual {
-        field: String,
+        field: FieldKey,
         value: String,
     },
+    #[serde(rename = "Rule-Required-If-Another-Field-Is-Less-Than")]
     RuleRequiredIfAnotherFieldIsLessThan {
-        field: String,
+        field: FieldKey,
         value: String,
     },
+    #[serde(rename = "Rule-Required-If-Another-Field-Is-Less-Than-Or-Equal")]
     RuleRequiredIfAnotherFieldIsLessThanOrEqual {
-        field: String,
+        field: FieldKey,
         value: String,
     },
+    #[serde(rename = "Rule-Required-If-Another-Field-Is-Equal-To")]
     RuleRequiredIfAnotherFieldIsEqualTo {
-        field: String,
+        field: FieldKey,
         value: String,
     },
+    #[serde(rename = "Rule-Condition")]
     RuleCondition {
         key: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
         condition_groups: Vec<ConditionGroup>,
         title: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         title_locals: Option<Vec<Local>>,
     },
 }
@@ -1036,16 +1279,113 @@ impl Control {
             *condition_groups = conditions;
         }
     }
+    // Key of the other field referenced by inter fields controls
+    pub fn field(&self) -> Option<&FieldKey> {
+        match self {
+            Control::RuleMustBeGreaterThanAnotherField { field }
+            | Control::RuleMustBeGreaterThanOrEqualAnotherField { field }
+            | Control::RuleMustBeLessThanAnotherField { field }
+            | Control::RuleMustBeLessThanOrEqualAnotherField { field }
+            | Control::RuleRequiredIfAnotherFieldIsNotEmpty { field }
+            | Control::RuleRequiredIfAnotherFieldHasOptions { field, .. }
+            | Control::RuleRequiredIfAnotherFieldIsGreaterThan { field, .. }
+            | Control::RuleRequiredIfAnotherFieldIsGreaterThanOrEqual { field, .. }
+            | Control::RuleRequiredIfAnotherFieldIsLessThan { field, .. }
+            | Control::RuleRequiredIfAnotherFieldIsLessThanOrEqual { field, .. }
+            | Control::RuleRequiredIfAnotherFieldIsEqualTo { field, .. } => Some(field),
+            _ => None,
+        }
+    }
+    pub fn field_mut(&mut self) -> Option<&mut FieldKey> {
+        match self {
+            Control::RuleMustBeGreaterThanAnotherField { field }
+            | Control::RuleMustBeGreaterThanOrEqualAnotherField { field }
+            | Control::RuleMustBeLessThanAnotherField { field }
+            | Control::RuleMustBeLessThanOrEqualAnotherField { field }
+            | Control::RuleRequiredIfAnotherFieldIsNotEmpty { field }
+            | Control::RuleRequiredIfAnotherFieldHasOptions { field, .. }
+            | Control::RuleRequiredIfAnotherFieldIsGreaterThan { field, .. }
+            | Control::RuleRequiredIfAnotherFieldIsGreaterThanOrEqual { field, .. }
+            | Control::RuleRequiredIfAnotherFieldIsLessThan { field, .. }
+            | Control::RuleRequiredIfAnotherFieldIsLessThanOrEqual { field, .. }
+            | Control::RuleRequiredIfAnotherFieldIsEqualTo { field, .. } => Some(field),
+            _ => None,
+        }
+    }
+    pub fn element_name(&self) -> &'static str {
+        match self {
+            Control::RuleRequired => "Rule-Required",
+            Control::RuleIsLeaf => "Rule-Is-Leaf",
+            Control::RuleMinLength { .. } => "Rule-Min-Length",
+            Control::RuleMaxLength { .. } => "Rule-Max-Length",
+            Control::RuleRegex { .. } => "Rule-Regex",
+            Control::RuleBarcode { .. } => "Rule-Barcode",
+            Control::RuleLessThan { .. } => "Rule-Less-Than",
+            Control::RuleGreaterThan { .. } => "Rule-Greater-Than",
+            Control::RuleLessThanOrEqual { .. } => "Rule-Less-Than-Or-Equal",
+            Control::RuleGreaterThanOrEqual { .. } => "Rule-Greater-Than-Or-Equal",
+            Control::RuleDecimalPlaces { .. } => "Rule-Decimal-Places",
+            Control::RuleMinWidthPx { .. } => "Rule-Min-Width-Px",
+            Control::RuleMaxWidthPx { .. } => "Rule-Max-Width-Px",
+            Control::RuleMinHeightPx { .. } => "Rule-Min-Height-Px",
+            Control::RuleMaxHeightPx { .. } => "Rule-Max-Height-Px",
+            Control::RuleMaxSizeKb { .. } => "Rule-Max-Size-Kb",
+            Control::RuleExtension { .. } => "Rule-Extension",
+            Control::RuleColorSpace { .. } => "Rule-Color-Space",
+            Control::RuleColorProfile { .. } => "Rule-Color-Profile",
+            Control::RuleMinValues { .. } => "Rule-Min-Values",
+            Control::RuleMaxValues { .. } => "Rule-Max-Values",
+            Control::RuleMustBeGreaterThanAnotherField { .. } => {
+                "Rule-Must-Be-Greater-Than-Another-Field"
+            }
+            Control::RuleMustBeGreaterThanOrEqualAnotherField { .. } => {
+                "Rule-Must-Be-Greater-Than-Or-Equal-Another-Field"
+            }
+            Control::RuleMustBeLessThanAnotherField { .. } => "Rule-Must-Be-Less-Than-Another-Field",
+            Control::RuleMustBeLessThanOrEqualAnotherField { .. } => {
+                "Rule-Must-Be-Less-Than-Or-Equal-Another-Field"
+            }
+            Control::RuleRequiredIfAnotherFieldIsNotEmpty { .. } => {
+                "Rule-Required-If-Another-Field-Is-Not-Empty"
+            }
+            Control::RuleRequiredIfAnotherFieldHasOptions { .. } => {
+                "Rule-Required-If-Another-Field-Has-Options"
+            }
+            Control::RuleRequiredIfAnotherFieldIsGreaterThan { .. } => {
+                "Rule-Required-If-Another-Field-Is-Greater-Than"
+            }
+            Control::RuleRequiredIfAnotherFieldIsGreaterThanOrEqual { .. } => {
+                "Rule-Required-If-Another-Field-Is-Greater-Than-Or-Equal"
+            }
+            Control::RuleRequiredIfAnotherFieldIsLessThan { .. } => {
+                "Rule-Required-If-Another-Field-Is-Less-Than"
+            }
+            Control::RuleRequiredIfAnotherFieldIsLessThanOrEqual { .. } => {
+                "Rule-Required-If-Another-Field-Is-Less-Than-Or-Equal"
+            }
+            Control::RuleRequiredIfAnotherFieldIsEqualTo { .. } => {
+                "Rule-Required-If-Another-Field-Is-Equal-To"
+            }
+            Control::RuleCondition { .. } => "Rule-Condition",
+        }
+    }
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct OptionRule {
-    pub key: String,
+    pub key: OptionKey,
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct ConditionGroup {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub conditions: Vec<Condition>,
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE", rename_all_fields = "camelCase")]
 pub enum Condition {
     Empty {
         source: String,
@@ -1060,6 +1400,7 @@ pub enum Condition {
     Equals {
         source: String,
         value: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         use_suffix: Option<UseSuffix>,
     },
     NotEquals {
@@ -1092,14 +1433,54 @@ pub enum Condition {
     },
     In {
         source: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
         values: Vec<String>,
     },
     NotIn {
         source: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
         values: Vec<String>,
     },
 }
+impl Condition {
+    pub fn source(&self) -> &String {
+        match self {
+            Condition::Empty { source }
+            | Condition::NotEmpty { source }
+            | Condition::Contains { source, .. }
+            | Condition::Equals { source, .. }
+            | Condition::NotEquals { source, .. }
+            | Condition::StartsWith { source, .. }
+            | Condition::EndsWith { source, .. }
+            | Condition::LessThan { source, .. }
+            | Condition::LessThanOrEqual { source, .. }
+            | Condition::GreaterThan { source, .. }
+            | Condition::GreaterThanOrEqual { source, .. }
+            | Condition::In { source, .. }
+            | Condition::NotIn { source, .. } => source,
+        }
+    }
+    pub fn source_mut(&mut self) -> &mut String {
+        match self {
+            Condition::Empty { source }
+            | Condition::NotEmpty { source }
+            | Condition::Contains { source, .. }
+            | Condition::Equals { source, .. }
+            | Condition::NotEquals { source, .. }
+            | Condition::StartsWith { source, .. }
+            | Condition::EndsWith { source, .. }
+            | Condition::LessThan { source, .. }
+            | Condition::LessThanOrEqual { source, .. }
+            | Condition::GreaterThan { source, .. }
+            | Condition::GreaterThanOrEqual { source, .. }
+            | Condition::In { source, .. }
+            | Condition::NotIn { source, .. } => source,
+        }
+    }
+}
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum UseSuffix {
     Value,
     SuffixKey,
@@ -1115,29 +1496,41 @@ impl UseSuffix {
     }
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct Formula {
     pub attribute_type: AttributeType,
     pub key: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub rules: Vec<Rule>,
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct Rule {
     pub priority: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub conditions: Vec<ConditionGroup>,
     pub action: Action,
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(tag = "type", rename_all_fields = "camelCase")]
 pub enum Action {
+    #[serde(rename = "SET_TEXT")]
     SetTextTemplate {
         trim_spaces: bool,
         value: String,
     },
+    #[serde(rename = "SET_NUMBER")]
     SetNumberTemplate {
         precision: usize,
-        round: String,
+        round: Rounding,
         value: String,
     },
+    #[serde(rename = "SET_SELECTABLE_OPTIONS")]
     SetSelectableOptions {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
         values: Vec<String>,
     },
 }
@@ -1152,18 +1545,24 @@ impl Action {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct Section {
-    pub key: String,
+    pub key: SectionKey,
     pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub title_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Vec<Metadata>>,
 }
 impl Section {
     pub fn new() -> Self {
         Section {
-            key: "UNKNOWN".to_owned(),
+            key: "UNKNOWN".into(),
             title: "UNKNOWN".to_owned(),
             description: None,
             title_locals: None,
@@ -1213,14 +1612,20 @@ impl Section {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct Screen {
     pub key: String,
-    pub level: String,
+    pub level: LevelKey,
     pub position: usize,
     pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub title_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description_locals: Option<Vec<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Vec<Metadata>>,
     pub grid: ScreenGrid,
 }
@@ -1228,7 +1633,7 @@ impl Screen {
     pub fn new() -> Self {
         Screen {
             key: "UNKNOWN".to_owned(),
-            level: "UNKNOWN".to_owned(),
+            level: "UNKNOWN".into(),
             title: "UNKNOWN".to_owned(),
             position: 0,
             description: None,
@@ -1236,7 +1641,7 @@ impl Screen {
             description_locals: None,
             metadata: None,
             grid: ScreenGrid {
-                line_height: "MEDIUM".to_owned(),
+                line_height: LineHeight::Medium,
                 common: vec![],
                 specifics: vec![],
             },
@@ -1283,79 +1688,430 @@ impl Screen {
     }
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct ScreenGrid {
-    pub line_height: String,
+    pub line_height: LineHeight,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub common: Vec<CommonSection>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub specifics: Vec<GridSpecific>,
 }
+impl ScreenGrid {
+    // Sorts sections and columns by position and renumbers them from 1 without gaps, the
+    // common sections and the sections of each specific are numbered separately
+    pub fn normalize_positions(&mut self) {
+        self.common.sort_by_key(|section| section.position);
+        for (index, section) in self.common.iter_mut().enumerate() {
+            section.position = index + 1;
+            section.columns.sort_by_key(|column| column.position());
+            for (index, column) in section.columns.iter_mut().enumerate() {
+                column.set_position(index as u32 + 1);
+            }
+        }
+        for grid_specific in self.specifics.iter_mut() {
+            grid_specific
+                .sections
+                .sort_by_key(|section| section.position);
+            for (index, section) in grid_specific.sections.iter_mut().enumerate() {
+                section.position = index as u32 + 1;
+                section.columns.sort_by_key(|column| column.position);
+                for (index, column) in section.columns.iter_mut().enumerate() {
+                    column.position = index as u32 + 1;
+                }
+            }
+        }
+    }
+}
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct CommonSection {
-    pub key: String,
+    pub key: SectionKey,
     pub position: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub columns: Vec<CommonColumn>,
 }
 impl CommonSection {
     pub fn new() -> Self {
         CommonSection {
-            key: "UNKNOWN".to_owned(),
+            key: "UNKNOWN".into(),
             position: 0,
             columns: vec![],
         }
     }
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(tag = "type")]
 pub enum CommonColumn {
-    ColumnIdentifier(Column),
-    ColumnClassification(Column),
-    ColumnConditionalFormatting(Column),
-    ColumnField(Column),
+    #[serde(rename = "Column-Identifier")]
+    ColumnIdentifier(Column<IdentifierKey>),
+    #[serde(rename = "Column-Classification")]
+    ColumnClassification(Column<ClassificationKey>),
+    #[serde(rename = "Column-Conditional-Formatting")]
+    ColumnConditionalFormatting(Column<ConditionalFormattingKey>),
+    #[serde(rename = "Column-Field")]
+    ColumnField(Column<FieldKey>),
+}
+impl CommonColumn {
+    pub fn element_name(&self) -> &'static str {
+        match self {
+            CommonColumn::ColumnIdentifier(_) => "Column-Identifier",
+            CommonColumn::ColumnClassification(_) => "Column-Classification",
+            CommonColumn::ColumnConditionalFormatting(_) => "Column-Conditional-Formatting",
+            CommonColumn::ColumnField(_) => "Column-Field",
+        }
+    }
+    // Key of the identifier, classification, conditional formatting or field of the column
+    pub fn key(&self) -> &str {
+        match self {
+            CommonColumn::ColumnIdentifier(column) => &column.key,
+            CommonColumn::ColumnClassification(column) => &column.key,
+            CommonColumn::ColumnConditionalFormatting(column) => &column.key,
+            CommonColumn::ColumnField(column) => &column.key,
+        }
+    }
+    pub fn position(&self) -> u32 {
+        match self {
+            CommonColumn::ColumnIdentifier(column) => column.position,
+            CommonColumn::ColumnClassification(column) => column.position,
+            CommonColumn::ColumnConditionalFormatting(column) => column.position,
+            CommonColumn::ColumnField(column) => column.position,
+        }
+    }
+    pub fn set_position(&mut self, position: u32) {
+        match self {
+            CommonColumn::ColumnIdentifier(column) => column.position = position,
+            CommonColumn::ColumnClassification(column) => column.position = position,
+            CommonColumn::ColumnConditionalFormatting(column) => column.position = position,
+            CommonColumn::ColumnField(column) => column.position = position,
+        }
+    }
+    pub fn is_fixed(&self) -> bool {
+        match self {
+            CommonColumn::ColumnIdentifier(column) => column.is_fixed(),
+            CommonColumn::ColumnClassification(column) => column.is_fixed(),
+            CommonColumn::ColumnConditionalFormatting(column) => column.is_fixed(),
+            CommonColumn::ColumnField(column) => column.is_fixed(),
+        }
+    }
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct GridSpecific {
-    pub classification: String,
-    pub category: String,
+    pub classification: ClassificationKey,
+    pub category: CategoryKey,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub sections: Vec<SpecificSection>,
 }
 impl GridSpecific {
+    pub fn category_ref(&self) -> CategoryRef {
+        CategoryRef::new(self.classification.to_owned(), self.category.to_owned())
+    }
     pub fn new() -> Self {
         GridSpecific {
-            classification: "UNKNOWN".to_owned(),
-            category: "UNKNOWN".to_owned(),
+            classification: "UNKNOWN".into(),
+            category: "UNKNOWN".into(),
             sections: vec![],
         }
     }
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct SpecificSection {
-    pub key: String,
+    pub key: SectionKey,
     pub position: u32,
-    pub columns: Vec<Column>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<Column<FieldKey>>,
 }
 impl SpecificSection {
     pub fn new() -> Self {
         SpecificSection {
-            key: "UNKNOWN".to_owned(),
+            key: "UNKNOWN".into(),
             position: 0,
             columns: vec![],
         }
     }
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct Column {
-    pub key: String,
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Column<K> {
+    pub key: K,
     pub position: u32,
-    pub width: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<ColumnWidth>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub read_only: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub fixed: Option<bool>,
 }
+impl<K> Column<K> {
+    pub fn is_fixed(&self) -> bool {
+        self.fixed.unwrap_or(false)
+    }
+}
+
+// TypeScript alias of a type serialized as a string. ts-rs can't derive it from the serde
+// attributes transparent, from and into.
+#[cfg(test)]
+macro_rules! ts_alias {
+    ($name:ident, $inline:expr) => {
+        impl ts_rs::TS for $name {
+            type WithoutGenerics = Self;
+            type OptionInnerType = Self;
+            fn name() -> String {
+                stringify!($name).to_owned()
+            }
+            fn decl() -> String {
+                format!("type {} = {};", Self::name(), Self::inline())
+            }
+            fn decl_concrete() -> String {
+                Self::decl()
+            }
+            fn inline() -> String {
+                $inline
+            }
+            fn inline_flattened() -> String {
+                panic!("{} cannot be flattened", Self::name())
+            }
+        }
+    };
+}
+
+// Keys
+// Keys of the elements which are referenced by other elements. Each kind of key has its
+// own type so that a level key can't be given where a field key is expected, they are
+// serialized as plain strings.
+macro_rules! key_type {
+    ($(#[$doc:meta])* $name:ident) => {
+        $(#[$doc])*
+        #[derive(
+            Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+        )]
+        #[cfg_attr(test, derive(schemars::JsonSchema))]
+        #[serde(transparent)]
+        pub struct $name(pub String);
+        #[cfg(test)]
+        ts_alias!($name, "string".to_owned());
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+        impl std::ops::Deref for $name {
+            type Target = str;
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+        impl std::borrow::Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                $name(value.to_owned())
+            }
+        }
+        impl From<String> for $name {
+            fn from(value: String) -> Self {
+                $name(value)
+            }
+        }
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+        impl PartialEq<String> for $name {
+            fn eq(&self, other: &String) -> bool {
+                &self.0 == other
+            }
+        }
+        impl PartialEq<$name> for str {
+            fn eq(&self, other: &$name) -> bool {
+                self == other.0
+            }
+        }
+        impl PartialEq<$name> for &str {
+            fn eq(&self, other: &$name) -> bool {
+                *self == other.0
+            }
+        }
+        impl PartialEq<$name> for String {
+            fn eq(&self, other: &$name) -> bool {
+                *self == other.0
+            }
+        }
+    };
+}
+key_type!(LevelKey);
+key_type!(IdentifierKey);
+key_type!(ClassificationKey);
+// Unique inside its classification only, see CategoryRef
+key_type!(CategoryKey);
+key_type!(FieldKey);
+// Unique inside its field only
+key_type!(OptionKey);
+// Unique inside its field only
+key_type!(SuffixKey);
+key_type!(ConditionalFormattingKey);
+key_type!(SectionKey);
+
+// A category, identified by its classification and its key
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryRef {
+    pub classification: ClassificationKey,
+    pub category: CategoryKey,
+}
+impl CategoryRef {
+    pub fn new(classification: ClassificationKey, category: CategoryKey) -> Self {
+        CategoryRef {
+            classification,
+            category,
+        }
+    }
+}
+
+// Values
+// Enums of known values which keep the values they don't know, so that they are written
+// back as they were read. The parser warns about unknown values.
+pub trait OpenEnum {
+    fn unknown(&self) -> Option<&str>;
+}
+macro_rules! open_enum {
+    ($name:ident { $($variant:ident => $value:literal),* $(,)? }) => {
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        #[cfg_attr(test, derive(schemars::JsonSchema), schemars(with = "String"))]
+        #[serde(from = "String", into = "String")]
+        pub enum $name {
+            $($variant,)*
+            Unknown(String),
+        }
+        // The known values, then any string for the unknown ones. `string & {}` keeps the
+        // known values from being absorbed by string, so editors still suggest them.
+        #[cfg(test)]
+        ts_alias!(
+            $name,
+            [$(concat!("\"", $value, "\""),)* "(string & {})"].join(" | ")
+        );
+        impl OpenEnum for $name {
+            fn unknown(&self) -> Option<&str> {
+                match self {
+                    $name::Unknown(value) => Some(value),
+                    _ => None,
+                }
+            }
+        }
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => $name::$variant,)*
+                    _ => $name::Unknown(value.to_owned()),
+                }
+            }
+        }
+        impl From<String> for $name {
+            fn from(value: String) -> Self {
+                $name::from(value.as_str())
+            }
+        }
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.to_string()
+            }
+        }
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(match self {
+                    $($name::$variant => $value,)*
+                    $name::Unknown(value) => value,
+                })
+            }
+        }
+    };
+}
+// Colors of tables, options and statuses
+open_enum!(Color {
+    None => "NONE",
+    Grey => "GREY",
+    Blue => "BLUE",
+    Green => "GREEN",
+    Yellow => "YELLOW",
+    Orange => "ORANGE",
+    Red => "RED",
+    Pink => "PINK",
+    Purple => "PURPLE",
+    Brown => "BROWN",
+});
+// Height of the lines of a screen grid
+open_enum!(LineHeight {
+    Short => "SHORT",
+    Medium => "MEDIUM",
+    Tall => "TALL",
+    ExtraTall => "EXTRA_TALL",
+});
+// Width of a column of a screen grid
+open_enum!(ColumnWidth {
+    Small => "SMALL",
+    Medium => "MEDIUM",
+    Large => "LARGE",
+});
+// Rounding of the result of a SET_NUMBER action
+open_enum!(Rounding {
+    Up => "UP",
+    Down => "DOWN",
+    Ceiling => "CEILING",
+    Floor => "FLOOR",
+    HalfUp => "HALF_UP",
+    HalfDown => "HALF_DOWN",
+    HalfEven => "HALF_EVEN",
+});
+// Barcodes checked by Rule-Barcode
+open_enum!(BarcodeType {
+    Ean8 => "EAN8",
+    Ean13 => "EAN13",
+    Upca => "UPCA",
+    Gtin14 => "GTIN14",
+});
 
 // Utils
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct Local {
     pub lang: String,
     pub value: String,
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
 pub struct Metadata {
     pub key: String,
     pub value: String,
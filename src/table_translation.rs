@@ -0,0 +1,426 @@
+use std::{collections::HashMap, error::Error};
+
+use quick_xml::{
+    events::{BytesDecl, BytesText, Event},
+    Reader, Writer,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    table_locals::{localized_texts, set_locals},
+    table_structs::Table,
+    table_visitor::local_xpath,
+};
+
+const XLIFF_NAMESPACE: &str = "urn:oasis:names:tc:xliff:document:2.0";
+
+// Result of an import. Entries are identified by the xpath of the Title or Description
+// element they translate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranslationReport {
+    pub lang: String,
+    // Texts whose local was set from the file
+    pub translated: Vec<String>,
+    // Texts still without local in the lang after the import
+    pub missing: Vec<String>,
+    // Entries of the file whose source text changed or no longer exists, they are skipped
+    pub stale: Vec<String>,
+}
+
+// Share of the translatable texts having a local in a lang, missing texts are
+// identified by the xpath of their Title or Description element
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LangCompleteness {
+    pub lang: String,
+    pub total: usize,
+    pub translated: usize,
+    pub percentage: f64,
+    pub missing: Vec<String>,
+    // Locals identical to their text, counted as translated
+    pub identical: Vec<String>,
+}
+
+// A local equal to the text it translates. It may be a copy-paste left untranslated, but
+// codes and units often read the same in both langs, so it is only worth a review.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdenticalLocal {
+    // Xpath of the Title-Local or Description-Local element
+    pub xpath: String,
+    // Title or Description
+    pub element: String,
+    pub lang: String,
+}
+impl IdenticalLocal {
+    pub fn message(&self) -> String {
+        format!(
+            "{}-Local '{}' is identical to the {}.",
+            self.element,
+            self.lang,
+            self.element.to_lowercase()
+        )
+    }
+}
+
+// One translatable text as found in a translation file
+struct Entry {
+    xpath: String,
+    source: String,
+    target: Option<String>,
+}
+
+// XLIFF 2.0 with one unit per text, the unit name is the xpath of the text:
+//   <unit id="u1" name="/Table/Schema/Fields/Field[@key='COLOR']/Title">
+//     <segment state="translated"><source>Color</source><target>Couleur</target></segment>
+//   </unit>
+pub fn to_xliff(table: &Table, source_lang: &str, lang: &str) -> Result<String, Box<dyn Error>> {
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+    writer
+        .create_element("xliff")
+        .with_attributes([
+            ("xmlns", XLIFF_NAMESPACE),
+            ("version", "2.0"),
+            ("srcLang", source_lang),
+            ("trgLang", lang),
+        ])
+        .write_inner_content::<_, quick_xml::Error>(|writer| {
+            writer
+                .create_element("file")
+                .with_attributes([("id", "f1"), ("original", table.key.as_str())])
+                .write_inner_content::<_, quick_xml::Error>(|writer| {
+                    for (index, text) in localized_texts(table).iter().enumerate() {
+                        let target = text.local(lang);
+                        writer
+                            .create_element("unit")
+                            .with_attributes([
+                                ("id", format!("u{}", index + 1).as_str()),
+                                ("name", text.xpath.as_str()),
+                            ])
+                            .write_inner_content::<_, quick_xml::Error>(|writer| {
+                                let state = match target {
+                                    Some(_) => "translated",
+                                    None => "initial",
+                                };
+                                writer
+                                    .create_element("segment")
+                                    .with_attribute(("state", state))
+                                    .write_inner_content::<_, quick_xml::Error>(|writer| {
+                                        writer
+                                            .create_element("source")
+                                            .write_text_content(BytesText::new(&text.text))?;
+                                        if let Some(target) = target {
+                                            writer
+                                                .create_element("target")
+                                                .write_text_content(BytesText::new(target))?;
+                                        }
+                                        Ok(())
+                                    })?;
+                                Ok(())
+                            })?;
+                    }
+                    Ok(())
+                })?;
+            Ok(())
+        })?;
+    Ok(String::from_utf8(writer.into_inner())?)
+}
+
+pub fn from_xliff(table: &mut Table, xliff: &str) -> Result<TranslationReport, Box<dyn Error>> {
+    let mut reader = Reader::from_str(xliff);
+    let mut lang = None;
+    let mut entries = vec![];
+    let mut entry: Option<Entry> = None;
+    // Text of the source or target element being read
+    let mut text: Option<String> = None;
+    loop {
+        match reader.read_event()? {
+            Event::Start(ev) | Event::Empty(ev) => match ev.local_name().as_ref() {
+                b"xliff" => {
+                    if let Some(attribute) = ev.try_get_attribute("trgLang")? {
+                        lang = Some(attribute.unescape_value()?.to_string());
+                    }
+                }
+                b"unit" => {
+                    if let Some(attribute) = ev.try_get_attribute("name")? {
+                        entry = Some(Entry {
+                            xpath: attribute.unescape_value()?.to_string(),
+                            source: String::new(),
+                            target: None,
+                        });
+                    }
+                }
+                b"source" | b"target" => text = Some(String::new()),
+                _ => (),
+            },
+            Event::Text(ev) => {
+                if let Some(text) = text.as_mut() {
+                    text.push_str(&ev.unescape()?);
+                }
+            }
+            Event::CData(ev) => {
+                if let Some(text) = text.as_mut() {
+                    text.push_str(&String::from_utf8_lossy(&ev));
+                }
+            }
+            Event::End(ev) => match ev.local_name().as_ref() {
+                b"source" => {
+                    if let (Some(entry), Some(text)) = (entry.as_mut(), text.take()) {
+                        entry.source.push_str(&text);
+                    }
+                }
+                b"target" => {
+                    if let (Some(entry), Some(text)) = (entry.as_mut(), text.take()) {
+                        entry.target.get_or_insert_with(String::new).push_str(&text);
+                    }
+                }
+                b"unit" => entries.extend(entry.take()),
+                _ => (),
+            },
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    let lang = lang.ok_or("XLIFF file has no trgLang attribute.")?;
+    Ok(import(table, &lang, entries))
+}
+
+// Gettext PO with one entry per text, the context is the xpath of the text:
+//   #: /Table/Schema/Fields/Field[@key='COLOR']/Title
+//   msgctxt "/Table/Schema/Fields/Field[@key='COLOR']/Title"
+//   msgid "Color"
+//   msgstr "Couleur"
+pub fn to_po(table: &Table, lang: &str) -> String {
+    let mut po = format!(
+        "# Translations of the table {}\nmsgid \"\"\nmsgstr \"\"\n\"Language: {}\\n\"\n\"MIME-Version: 1.0\\n\"\n\"Content-Type: text/plain; charset=UTF-8\\n\"\n\"Content-Transfer-Encoding: 8bit\\n\"\n",
+        table.key, lang
+    );
+    for text in localized_texts(table).iter() {
+        po.push_str(&format!(
+            "\n#: {}\nmsgctxt {}\nmsgid {}\nmsgstr {}\n",
+            text.xpath,
+            po_string(&text.xpath),
+            po_string(&text.text),
+            po_string(text.local(lang).unwrap_or_default())
+        ));
+    }
+    po
+}
+
+// Entry of a PO file being read
+#[derive(Default)]
+struct PoEntry {
+    context: Option<String>,
+    id: Option<String>,
+    value: Option<String>,
+    fuzzy: bool,
+}
+impl PoEntry {
+    fn is_empty(&self) -> bool {
+        self.context.is_none() && self.id.is_none() && self.value.is_none() && !self.fuzzy
+    }
+    fn text_mut(&mut self, keyword: &str) -> Option<&mut Option<String>> {
+        match keyword {
+            "msgctxt" => Some(&mut self.context),
+            "msgid" => Some(&mut self.id),
+            "msgstr" => Some(&mut self.value),
+            _ => None,
+        }
+    }
+    // The header entry gives the lang, the others are the translated texts
+    fn read(self, lang: &mut Option<String>, entries: &mut Vec<Entry>) {
+        match (self.context, self.id) {
+            (Some(xpath), Some(source)) => entries.push(Entry {
+                xpath,
+                source,
+                target: self.value.filter(|_| !self.fuzzy),
+            }),
+            (None, Some(id)) if id.is_empty() => {
+                *lang = self.value.unwrap_or_default().lines().find_map(|line| {
+                    line.strip_prefix("Language:")
+                        .map(|lang| lang.trim().to_owned())
+                });
+            }
+            _ => (),
+        }
+    }
+}
+
+// Entries are separated by blank lines, which may hold spaces, and an entry also ends at
+// the comment, msgctxt or msgid following its msgstr. Fuzzy entries are not imported, as
+// gettext tools do. The texts of a table have no plural forms, plural entries are refused.
+pub fn from_po(table: &mut Table, po: &str) -> Result<TranslationReport, Box<dyn Error>> {
+    let mut lang = None;
+    let mut entries = vec![];
+    let mut entry = PoEntry::default();
+    let mut number = 1;
+    // Keyword the continuation lines are appended to
+    let mut current = None;
+    for line in po.lines().map(|line| line.trim()) {
+        let (keyword, string) = match line.split_once(' ') {
+            Some((keyword, string)) if !line.starts_with('"') && !line.starts_with('#') => {
+                (Some(keyword), string)
+            }
+            _ => (None, line),
+        };
+        let ends_entry = line.is_empty()
+            || (entry.value.is_some()
+                && (line.starts_with('#') || matches!(keyword, Some("msgctxt" | "msgid"))));
+        if ends_entry && !entry.is_empty() {
+            std::mem::take(&mut entry).read(&mut lang, &mut entries);
+            number += 1;
+            current = None;
+        }
+        if line.starts_with("#,") {
+            entry.fuzzy = entry.fuzzy || line.contains("fuzzy");
+            continue;
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let string = po_unescape(string)
+            .ok_or_else(|| format!("Invalid PO string '{}' in entry {}.", line, number))?;
+        match keyword {
+            Some(keyword) if keyword == "msgid_plural" || keyword.starts_with("msgstr[") => {
+                return Err(format!(
+                    "Plural forms are not supported, '{}' in entry {}.",
+                    keyword, number
+                )
+                .into());
+            }
+            Some(keyword) if entry.text_mut(keyword).is_none() => {
+                return Err(format!("Unknown PO keyword '{}'.", keyword).into());
+            }
+            Some(keyword) => current = Some(keyword),
+            None => (),
+        }
+        if let Some(text) = current.and_then(|keyword| entry.text_mut(keyword)) {
+            text.get_or_insert_with(String::new).push_str(&string);
+        }
+    }
+    entry.read(&mut lang, &mut entries);
+    let lang = lang
+        .filter(|lang| !lang.is_empty())
+        .ok_or("PO file has no Language header.")?;
+    Ok(import(table, &lang, entries))
+}
+
+// Completeness of every lang used by a local of the table, in order of appearance
+pub fn completeness(table: &Table) -> Vec<LangCompleteness> {
+    let texts = localized_texts(table);
+    let identical = identical_locals(table);
+    let mut langs: Vec<&str> = vec![];
+    for local in texts.iter().flat_map(|text| text.locals.iter()) {
+        if !langs.contains(&local.lang.as_str()) {
+            langs.push(&local.lang);
+        }
+    }
+    langs
+        .into_iter()
+        .map(|lang| {
+            let missing: Vec<String> = texts
+                .iter()
+                .filter(|text| text.local(lang).is_none())
+                .map(|text| text.xpath.to_owned())
+                .collect();
+            let translated = texts.len() - missing.len();
+            LangCompleteness {
+                lang: lang.to_owned(),
+                total: texts.len(),
+                translated,
+                percentage: match texts.len() {
+                    0 => 100.0,
+                    total => translated as f64 * 100.0 / total as f64,
+                },
+                missing,
+                identical: identical
+                    .iter()
+                    .filter(|local| local.lang == lang)
+                    .map(|local| local.xpath.to_owned())
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+pub fn identical_locals(table: &Table) -> Vec<IdenticalLocal> {
+    let mut identical = vec![];
+    for text in localized_texts(table).iter() {
+        let (element_xpath, element) = text.xpath.rsplit_once('/').unwrap_or_default();
+        for local in text.locals.iter().filter(|local| local.value == text.text) {
+            identical.push(IdenticalLocal {
+                xpath: local_xpath(element_xpath, &format!("{}-Local", element), &local.lang),
+                element: element.to_owned(),
+                lang: local.lang.to_owned(),
+            });
+        }
+    }
+    identical
+}
+
+fn import(table: &mut Table, lang: &str, entries: Vec<Entry>) -> TranslationReport {
+    let texts = localized_texts(table)
+        .into_iter()
+        .map(|text| (text.xpath.to_owned(), text))
+        .collect::<HashMap<_, _>>();
+    let mut values = HashMap::new();
+    let mut stale = vec![];
+    for entry in entries {
+        let target = match entry.target.filter(|target| !target.is_empty()) {
+            Some(target) => target,
+            None => continue,
+        };
+        match texts.get(&entry.xpath) {
+            Some(text) if text.text == entry.source => {
+                values.insert(entry.xpath, target);
+            }
+            _ => stale.push(entry.xpath),
+        }
+    }
+    let translated = set_locals(table, lang, &values);
+    let missing = localized_texts(table)
+        .into_iter()
+        .filter(|text| text.local(lang).is_none())
+        .map(|text| text.xpath)
+        .collect();
+    TranslationReport {
+        lang: lang.to_owned(),
+        translated,
+        missing,
+        stale,
+    }
+}
+
+fn po_string(value: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn po_unescape(value: &str) -> Option<String> {
+    let value = value.strip_prefix('"')?.strip_suffix('"')?;
+    let mut unescaped = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => unescaped.push('\n'),
+            'r' => unescaped.push('\r'),
+            't' => unescaped.push('\t'),
+            other => unescaped.push(other),
+        }
+    }
+    Some(unescaped)
+}
@@ -1,6 +1,14 @@
 use std::{collections::HashSet, error::Error};
 
-use crate::table_structs::{Field, Partition, Table};
+use crate::{
+    table_locals::is_three_letter_lang,
+    table_structs::{
+        CategoryKey, ClassificationKey, CommonColumn, Field, FieldKey, Local, Partition, Screen,
+        SectionKey, Table,
+    },
+    table_translation::identical_locals,
+    table_visitor::{keyed, specific, walk, Visitor},
+};
 
 pub enum LogError {
     Partition {
@@ -13,35 +21,121 @@ pub enum LogError {
         code: String,
         message: String,
         xpath: String,
-        classification_key: String,
+        classification_key: ClassificationKey,
     },
     Category {
         code: String,
         message: String,
         xpath: String,
-        classification_key: String,
-        category_key: String,
+        classification_key: ClassificationKey,
+        category_key: CategoryKey,
     },
     Field {
         code: String,
         message: String,
         xpath: String,
-        field_key: String,
+        field_key: FieldKey,
+    },
+    Local {
+        code: String,
+        message: String,
+        xpath: String,
+        lang: String,
+    },
+    Screen {
+        code: String,
+        message: String,
+        xpath: String,
+        screen_key: String,
     },
 }
+impl LogError {
+    pub fn code(&self) -> &str {
+        match self {
+            LogError::Partition { code, .. } => code,
+            LogError::Classification { code, .. } => code,
+            LogError::Category { code, .. } => code,
+            LogError::Field { code, .. } => code,
+            LogError::Local { code, .. } => code,
+            LogError::Screen { code, .. } => code,
+        }
+    }
+    pub fn message(&self) -> &str {
+        match self {
+            LogError::Partition { message, .. } => message,
+            LogError::Classification { message, .. } => message,
+            LogError::Category { message, .. } => message,
+            LogError::Field { message, .. } => message,
+            LogError::Local { message, .. } => message,
+            LogError::Screen { message, .. } => message,
+        }
+    }
+    pub fn xpath(&self) -> &str {
+        match self {
+            LogError::Partition { xpath, .. } => xpath,
+            LogError::Classification { xpath, .. } => xpath,
+            LogError::Category { xpath, .. } => xpath,
+            LogError::Field { xpath, .. } => xpath,
+            LogError::Local { xpath, .. } => xpath,
+            LogError::Screen { xpath, .. } => xpath,
+        }
+    }
+}
 
 pub struct TableValidation {
     pub errors: Vec<LogError>,
+    pub warnings: Vec<LogError>,
 }
 
 impl TableValidation {
     pub fn new() -> Self {
-        TableValidation { errors: vec![] }
+        TableValidation {
+            errors: vec![],
+            warnings: vec![],
+        }
     }
     pub fn validate(&mut self, table: &Table) -> Result<(), Box<dyn Error>> {
         self.validate_partitions(&table.schema.partitions)?;
+        self.validate_locals(table);
+        self.validate_untranslated_locals(table);
+        self.validate_screens(table);
         Ok(())
     }
+    // Langs must be three-letter lowercase codes, and used once by the Title-Local and once
+    // by the Description-Local of an element
+    pub fn validate_locals(&mut self, table: &Table) {
+        let mut validation = LocalsValidation {
+            xpaths: HashSet::new(),
+            errors: vec![],
+        };
+        walk(&mut validation, table);
+        self.errors.append(&mut validation.errors);
+    }
+    // A local equal to the text it translates is usually a copy-paste left untranslated, it
+    // is only a warning as codes and units often read the same in both langs
+    pub fn validate_untranslated_locals(&mut self, table: &Table) {
+        for local in identical_locals(table) {
+            self.warnings.push(LogError::Local {
+                code: "UNTRANSLATED_LOCAL".to_owned(),
+                message: local.message(),
+                xpath: local.xpath,
+                lang: local.lang,
+            });
+        }
+    }
+    // Screens must use an existing level, sections and columns must reference existing keys
+    // and be numbered from 1 without duplicates or gaps, and fixed columns must come first
+    pub fn validate_screens(&mut self, table: &Table) {
+        for screen in table.schema.screens.iter() {
+            let mut validation = ScreenValidation {
+                table,
+                screen_key: &screen.key,
+                errors: vec![],
+            };
+            validation.validate(screen);
+            self.errors.append(&mut validation.errors);
+        }
+    }
     pub fn validate_partitions(
         &mut self,
         partitions: &Vec<Partition>,
@@ -147,7 +241,7 @@ impl TableValidation {
         let mut field_keys: Vec<String> = vec![];
         for field in fields.iter() {
             let key = &field.key;
-            field_keys.push(key.to_owned());
+            field_keys.push(key.to_string());
             self.validate_field(field)?;
         }
         match control_text_uniqueness(field_keys)? {
@@ -157,7 +251,7 @@ impl TableValidation {
                         code: "DUPLICATE_KEY".to_owned(),
                         message: "Field' attribute @key is not unique.".to_owned(),
                         xpath: format!("/Table/Schema/Fields/Field[@key='{}']/@key", key),
-                        field_key: key.to_owned(),
+                        field_key: key.as_str().into(),
                     })
                 }
             }
@@ -175,7 +269,7 @@ impl TableValidation {
                 field_key: key.to_owned(),
             })
         }
-        match control_min_length(field.key.to_owned(), 1)? {
+        match control_min_length(field.key.to_string(), 1)? {
             Some(value_length) => self.errors.push(LogError::Field {
                 code: "MIN_LENGTH".to_owned(),
                 message: format!(
@@ -187,7 +281,7 @@ impl TableValidation {
             }),
             None => (),
         }
-        match control_max_length(field.key.to_owned(), 255)? {
+        match control_max_length(field.key.to_string(), 255)? {
             Some(value_length) => self.errors.push(LogError::Field {
                 code: "MAX_LENGTH".to_owned(),
                 message: format!(
@@ -227,6 +321,263 @@ impl TableValidation {
     }
 }
 
+struct LocalsValidation {
+    xpaths: HashSet<String>,
+    errors: Vec<LogError>,
+}
+impl Visitor for LocalsValidation {
+    // The xpath of a local contains its lang, it is seen twice when the lang is duplicated
+    fn visit_local(&mut self, local: &Local, xpath: &str) {
+        if !is_three_letter_lang(&local.lang) {
+            self.errors.push(LogError::Local {
+                code: "INVALID_LANG".to_owned(),
+                message: format!(
+                    "Lang '{}' is not a three-letter lowercase lang code.",
+                    local.lang
+                ),
+                xpath: format!("{}/@lang", xpath),
+                lang: local.lang.to_owned(),
+            })
+        }
+        if !self.xpaths.insert(xpath.to_owned()) {
+            self.errors.push(LogError::Local {
+                code: "DUPLICATE_LANG".to_owned(),
+                message: format!("Lang '{}' is used more than once.", local.lang),
+                xpath: format!("{}/@lang", xpath),
+                lang: local.lang.to_owned(),
+            })
+        }
+    }
+}
+
+struct ScreenValidation<'a> {
+    table: &'a Table,
+    screen_key: &'a str,
+    errors: Vec<LogError>,
+}
+impl ScreenValidation<'_> {
+    fn validate(&mut self, screen: &Screen) {
+        let xpath = keyed("/Table/Schema/Screens", "Screen", &screen.key);
+        let schema = &self.table.schema;
+        if schema.level(&screen.level).is_none() {
+            self.push(
+                "UNKNOWN_LEVEL",
+                format!(
+                    "Screen' attribute @level references the unknown Level '{}'.",
+                    screen.level
+                ),
+                format!("{}/@level", xpath),
+            );
+        }
+        let grid_xpath = format!("{}/Grid", xpath);
+        self.positions(
+            "Section",
+            &format!("{}/Common", grid_xpath),
+            screen
+                .grid
+                .common
+                .iter()
+                .map(|section| (section.key.as_str(), section.position))
+                .collect(),
+        );
+        for section in screen.grid.common.iter() {
+            let xpath = keyed(&format!("{}/Common", grid_xpath), "Section", &section.key);
+            self.section(&section.key, &xpath);
+            for column in section.columns.iter() {
+                let (label, known) = match column {
+                    CommonColumn::ColumnIdentifier(column) => {
+                        ("Identifier", schema.identifier(&column.key).is_some())
+                    }
+                    CommonColumn::ColumnClassification(column) => (
+                        "Classification",
+                        schema.classification(&column.key).is_some(),
+                    ),
+                    CommonColumn::ColumnConditionalFormatting(column) => (
+                        "Conditional-Formatting",
+                        schema.conditional_formatting(&column.key).is_some(),
+                    ),
+                    CommonColumn::ColumnField(column) => {
+                        ("Field", schema.field(&column.key).is_some())
+                    }
+                };
+                if !known {
+                    self.unknown_key(column.element_name(), label, column.key(), &xpath);
+                }
+            }
+            self.columns(
+                &xpath,
+                section
+                    .columns
+                    .iter()
+                    .map(|column| {
+                        (
+                            column.element_name(),
+                            column.key(),
+                            column.position(),
+                            column.is_fixed(),
+                        )
+                    })
+                    .collect(),
+            );
+        }
+        for grid_specific in screen.grid.specifics.iter() {
+            let xpath = specific(
+                &grid_xpath,
+                &grid_specific.classification,
+                &grid_specific.category,
+            );
+            if schema.category(&grid_specific.category_ref()).is_none() {
+                self.push(
+                    "UNKNOWN_CATEGORY",
+                    format!(
+                        "Specific' attribute @category references the unknown Category '{}' of '{}'.",
+                        grid_specific.category, grid_specific.classification
+                    ),
+                    format!("{}/@category", xpath),
+                );
+            }
+            self.positions(
+                "Section",
+                &xpath,
+                grid_specific
+                    .sections
+                    .iter()
+                    .map(|section| (section.key.as_str(), section.position as usize))
+                    .collect(),
+            );
+            for section in grid_specific.sections.iter() {
+                let xpath = keyed(&xpath, "Section", &section.key);
+                self.section(&section.key, &xpath);
+                for column in section.columns.iter() {
+                    if schema.field(&column.key).is_none() {
+                        self.unknown_key("Column-Field", "Field", &column.key, &xpath);
+                    }
+                }
+                self.columns(
+                    &xpath,
+                    section
+                        .columns
+                        .iter()
+                        .map(|column| {
+                            (
+                                "Column-Field",
+                                column.key.as_str(),
+                                column.position,
+                                column.is_fixed(),
+                            )
+                        })
+                        .collect(),
+                );
+            }
+        }
+    }
+    fn section(&mut self, key: &SectionKey, xpath: &str) {
+        if self.table.schema.section(key).is_none() {
+            self.push(
+                "UNKNOWN_SECTION",
+                format!(
+                    "Section' attribute @key references the unknown Section '{}'.",
+                    key
+                ),
+                format!("{}/@key", xpath),
+            );
+        }
+    }
+    // The label names the element of the schema referenced by the column
+    fn unknown_key(&mut self, element: &str, label: &str, key: &str, xpath: &str) {
+        self.push(
+            "UNKNOWN_KEY",
+            format!(
+                "{}' attribute @key references the unknown {} '{}'.",
+                element, label, key
+            ),
+            format!("{}/@key", keyed(xpath, element, key)),
+        );
+    }
+    // Columns of a section as (element name, key, position, fixed)
+    fn columns(&mut self, xpath: &str, mut columns: Vec<(&str, &str, u32, bool)>) {
+        self.positions(
+            "Column",
+            xpath,
+            columns
+                .iter()
+                .map(|(_, key, position, _)| (*key, *position as usize))
+                .collect(),
+        );
+        columns.sort_by_key(|(_, _, position, _)| *position);
+        let mut non_fixed = None;
+        for (element, key, _, fixed) in columns {
+            match (fixed, non_fixed) {
+                (false, None) => non_fixed = Some(key),
+                (true, Some(previous)) => self.push(
+                    "FIXED_COLUMN_AFTER_NON_FIXED",
+                    format!(
+                        "{} '{}' is fixed but placed after the non-fixed column '{}'.",
+                        element, key, previous
+                    ),
+                    format!("{}/@fixed", keyed(xpath, element, key)),
+                ),
+                _ => (),
+            }
+        }
+    }
+    // Positions of the children of an element, as (key, position), must be 1 to n
+    fn positions(&mut self, label: &str, xpath: &str, elements: Vec<(&str, usize)>) {
+        let mut positions = elements
+            .iter()
+            .map(|(_, position)| *position)
+            .collect::<Vec<usize>>();
+        positions.sort();
+        positions.dedup();
+        for position in positions.iter() {
+            let keys = elements
+                .iter()
+                .filter(|(_, other)| other == position)
+                .map(|(key, _)| format!("'{}'", key))
+                .collect::<Vec<String>>();
+            if keys.len() > 1 {
+                self.push(
+                    "DUPLICATE_POSITION",
+                    format!(
+                        "{}s {} have the same position {}.",
+                        label,
+                        keys.join(", "),
+                        position
+                    ),
+                    xpath.to_owned(),
+                );
+            }
+        }
+        if positions
+            .iter()
+            .enumerate()
+            .any(|(index, position)| *position != index + 1)
+        {
+            self.push(
+                "POSITION_GAP",
+                format!(
+                    "{} positions are not numbered from 1 without gaps (actual: {}).",
+                    label,
+                    positions
+                        .iter()
+                        .map(|position| position.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+                xpath.to_owned(),
+            );
+        }
+    }
+    fn push(&mut self, code: &str, message: String, xpath: String) {
+        self.errors.push(LogError::Screen {
+            code: code.to_owned(),
+            message,
+            xpath,
+            screen_key: self.screen_key.to_owned(),
+        });
+    }
+}
+
 // Utils
 fn control_text_uniqueness(elements: Vec<String>) -> Result<Option<Vec<String>>, Box<dyn Error>> {
     let unique_elements: HashSet<String> = elements.to_vec().into_iter().collect();
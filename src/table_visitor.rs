@@ -0,0 +1,1121 @@
+use crate::table_structs::{
+    Action, Category, Classification, Column, CommonAttributeRules, CommonColumn, CommonSection,
+    Condition, ConditionGroup, ConditionalFormatting, Control, DefaultStatus, Field, Formula,
+    GridSpecific, Identifier, Level, Local, Matrix, MatrixField, MatrixSpecific, Metadata,
+    OptionRule, Partition, Rule, Rules, Schema, Screen, ScreenGrid, Section, SelectOption,
+    SpecificAttributeRules, SpecificRules, SpecificSection, Status, Suffix, Table,
+};
+
+// Every visit_* method receives the xpath of the node it visits, using the same
+// notation as the validation logs (e.g. /Table/Schema/Fields/Field[@key='COLOR']).
+// Default implementations walk into the children; overriding a method without
+// calling the matching walk_* function skips the whole subtree.
+
+pub fn walk<V: Visitor + ?Sized>(visitor: &mut V, table: &Table) {
+    visitor.visit_table(table, "/Table");
+}
+pub fn walk_mut<V: VisitorMut + ?Sized>(visitor: &mut V, table: &mut Table) {
+    visitor.visit_table_mut(table, "/Table");
+}
+
+pub trait Visitor {
+    fn visit_table(&mut self, table: &Table, xpath: &str) {
+        walk_table(self, table, xpath)
+    }
+    fn visit_schema(&mut self, schema: &Schema, xpath: &str) {
+        walk_schema(self, schema, xpath)
+    }
+    fn visit_partition(&mut self, partition: &Partition, xpath: &str) {
+        walk_partition(self, partition, xpath)
+    }
+    fn visit_level(&mut self, level: &Level, xpath: &str) {
+        walk_level(self, level, xpath)
+    }
+    fn visit_identifier(&mut self, identifier: &Identifier, xpath: &str) {
+        walk_identifier(self, identifier, xpath)
+    }
+    fn visit_classification(&mut self, classification: &Classification, xpath: &str) {
+        walk_classification(self, classification, xpath)
+    }
+    fn visit_category(&mut self, category: &Category, xpath: &str) {
+        walk_category(self, category, xpath)
+    }
+    fn visit_field(&mut self, field: &Field, xpath: &str) {
+        walk_field(self, field, xpath)
+    }
+    fn visit_select_option(&mut self, option: &SelectOption, xpath: &str) {
+        walk_select_option(self, option, xpath)
+    }
+    fn visit_suffix(&mut self, suffix: &Suffix, xpath: &str) {
+        walk_suffix(self, suffix, xpath)
+    }
+    fn visit_formula(&mut self, formula: &Formula, xpath: &str) {
+        walk_formula(self, formula, xpath)
+    }
+    fn visit_rule(&mut self, rule: &Rule, xpath: &str) {
+        walk_rule(self, rule, xpath)
+    }
+    fn visit_action(&mut self, _action: &Action, _xpath: &str) {}
+    fn visit_condition_group(&mut self, condition_group: &ConditionGroup, xpath: &str) {
+        walk_condition_group(self, condition_group, xpath)
+    }
+    fn visit_condition(&mut self, _condition: &Condition, _xpath: &str) {}
+    fn visit_matrix(&mut self, matrix: &Matrix, xpath: &str) {
+        walk_matrix(self, matrix, xpath)
+    }
+    fn visit_matrix_field(&mut self, _matrix_field: &MatrixField, _xpath: &str) {}
+    fn visit_matrix_specific(&mut self, matrix_specific: &MatrixSpecific, xpath: &str) {
+        walk_matrix_specific(self, matrix_specific, xpath)
+    }
+    fn visit_conditional_formatting(
+        &mut self,
+        conditional_formatting: &ConditionalFormatting,
+        xpath: &str,
+    ) {
+        walk_conditional_formatting(self, conditional_formatting, xpath)
+    }
+    fn visit_default_status(&mut self, default_status: &DefaultStatus, xpath: &str) {
+        walk_default_status(self, default_status, xpath)
+    }
+    fn visit_status(&mut self, status: &Status, xpath: &str) {
+        walk_status(self, status, xpath)
+    }
+    fn visit_rules(&mut self, rules: &Rules, xpath: &str) {
+        walk_rules(self, rules, xpath)
+    }
+    fn visit_common_attribute_rules(&mut self, rules: &CommonAttributeRules, xpath: &str) {
+        walk_common_attribute_rules(self, rules, xpath)
+    }
+    fn visit_specific_rules(&mut self, rules: &SpecificRules, xpath: &str) {
+        walk_specific_rules(self, rules, xpath)
+    }
+    fn visit_specific_attribute_rules(&mut self, rules: &SpecificAttributeRules, xpath: &str) {
+        walk_specific_attribute_rules(self, rules, xpath)
+    }
+    fn visit_control(&mut self, control: &Control, xpath: &str) {
+        walk_control(self, control, xpath)
+    }
+    fn visit_option_rule(&mut self, _option_rule: &OptionRule, _xpath: &str) {}
+    fn visit_section(&mut self, section: &Section, xpath: &str) {
+        walk_section(self, section, xpath)
+    }
+    fn visit_screen(&mut self, screen: &Screen, xpath: &str) {
+        walk_screen(self, screen, xpath)
+    }
+    fn visit_screen_grid(&mut self, grid: &ScreenGrid, xpath: &str) {
+        walk_screen_grid(self, grid, xpath)
+    }
+    fn visit_common_section(&mut self, section: &CommonSection, xpath: &str) {
+        walk_common_section(self, section, xpath)
+    }
+    fn visit_common_column(&mut self, column: &CommonColumn, xpath: &str) {
+        walk_common_column(self, column, xpath)
+    }
+    fn visit_grid_specific(&mut self, specific: &GridSpecific, xpath: &str) {
+        walk_grid_specific(self, specific, xpath)
+    }
+    fn visit_specific_section(&mut self, section: &SpecificSection, xpath: &str) {
+        walk_specific_section(self, section, xpath)
+    }
+    // Columns of common sections are keyed by identifiers, classifications, conditional
+    // formattings or fields, columns of specific sections by fields
+    fn visit_column<K: AsRef<str>>(&mut self, _column: &Column<K>, _xpath: &str) {}
+    fn visit_local(&mut self, _local: &Local, _xpath: &str) {}
+    fn visit_metadata(&mut self, _metadata: &Metadata, _xpath: &str) {}
+}
+
+pub fn walk_table<V: Visitor + ?Sized>(visitor: &mut V, table: &Table, xpath: &str) {
+    walk_locals(visitor, &table.title_locals, &table.description_locals, xpath);
+    visitor.visit_schema(&table.schema, &format!("{}/Schema", xpath));
+}
+pub fn walk_schema<V: Visitor + ?Sized>(visitor: &mut V, schema: &Schema, xpath: &str) {
+    for partition in schema.partitions.iter() {
+        visitor.visit_partition(
+            partition,
+            &keyed(&format!("{}/Partitions", xpath), "Partition", &partition.key),
+        );
+    }
+    for level in schema.levels.iter() {
+        visitor.visit_level(level, &keyed(&format!("{}/Levels", xpath), "Level", &level.key));
+    }
+    for identifier in schema.identifiers.iter() {
+        visitor.visit_identifier(
+            identifier,
+            &keyed(&format!("{}/Identifiers", xpath), "Identifier", &identifier.key),
+        );
+    }
+    for classification in schema.classifications.iter() {
+        visitor.visit_classification(
+            classification,
+            &keyed(
+                &format!("{}/Classifications", xpath),
+                "Classification",
+                &classification.key,
+            ),
+        );
+    }
+    for field in schema.fields.iter() {
+        visitor.visit_field(field, &keyed(&format!("{}/Fields", xpath), "Field", &field.key));
+    }
+    for formula in schema.formulas.iter() {
+        visitor.visit_formula(
+            formula,
+            &keyed(
+                &format!("{}/Formulas", xpath),
+                formula.attribute_type.element_name(),
+                &formula.key,
+            ),
+        );
+    }
+    visitor.visit_matrix(&schema.matrix, &format!("{}/Matrix", xpath));
+    for conditional_formatting in schema.conditional_formattings.iter() {
+        visitor.visit_conditional_formatting(
+            conditional_formatting,
+            &keyed(
+                &format!("{}/Conditional-Formattings", xpath),
+                "Conditional-Formatting",
+                &conditional_formatting.key,
+            ),
+        );
+    }
+    for section in schema.sections.iter() {
+        visitor.visit_section(
+            section,
+            &keyed(&format!("{}/Sections", xpath), "Section", &section.key),
+        );
+    }
+    for screen in schema.screens.iter() {
+        visitor.visit_screen(screen, &keyed(&format!("{}/Screens", xpath), "Screen", &screen.key));
+    }
+}
+pub fn walk_partition<V: Visitor + ?Sized>(visitor: &mut V, partition: &Partition, xpath: &str) {
+    walk_locals(visitor, &partition.title_locals, &partition.description_locals, xpath);
+    walk_metadata(visitor, &partition.metadata, xpath);
+}
+pub fn walk_level<V: Visitor + ?Sized>(visitor: &mut V, level: &Level, xpath: &str) {
+    walk_locals(visitor, &level.title_locals, &level.description_locals, xpath);
+    walk_metadata(visitor, &level.metadata, xpath);
+}
+pub fn walk_identifier<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    identifier: &Identifier,
+    xpath: &str,
+) {
+    walk_locals(visitor, &identifier.title_locals, &identifier.description_locals, xpath);
+    walk_metadata(visitor, &identifier.metadata, xpath);
+}
+pub fn walk_classification<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    classification: &Classification,
+    xpath: &str,
+) {
+    walk_locals(
+        visitor,
+        &classification.title_locals,
+        &classification.description_locals,
+        xpath,
+    );
+    walk_metadata(visitor, &classification.metadata, xpath);
+    for category in classification.categories.iter() {
+        visitor.visit_category(
+            category,
+            &keyed(&format!("{}/Categories", xpath), "Category", &category.key),
+        );
+    }
+}
+pub fn walk_category<V: Visitor + ?Sized>(visitor: &mut V, category: &Category, xpath: &str) {
+    walk_locals(visitor, &category.title_locals, &category.description_locals, xpath);
+    walk_metadata(visitor, &category.metadata, xpath);
+}
+pub fn walk_field<V: Visitor + ?Sized>(visitor: &mut V, field: &Field, xpath: &str) {
+    walk_locals(visitor, &field.title_locals, &field.description_locals, xpath);
+    walk_metadata(visitor, &field.metadata, xpath);
+    if let Some(options) = &field.options {
+        for option in options.iter() {
+            visitor.visit_select_option(
+                option,
+                &keyed(&format!("{}/Options", xpath), "Option", &option.key),
+            );
+        }
+    }
+    if let Some(suffixes) = &field.suffixes {
+        for suffix in suffixes.iter() {
+            visitor.visit_suffix(
+                suffix,
+                &keyed(&format!("{}/Suffixes", xpath), "Suffix", &suffix.key),
+            );
+        }
+    }
+}
+pub fn walk_select_option<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    option: &SelectOption,
+    xpath: &str,
+) {
+    walk_locals(visitor, &option.title_locals, &option.description_locals, xpath);
+    walk_metadata(visitor, &option.metadata, xpath);
+}
+pub fn walk_suffix<V: Visitor + ?Sized>(visitor: &mut V, suffix: &Suffix, xpath: &str) {
+    walk_locals(visitor, &suffix.title_locals, &suffix.description_locals, xpath);
+    walk_metadata(visitor, &suffix.metadata, xpath);
+}
+pub fn walk_formula<V: Visitor + ?Sized>(visitor: &mut V, formula: &Formula, xpath: &str) {
+    for rule in formula.rules.iter() {
+        visitor.visit_rule(rule, &format!("{}/Rule[@priority='{}']", xpath, rule.priority));
+    }
+}
+pub fn walk_rule<V: Visitor + ?Sized>(visitor: &mut V, rule: &Rule, xpath: &str) {
+    walk_condition_groups(visitor, &rule.conditions, xpath);
+    visitor.visit_action(&rule.action, &format!("{}/Action", xpath));
+}
+pub fn walk_condition_group<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    condition_group: &ConditionGroup,
+    xpath: &str,
+) {
+    for (index, condition) in condition_group.conditions.iter().enumerate() {
+        visitor.visit_condition(condition, &format!("{}/Condition[{}]", xpath, index + 1));
+    }
+}
+pub fn walk_matrix<V: Visitor + ?Sized>(visitor: &mut V, matrix: &Matrix, xpath: &str) {
+    for matrix_field in matrix.common.iter() {
+        visitor.visit_matrix_field(
+            matrix_field,
+            &keyed(&format!("{}/Common", xpath), "Field", &matrix_field.key),
+        );
+    }
+    for matrix_specific in matrix.specifics.iter() {
+        visitor.visit_matrix_specific(
+            matrix_specific,
+            &specific(xpath, &matrix_specific.classification, &matrix_specific.category),
+        );
+    }
+}
+pub fn walk_matrix_specific<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    matrix_specific: &MatrixSpecific,
+    xpath: &str,
+) {
+    for matrix_field in matrix_specific.fields.iter() {
+        visitor.visit_matrix_field(matrix_field, &keyed(xpath, "Field", &matrix_field.key));
+    }
+}
+pub fn walk_conditional_formatting<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    conditional_formatting: &ConditionalFormatting,
+    xpath: &str,
+) {
+    walk_locals(
+        visitor,
+        &conditional_formatting.title_locals,
+        &conditional_formatting.description_locals,
+        xpath,
+    );
+    walk_metadata(visitor, &conditional_formatting.metadata, xpath);
+    visitor.visit_default_status(
+        &conditional_formatting.default_status,
+        &format!("{}/Default-Status", xpath),
+    );
+    for status in conditional_formatting.statuses.iter() {
+        visitor.visit_status(
+            status,
+            &keyed(&format!("{}/Statuses", xpath), "Status", &status.key),
+        );
+    }
+}
+pub fn walk_default_status<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    default_status: &DefaultStatus,
+    xpath: &str,
+) {
+    walk_locals(
+        visitor,
+        &default_status.title_locals,
+        &default_status.description_locals,
+        xpath,
+    );
+    walk_metadata(visitor, &default_status.metadata, xpath);
+}
+pub fn walk_status<V: Visitor + ?Sized>(visitor: &mut V, status: &Status, xpath: &str) {
+    walk_locals(visitor, &status.title_locals, &status.description_locals, xpath);
+    walk_metadata(visitor, &status.metadata, xpath);
+    visitor.visit_rules(&status.rules, &format!("{}/Rules", xpath));
+}
+pub fn walk_rules<V: Visitor + ?Sized>(visitor: &mut V, rules: &Rules, xpath: &str) {
+    for common in rules.common.iter() {
+        visitor.visit_common_attribute_rules(
+            common,
+            &keyed(
+                &format!("{}/Common", xpath),
+                common.attribute_type.element_name(),
+                &common.key,
+            ),
+        );
+    }
+    for specific_rules in rules.specifics.iter() {
+        visitor.visit_specific_rules(
+            specific_rules,
+            &specific(xpath, &specific_rules.classification, &specific_rules.category),
+        );
+    }
+}
+pub fn walk_common_attribute_rules<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    rules: &CommonAttributeRules,
+    xpath: &str,
+) {
+    for control in rules.controls.iter() {
+        visitor.visit_control(control, &control_xpath(xpath, control));
+    }
+}
+pub fn walk_specific_rules<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    rules: &SpecificRules,
+    xpath: &str,
+) {
+    for attribute in rules.attributes.iter() {
+        visitor.visit_specific_attribute_rules(attribute, &keyed(xpath, "Field", &attribute.key));
+    }
+}
+pub fn walk_specific_attribute_rules<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    rules: &SpecificAttributeRules,
+    xpath: &str,
+) {
+    for control in rules.controls.iter() {
+        visitor.visit_control(control, &control_xpath(xpath, control));
+    }
+}
+pub fn walk_control<V: Visitor + ?Sized>(visitor: &mut V, control: &Control, xpath: &str) {
+    match control {
+        Control::RuleRequiredIfAnotherFieldHasOptions { field: _, options } => {
+            for option in options.iter() {
+                visitor.visit_option_rule(option, &keyed(xpath, "Option", &option.key));
+            }
+        }
+        Control::RuleCondition {
+            key: _,
+            condition_groups,
+            title: _,
+            title_locals,
+        } => {
+            walk_condition_groups(visitor, condition_groups, xpath);
+            walk_locals(visitor, title_locals, &None, xpath);
+        }
+        _ => (),
+    }
+}
+pub fn walk_section<V: Visitor + ?Sized>(visitor: &mut V, section: &Section, xpath: &str) {
+    walk_locals(visitor, &section.title_locals, &section.description_locals, xpath);
+    walk_metadata(visitor, &section.metadata, xpath);
+}
+pub fn walk_screen<V: Visitor + ?Sized>(visitor: &mut V, screen: &Screen, xpath: &str) {
+    walk_locals(visitor, &screen.title_locals, &screen.description_locals, xpath);
+    walk_metadata(visitor, &screen.metadata, xpath);
+    visitor.visit_screen_grid(&screen.grid, &format!("{}/Grid", xpath));
+}
+pub fn walk_screen_grid<V: Visitor + ?Sized>(visitor: &mut V, grid: &ScreenGrid, xpath: &str) {
+    for section in grid.common.iter() {
+        visitor.visit_common_section(
+            section,
+            &keyed(&format!("{}/Common", xpath), "Section", &section.key),
+        );
+    }
+    for grid_specific in grid.specifics.iter() {
+        visitor.visit_grid_specific(
+            grid_specific,
+            &specific(xpath, &grid_specific.classification, &grid_specific.category),
+        );
+    }
+}
+pub fn walk_common_section<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    section: &CommonSection,
+    xpath: &str,
+) {
+    for column in section.columns.iter() {
+        visitor.visit_common_column(column, &keyed(xpath, column.element_name(), column.key()));
+    }
+}
+pub fn walk_common_column<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    column: &CommonColumn,
+    xpath: &str,
+) {
+    match column {
+        CommonColumn::ColumnIdentifier(column) => visitor.visit_column(column, xpath),
+        CommonColumn::ColumnClassification(column) => visitor.visit_column(column, xpath),
+        CommonColumn::ColumnConditionalFormatting(column) => visitor.visit_column(column, xpath),
+        CommonColumn::ColumnField(column) => visitor.visit_column(column, xpath),
+    }
+}
+pub fn walk_grid_specific<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    grid_specific: &GridSpecific,
+    xpath: &str,
+) {
+    for section in grid_specific.sections.iter() {
+        visitor.visit_specific_section(section, &keyed(xpath, "Section", &section.key));
+    }
+}
+pub fn walk_specific_section<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    section: &SpecificSection,
+    xpath: &str,
+) {
+    for column in section.columns.iter() {
+        visitor.visit_column(column, &keyed(xpath, "Column-Field", &column.key));
+    }
+}
+fn walk_condition_groups<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    condition_groups: &[ConditionGroup],
+    xpath: &str,
+) {
+    for (index, condition_group) in condition_groups.iter().enumerate() {
+        visitor.visit_condition_group(
+            condition_group,
+            &format!("{}/Conditions/Condition-Group[{}]", xpath, index + 1),
+        );
+    }
+}
+fn walk_locals<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    title_locals: &Option<Vec<Local>>,
+    description_locals: &Option<Vec<Local>>,
+    xpath: &str,
+) {
+    if let Some(locals) = title_locals {
+        for local in locals.iter() {
+            visitor.visit_local(local, &local_xpath(xpath, "Title-Local", &local.lang));
+        }
+    }
+    if let Some(locals) = description_locals {
+        for local in locals.iter() {
+            visitor.visit_local(local, &local_xpath(xpath, "Description-Local", &local.lang));
+        }
+    }
+}
+fn walk_metadata<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    metadata: &Option<Vec<Metadata>>,
+    xpath: &str,
+) {
+    if let Some(metadata) = metadata {
+        for metadata in metadata.iter() {
+            visitor.visit_metadata(metadata, &keyed(xpath, "Metadata", &metadata.key));
+        }
+    }
+}
+
+pub trait VisitorMut {
+    fn visit_table_mut(&mut self, table: &mut Table, xpath: &str) {
+        walk_table_mut(self, table, xpath)
+    }
+    fn visit_schema_mut(&mut self, schema: &mut Schema, xpath: &str) {
+        walk_schema_mut(self, schema, xpath)
+    }
+    fn visit_partition_mut(&mut self, partition: &mut Partition, xpath: &str) {
+        walk_partition_mut(self, partition, xpath)
+    }
+    fn visit_level_mut(&mut self, level: &mut Level, xpath: &str) {
+        walk_level_mut(self, level, xpath)
+    }
+    fn visit_identifier_mut(&mut self, identifier: &mut Identifier, xpath: &str) {
+        walk_identifier_mut(self, identifier, xpath)
+    }
+    fn visit_classification_mut(&mut self, classification: &mut Classification, xpath: &str) {
+        walk_classification_mut(self, classification, xpath)
+    }
+    fn visit_category_mut(&mut self, category: &mut Category, xpath: &str) {
+        walk_category_mut(self, category, xpath)
+    }
+    fn visit_field_mut(&mut self, field: &mut Field, xpath: &str) {
+        walk_field_mut(self, field, xpath)
+    }
+    fn visit_select_option_mut(&mut self, option: &mut SelectOption, xpath: &str) {
+        walk_select_option_mut(self, option, xpath)
+    }
+    fn visit_suffix_mut(&mut self, suffix: &mut Suffix, xpath: &str) {
+        walk_suffix_mut(self, suffix, xpath)
+    }
+    fn visit_formula_mut(&mut self, formula: &mut Formula, xpath: &str) {
+        walk_formula_mut(self, formula, xpath)
+    }
+    fn visit_rule_mut(&mut self, rule: &mut Rule, xpath: &str) {
+        walk_rule_mut(self, rule, xpath)
+    }
+    fn visit_action_mut(&mut self, _action: &mut Action, _xpath: &str) {}
+    fn visit_condition_group_mut(&mut self, condition_group: &mut ConditionGroup, xpath: &str) {
+        walk_condition_group_mut(self, condition_group, xpath)
+    }
+    fn visit_condition_mut(&mut self, _condition: &mut Condition, _xpath: &str) {}
+    fn visit_matrix_mut(&mut self, matrix: &mut Matrix, xpath: &str) {
+        walk_matrix_mut(self, matrix, xpath)
+    }
+    fn visit_matrix_field_mut(&mut self, _matrix_field: &mut MatrixField, _xpath: &str) {}
+    fn visit_matrix_specific_mut(&mut self, matrix_specific: &mut MatrixSpecific, xpath: &str) {
+        walk_matrix_specific_mut(self, matrix_specific, xpath)
+    }
+    fn visit_conditional_formatting_mut(
+        &mut self,
+        conditional_formatting: &mut ConditionalFormatting,
+        xpath: &str,
+    ) {
+        walk_conditional_formatting_mut(self, conditional_formatting, xpath)
+    }
+    fn visit_default_status_mut(&mut self, default_status: &mut DefaultStatus, xpath: &str) {
+        walk_default_status_mut(self, default_status, xpath)
+    }
+    fn visit_status_mut(&mut self, status: &mut Status, xpath: &str) {
+        walk_status_mut(self, status, xpath)
+    }
+    fn visit_rules_mut(&mut self, rules: &mut Rules, xpath: &str) {
+        walk_rules_mut(self, rules, xpath)
+    }
+    fn visit_common_attribute_rules_mut(&mut self, rules: &mut CommonAttributeRules, xpath: &str) {
+        walk_common_attribute_rules_mut(self, rules, xpath)
+    }
+    fn visit_specific_rules_mut(&mut self, rules: &mut SpecificRules, xpath: &str) {
+        walk_specific_rules_mut(self, rules, xpath)
+    }
+    fn visit_specific_attribute_rules_mut(
+        &mut self,
+        rules: &mut SpecificAttributeRules,
+        xpath: &str,
+    ) {
+        walk_specific_attribute_rules_mut(self, rules, xpath)
+    }
+    fn visit_control_mut(&mut self, control: &mut Control, xpath: &str) {
+        walk_control_mut(self, control, xpath)
+    }
+    fn visit_option_rule_mut(&mut self, _option_rule: &mut OptionRule, _xpath: &str) {}
+    fn visit_section_mut(&mut self, section: &mut Section, xpath: &str) {
+        walk_section_mut(self, section, xpath)
+    }
+    fn visit_screen_mut(&mut self, screen: &mut Screen, xpath: &str) {
+        walk_screen_mut(self, screen, xpath)
+    }
+    fn visit_screen_grid_mut(&mut self, grid: &mut ScreenGrid, xpath: &str) {
+        walk_screen_grid_mut(self, grid, xpath)
+    }
+    fn visit_common_section_mut(&mut self, section: &mut CommonSection, xpath: &str) {
+        walk_common_section_mut(self, section, xpath)
+    }
+    fn visit_common_column_mut(&mut self, column: &mut CommonColumn, xpath: &str) {
+        walk_common_column_mut(self, column, xpath)
+    }
+    fn visit_grid_specific_mut(&mut self, specific: &mut GridSpecific, xpath: &str) {
+        walk_grid_specific_mut(self, specific, xpath)
+    }
+    fn visit_specific_section_mut(&mut self, section: &mut SpecificSection, xpath: &str) {
+        walk_specific_section_mut(self, section, xpath)
+    }
+    fn visit_column_mut<K: AsRef<str>>(&mut self, _column: &mut Column<K>, _xpath: &str) {}
+    fn visit_local_mut(&mut self, _local: &mut Local, _xpath: &str) {}
+    fn visit_metadata_mut(&mut self, _metadata: &mut Metadata, _xpath: &str) {}
+}
+
+pub fn walk_table_mut<V: VisitorMut + ?Sized>(visitor: &mut V, table: &mut Table, xpath: &str) {
+    walk_locals_mut(
+        visitor,
+        &mut table.title_locals,
+        &mut table.description_locals,
+        xpath,
+    );
+    visitor.visit_schema_mut(&mut table.schema, &format!("{}/Schema", xpath));
+}
+pub fn walk_schema_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    schema: &mut Schema,
+    xpath: &str,
+) {
+    for partition in schema.partitions.iter_mut() {
+        let partition_xpath = keyed(&format!("{}/Partitions", xpath), "Partition", &partition.key);
+        visitor.visit_partition_mut(partition, &partition_xpath);
+    }
+    for level in schema.levels.iter_mut() {
+        let level_xpath = keyed(&format!("{}/Levels", xpath), "Level", &level.key);
+        visitor.visit_level_mut(level, &level_xpath);
+    }
+    for identifier in schema.identifiers.iter_mut() {
+        let identifier_xpath =
+            keyed(&format!("{}/Identifiers", xpath), "Identifier", &identifier.key);
+        visitor.visit_identifier_mut(identifier, &identifier_xpath);
+    }
+    for classification in schema.classifications.iter_mut() {
+        let classification_xpath = keyed(
+            &format!("{}/Classifications", xpath),
+            "Classification",
+            &classification.key,
+        );
+        visitor.visit_classification_mut(classification, &classification_xpath);
+    }
+    for field in schema.fields.iter_mut() {
+        let field_xpath = keyed(&format!("{}/Fields", xpath), "Field", &field.key);
+        visitor.visit_field_mut(field, &field_xpath);
+    }
+    for formula in schema.formulas.iter_mut() {
+        let formula_xpath = keyed(
+            &format!("{}/Formulas", xpath),
+            formula.attribute_type.element_name(),
+            &formula.key,
+        );
+        visitor.visit_formula_mut(formula, &formula_xpath);
+    }
+    visitor.visit_matrix_mut(&mut schema.matrix, &format!("{}/Matrix", xpath));
+    for conditional_formatting in schema.conditional_formattings.iter_mut() {
+        let conditional_formatting_xpath = keyed(
+            &format!("{}/Conditional-Formattings", xpath),
+            "Conditional-Formatting",
+            &conditional_formatting.key,
+        );
+        visitor.visit_conditional_formatting_mut(
+            conditional_formatting,
+            &conditional_formatting_xpath,
+        );
+    }
+    for section in schema.sections.iter_mut() {
+        let section_xpath = keyed(&format!("{}/Sections", xpath), "Section", &section.key);
+        visitor.visit_section_mut(section, &section_xpath);
+    }
+    for screen in schema.screens.iter_mut() {
+        let screen_xpath = keyed(&format!("{}/Screens", xpath), "Screen", &screen.key);
+        visitor.visit_screen_mut(screen, &screen_xpath);
+    }
+}
+pub fn walk_partition_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    partition: &mut Partition,
+    xpath: &str,
+) {
+    walk_locals_mut(
+        visitor,
+        &mut partition.title_locals,
+        &mut partition.description_locals,
+        xpath,
+    );
+    walk_metadata_mut(visitor, &mut partition.metadata, xpath);
+}
+pub fn walk_level_mut<V: VisitorMut + ?Sized>(visitor: &mut V, level: &mut Level, xpath: &str) {
+    walk_locals_mut(
+        visitor,
+        &mut level.title_locals,
+        &mut level.description_locals,
+        xpath,
+    );
+    walk_metadata_mut(visitor, &mut level.metadata, xpath);
+}
+pub fn walk_identifier_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    identifier: &mut Identifier,
+    xpath: &str,
+) {
+    walk_locals_mut(
+        visitor,
+        &mut identifier.title_locals,
+        &mut identifier.description_locals,
+        xpath,
+    );
+    walk_metadata_mut(visitor, &mut identifier.metadata, xpath);
+}
+pub fn walk_classification_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    classification: &mut Classification,
+    xpath: &str,
+) {
+    walk_locals_mut(
+        visitor,
+        &mut classification.title_locals,
+        &mut classification.description_locals,
+        xpath,
+    );
+    walk_metadata_mut(visitor, &mut classification.metadata, xpath);
+    for category in classification.categories.iter_mut() {
+        let category_xpath = keyed(&format!("{}/Categories", xpath), "Category", &category.key);
+        visitor.visit_category_mut(category, &category_xpath);
+    }
+}
+pub fn walk_category_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    category: &mut Category,
+    xpath: &str,
+) {
+    walk_locals_mut(
+        visitor,
+        &mut category.title_locals,
+        &mut category.description_locals,
+        xpath,
+    );
+    walk_metadata_mut(visitor, &mut category.metadata, xpath);
+}
+pub fn walk_field_mut<V: VisitorMut + ?Sized>(visitor: &mut V, field: &mut Field, xpath: &str) {
+    walk_locals_mut(
+        visitor,
+        &mut field.title_locals,
+        &mut field.description_locals,
+        xpath,
+    );
+    walk_metadata_mut(visitor, &mut field.metadata, xpath);
+    if let Some(options) = &mut field.options {
+        for option in options.iter_mut() {
+            let option_xpath = keyed(&format!("{}/Options", xpath), "Option", &option.key);
+            visitor.visit_select_option_mut(option, &option_xpath);
+        }
+    }
+    if let Some(suffixes) = &mut field.suffixes {
+        for suffix in suffixes.iter_mut() {
+            let suffix_xpath = keyed(&format!("{}/Suffixes", xpath), "Suffix", &suffix.key);
+            visitor.visit_suffix_mut(suffix, &suffix_xpath);
+        }
+    }
+}
+pub fn walk_select_option_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    option: &mut SelectOption,
+    xpath: &str,
+) {
+    walk_locals_mut(
+        visitor,
+        &mut option.title_locals,
+        &mut option.description_locals,
+        xpath,
+    );
+    walk_metadata_mut(visitor, &mut option.metadata, xpath);
+}
+pub fn walk_suffix_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    suffix: &mut Suffix,
+    xpath: &str,
+) {
+    walk_locals_mut(
+        visitor,
+        &mut suffix.title_locals,
+        &mut suffix.description_locals,
+        xpath,
+    );
+    walk_metadata_mut(visitor, &mut suffix.metadata, xpath);
+}
+pub fn walk_formula_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    formula: &mut Formula,
+    xpath: &str,
+) {
+    for rule in formula.rules.iter_mut() {
+        let rule_xpath = format!("{}/Rule[@priority='{}']", xpath, rule.priority);
+        visitor.visit_rule_mut(rule, &rule_xpath);
+    }
+}
+pub fn walk_rule_mut<V: VisitorMut + ?Sized>(visitor: &mut V, rule: &mut Rule, xpath: &str) {
+    walk_condition_groups_mut(visitor, &mut rule.conditions, xpath);
+    visitor.visit_action_mut(&mut rule.action, &format!("{}/Action", xpath));
+}
+pub fn walk_condition_group_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    condition_group: &mut ConditionGroup,
+    xpath: &str,
+) {
+    for (index, condition) in condition_group.conditions.iter_mut().enumerate() {
+        visitor.visit_condition_mut(condition, &format!("{}/Condition[{}]", xpath, index + 1));
+    }
+}
+pub fn walk_matrix_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    matrix: &mut Matrix,
+    xpath: &str,
+) {
+    for matrix_field in matrix.common.iter_mut() {
+        let matrix_field_xpath = keyed(&format!("{}/Common", xpath), "Field", &matrix_field.key);
+        visitor.visit_matrix_field_mut(matrix_field, &matrix_field_xpath);
+    }
+    for matrix_specific in matrix.specifics.iter_mut() {
+        let matrix_specific_xpath = specific(
+            xpath,
+            &matrix_specific.classification,
+            &matrix_specific.category,
+        );
+        visitor.visit_matrix_specific_mut(matrix_specific, &matrix_specific_xpath);
+    }
+}
+pub fn walk_matrix_specific_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    matrix_specific: &mut MatrixSpecific,
+    xpath: &str,
+) {
+    for matrix_field in matrix_specific.fields.iter_mut() {
+        let matrix_field_xpath = keyed(xpath, "Field", &matrix_field.key);
+        visitor.visit_matrix_field_mut(matrix_field, &matrix_field_xpath);
+    }
+}
+pub fn walk_conditional_formatting_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    conditional_formatting: &mut ConditionalFormatting,
+    xpath: &str,
+) {
+    walk_locals_mut(
+        visitor,
+        &mut conditional_formatting.title_locals,
+        &mut conditional_formatting.description_locals,
+        xpath,
+    );
+    walk_metadata_mut(visitor, &mut conditional_formatting.metadata, xpath);
+    visitor.visit_default_status_mut(
+        &mut conditional_formatting.default_status,
+        &format!("{}/Default-Status", xpath),
+    );
+    for status in conditional_formatting.statuses.iter_mut() {
+        let status_xpath = keyed(&format!("{}/Statuses", xpath), "Status", &status.key);
+        visitor.visit_status_mut(status, &status_xpath);
+    }
+}
+pub fn walk_default_status_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    default_status: &mut DefaultStatus,
+    xpath: &str,
+) {
+    walk_locals_mut(
+        visitor,
+        &mut default_status.title_locals,
+        &mut default_status.description_locals,
+        xpath,
+    );
+    walk_metadata_mut(visitor, &mut default_status.metadata, xpath);
+}
+pub fn walk_status_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    status: &mut Status,
+    xpath: &str,
+) {
+    walk_locals_mut(
+        visitor,
+        &mut status.title_locals,
+        &mut status.description_locals,
+        xpath,
+    );
+    walk_metadata_mut(visitor, &mut status.metadata, xpath);
+    visitor.visit_rules_mut(&mut status.rules, &format!("{}/Rules", xpath));
+}
+pub fn walk_rules_mut<V: VisitorMut + ?Sized>(visitor: &mut V, rules: &mut Rules, xpath: &str) {
+    for common in rules.common.iter_mut() {
+        let common_xpath = keyed(
+            &format!("{}/Common", xpath),
+            common.attribute_type.element_name(),
+            &common.key,
+        );
+        visitor.visit_common_attribute_rules_mut(common, &common_xpath);
+    }
+    for specific_rules in rules.specifics.iter_mut() {
+        let specific_xpath = specific(
+            xpath,
+            &specific_rules.classification,
+            &specific_rules.category,
+        );
+        visitor.visit_specific_rules_mut(specific_rules, &specific_xpath);
+    }
+}
+pub fn walk_common_attribute_rules_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    rules: &mut CommonAttributeRules,
+    xpath: &str,
+) {
+    for control in rules.controls.iter_mut() {
+        let control_xpath = control_xpath(xpath, control);
+        visitor.visit_control_mut(control, &control_xpath);
+    }
+}
+pub fn walk_specific_rules_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    rules: &mut SpecificRules,
+    xpath: &str,
+) {
+    for attribute in rules.attributes.iter_mut() {
+        let attribute_xpath = keyed(xpath, "Field", &attribute.key);
+        visitor.visit_specific_attribute_rules_mut(attribute, &attribute_xpath);
+    }
+}
+pub fn walk_specific_attribute_rules_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    rules: &mut SpecificAttributeRules,
+    xpath: &str,
+) {
+    for control in rules.controls.iter_mut() {
+        let control_xpath = control_xpath(xpath, control);
+        visitor.visit_control_mut(control, &control_xpath);
+    }
+}
+pub fn walk_control_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    control: &mut Control,
+    xpath: &str,
+) {
+    match control {
+        Control::RuleRequiredIfAnotherFieldHasOptions { field: _, options } => {
+            for option in options.iter_mut() {
+                let option_xpath = keyed(xpath, "Option", &option.key);
+                visitor.visit_option_rule_mut(option, &option_xpath);
+            }
+        }
+        Control::RuleCondition {
+            key: _,
+            condition_groups,
+            title: _,
+            title_locals,
+        } => {
+            walk_condition_groups_mut(visitor, condition_groups, xpath);
+            walk_locals_mut(visitor, title_locals, &mut None, xpath);
+        }
+        _ => (),
+    }
+}
+pub fn walk_section_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    section: &mut Section,
+    xpath: &str,
+) {
+    walk_locals_mut(
+        visitor,
+        &mut section.title_locals,
+        &mut section.description_locals,
+        xpath,
+    );
+    walk_metadata_mut(visitor, &mut section.metadata, xpath);
+}
+pub fn walk_screen_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    screen: &mut Screen,
+    xpath: &str,
+) {
+    walk_locals_mut(
+        visitor,
+        &mut screen.title_locals,
+        &mut screen.description_locals,
+        xpath,
+    );
+    walk_metadata_mut(visitor, &mut screen.metadata, xpath);
+    visitor.visit_screen_grid_mut(&mut screen.grid, &format!("{}/Grid", xpath));
+}
+pub fn walk_screen_grid_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    grid: &mut ScreenGrid,
+    xpath: &str,
+) {
+    for section in grid.common.iter_mut() {
+        let section_xpath = keyed(&format!("{}/Common", xpath), "Section", &section.key);
+        visitor.visit_common_section_mut(section, &section_xpath);
+    }
+    for grid_specific in grid.specifics.iter_mut() {
+        let grid_specific_xpath = specific(
+            xpath,
+            &grid_specific.classification,
+            &grid_specific.category,
+        );
+        visitor.visit_grid_specific_mut(grid_specific, &grid_specific_xpath);
+    }
+}
+pub fn walk_common_section_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    section: &mut CommonSection,
+    xpath: &str,
+) {
+    for column in section.columns.iter_mut() {
+        let column_xpath = keyed(xpath, column.element_name(), column.key());
+        visitor.visit_common_column_mut(column, &column_xpath);
+    }
+}
+pub fn walk_common_column_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    column: &mut CommonColumn,
+    xpath: &str,
+) {
+    match column {
+        CommonColumn::ColumnIdentifier(column) => visitor.visit_column_mut(column, xpath),
+        CommonColumn::ColumnClassification(column) => visitor.visit_column_mut(column, xpath),
+        CommonColumn::ColumnConditionalFormatting(column) => {
+            visitor.visit_column_mut(column, xpath)
+        }
+        CommonColumn::ColumnField(column) => visitor.visit_column_mut(column, xpath),
+    }
+}
+pub fn walk_grid_specific_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    grid_specific: &mut GridSpecific,
+    xpath: &str,
+) {
+    for section in grid_specific.sections.iter_mut() {
+        let section_xpath = keyed(xpath, "Section", &section.key);
+        visitor.visit_specific_section_mut(section, &section_xpath);
+    }
+}
+pub fn walk_specific_section_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    section: &mut SpecificSection,
+    xpath: &str,
+) {
+    for column in section.columns.iter_mut() {
+        let column_xpath = keyed(xpath, "Column-Field", &column.key);
+        visitor.visit_column_mut(column, &column_xpath);
+    }
+}
+fn walk_condition_groups_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    condition_groups: &mut [ConditionGroup],
+    xpath: &str,
+) {
+    for (index, condition_group) in condition_groups.iter_mut().enumerate() {
+        visitor.visit_condition_group_mut(
+            condition_group,
+            &format!("{}/Conditions/Condition-Group[{}]", xpath, index + 1),
+        );
+    }
+}
+fn walk_locals_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    title_locals: &mut Option<Vec<Local>>,
+    description_locals: &mut Option<Vec<Local>>,
+    xpath: &str,
+) {
+    if let Some(locals) = title_locals {
+        for local in locals.iter_mut() {
+            let local_xpath = local_xpath(xpath, "Title-Local", &local.lang);
+            visitor.visit_local_mut(local, &local_xpath);
+        }
+    }
+    if let Some(locals) = description_locals {
+        for local in locals.iter_mut() {
+            let local_xpath = local_xpath(xpath, "Description-Local", &local.lang);
+            visitor.visit_local_mut(local, &local_xpath);
+        }
+    }
+}
+fn walk_metadata_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    metadata: &mut Option<Vec<Metadata>>,
+    xpath: &str,
+) {
+    if let Some(metadata) = metadata {
+        for metadata in metadata.iter_mut() {
+            let metadata_xpath = keyed(xpath, "Metadata", &metadata.key);
+            visitor.visit_metadata_mut(metadata, &metadata_xpath);
+        }
+    }
+}
+
+// Xpath utils
+pub(crate) fn keyed(xpath: &str, element: &str, key: &str) -> String {
+    format!("{}/{}[@key='{}']", xpath, element, key)
+}
+pub(crate) fn specific(xpath: &str, classification: &str, category: &str) -> String {
+    format!(
+        "{}/Specific[@classification='{}'][@category='{}']",
+        xpath, classification, category
+    )
+}
+pub(crate) fn local_xpath(xpath: &str, element: &str, lang: &str) -> String {
+    format!("{}/{}[@lang='{}']", xpath, element, lang)
+}
+pub(crate) fn control_xpath(xpath: &str, control: &Control) -> String {
+    match control {
+        Control::RuleCondition { key, .. } => keyed(xpath, control.element_name(), key),
+        _ => format!("{}/{}", xpath, control.element_name()),
+    }
+}
@@ -1,14 +1,15 @@
-use std::{collections::HashMap, error::Error, fs::File, io::BufReader, str::from_utf8};
+use std::{collections::HashMap, error::Error, io::BufRead, str::from_utf8};
 
 use quick_xml::{events::Event, Reader};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     table_structs::{
-        Action, AttributeType, Category, Classification, Column, CommonAttributeRules,
-        CommonColumn, CommonSection, Condition, ConditionGroup, ConditionalFormatting, Control,
-        DataType, DefaultStatus, Field, Formula, GridSpecific, Identifier, Level, Local,
-        MatrixField, MatrixSpecific, Metadata, OptionRule, Partition, Rule, Screen, Section,
+        Action, AttributeType, BarcodeType, Category, Classification, Color, Column,
+        ColumnWidth, CommonAttributeRules, CommonColumn, CommonSection, Condition,
+        ConditionGroup, ConditionalFormatting, Control, DataType, DefaultStatus, Field, Formula,
+        GridSpecific, Identifier, Level, LineHeight, Local, MatrixField, MatrixSpecific,
+        Metadata, OpenEnum, OptionRule, Partition, Rounding, Rule, Screen, Section,
         SelectOption, SpecificAttributeRules, SpecificRules, SpecificSection, Status, Suffix,
         Table, UseSuffix,
     },
@@ -28,17 +29,22 @@ pub struct TableXmlParser {
 
 impl TableXmlParser {
     pub fn read(path: &str) -> Result<TableXmlParser, Box<dyn Error>> {
+        TableXmlParser::read_from(Reader::from_file(path)?)
+    }
+    pub fn read_from_str(xml: &str) -> Result<TableXmlParser, Box<dyn Error>> {
+        TableXmlParser::read_from(Reader::from_reader(xml.as_bytes()))
+    }
+    fn read_from<R: BufRead>(reader: Reader<R>) -> Result<TableXmlParser, Box<dyn Error>> {
         let mut xml_parser = TableXmlParser {
             table: Table::new(),
             warnings: vec![],
             errors: vec![],
         };
-        xml_parser.process_xml(path)?;
+        xml_parser.process_xml(reader)?;
         Ok(xml_parser)
     }
     // Parse xml file
-    fn process_xml(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
-        let mut reader: Reader<BufReader<File>> = Reader::from_file(path)?;
+    fn process_xml<R: BufRead>(&mut self, mut reader: Reader<R>) -> Result<(), Box<dyn Error>> {
         reader.trim_text(true);
 
         let mut buf = Vec::new();
@@ -95,10 +101,10 @@ impl TableXmlParser {
         Ok(())
     }
 
-    fn process_table(
+    fn process_table<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         self.table.key = attributes
@@ -111,7 +117,10 @@ impl TableXmlParser {
                     match ev.name().as_ref() {
                         b"Title" => self.table.title = self.handle_text(reader, buf)?,
                         b"Position" => self.table.position = self.handle_number(reader, buf)?,
-                        b"Color" => self.table.color = self.handle_text(reader, buf)?,
+                        b"Color" => {
+                            let color = Color::from(self.handle_text(reader, buf)?);
+                            self.table.color = self.known_value(color, "color", "Table")
+                        }
                         b"Description" => {
                             self.table.description = self.handle_optional_text(reader, buf)?
                         }
@@ -153,9 +162,9 @@ impl TableXmlParser {
         }
         Ok(())
     }
-    fn process_schema(
+    fn process_schema<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         loop {
@@ -186,9 +195,9 @@ impl TableXmlParser {
         Ok(())
     }
 
-    fn process_partitions(
+    fn process_partitions<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         loop {
@@ -209,10 +218,10 @@ impl TableXmlParser {
         }
         Ok(())
     }
-    fn process_partition(
+    fn process_partition<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         let mut partition = Partition::new();
@@ -267,9 +276,9 @@ impl TableXmlParser {
         Ok(())
     }
 
-    fn process_levels(
+    fn process_levels<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         loop {
@@ -290,17 +299,17 @@ impl TableXmlParser {
         }
         Ok(())
     }
-    fn process_level(
+    fn process_level<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         let mut level = Level::new();
         level.key = attributes
             .get("key")
             .unwrap_or(&"UNKNOWN".to_owned())
-            .to_owned();
+            .as_str().into();
         level.index = attributes
             .get("index")
             .unwrap_or(&"0".to_owned())
@@ -352,9 +361,9 @@ impl TableXmlParser {
         Ok(())
     }
 
-    fn process_identifiers(
+    fn process_identifiers<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         loop {
@@ -375,21 +384,21 @@ impl TableXmlParser {
         }
         Ok(())
     }
-    fn process_identifier(
+    fn process_identifier<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         let mut identifier = Identifier::new();
         identifier.key = attributes
             .get("key")
             .unwrap_or(&"UNKNOWN".to_owned())
-            .to_owned();
+            .as_str().into();
         identifier.level = attributes
             .get("level")
             .unwrap_or(&"UNKNOWN".to_owned())
-            .to_owned();
+            .as_str().into();
         identifier.index = attributes
             .get("index")
             .unwrap_or(&"0".to_owned())
@@ -441,9 +450,9 @@ impl TableXmlParser {
         Ok(())
     }
 
-    fn process_classifications(
+    fn process_classifications<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         loop {
@@ -464,17 +473,17 @@ impl TableXmlParser {
         }
         Ok(())
     }
-    fn process_classification(
+    fn process_classification<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         let mut classification = Classification::new();
         classification.key = attributes
             .get("key")
             .unwrap_or(&"UNKNOWN".to_owned())
-            .to_owned();
+            .as_str().into();
         loop {
             match reader.read_event_into(buf)? {
                 Event::Start(ev) => {
@@ -523,9 +532,9 @@ impl TableXmlParser {
         self.table.schema.classifications.push(classification);
         Ok(())
     }
-    fn process_categories(
+    fn process_categories<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Vec<Category>, Box<dyn Error>> {
         let mut categories: Vec<Category> = vec![];
@@ -549,19 +558,19 @@ impl TableXmlParser {
         }
         Ok(categories)
     }
-    fn process_category(
+    fn process_category<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Category, Box<dyn Error>> {
         let mut category = Category::new();
         category.key = attributes
             .get("key")
             .unwrap_or(&"UNKNOWN".to_owned())
-            .to_owned();
+            .as_str().into();
         match attributes.get("parent") {
-            Some(parent) => category.parent = Some(parent.to_owned()),
+            Some(parent) => category.parent = Some(parent.as_str().into()),
             None => (),
         }
         loop {
@@ -609,9 +618,9 @@ impl TableXmlParser {
         Ok(category)
     }
 
-    fn process_fields(
+    fn process_fields<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         loop {
@@ -632,10 +641,10 @@ impl TableXmlParser {
         }
         Ok(())
     }
-    fn process_field(
+    fn process_field<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         let key = attributes
@@ -651,22 +660,22 @@ impl TableXmlParser {
             Err("Data type is empty, field is skipped")?;
         }
         let mut field = match data_type.unwrap().as_str() {
-            "SINGLE-LINE-TEXT" => Field::new(key, level, DataType::SingleLineText),
-            "LONG-TEXT" => Field::new(key, level, DataType::LongText),
-            "HTML-TEXT" => Field::new(key, level, DataType::HtmlText),
-            "NUMBER" => Field::new(key, level, DataType::Number),
-            "SINGLE-SELECT" => Field::new(key, level, DataType::SingleSelect),
-            "MULTIPLE-SELECT" => Field::new(key, level, DataType::MultipleSelect),
+            "SINGLE-LINE-TEXT" => Field::new(key.into(), level.into(), DataType::SingleLineText),
+            "LONG-TEXT" => Field::new(key.into(), level.into(), DataType::LongText),
+            "HTML-TEXT" => Field::new(key.into(), level.into(), DataType::HtmlText),
+            "NUMBER" => Field::new(key.into(), level.into(), DataType::Number),
+            "SINGLE-SELECT" => Field::new(key.into(), level.into(), DataType::SingleSelect),
+            "MULTIPLE-SELECT" => Field::new(key.into(), level.into(), DataType::MultipleSelect),
             "MULTIPLE-SELECT-QUANTIFIED" => {
-                Field::new(key, level, DataType::MultipleSelectQuantified)
+                Field::new(key.into(), level.into(), DataType::MultipleSelectQuantified)
             }
             "MULTIPLE-SELECT-QUANTIFIED-WITH-COMMENTS" => {
-                Field::new(key, level, DataType::MultipleSelectQuantifiedWithComments)
+                Field::new(key.into(), level.into(), DataType::MultipleSelectQuantifiedWithComments)
             }
-            "DATE" => Field::new(key, level, DataType::Date),
-            "DATE-TIME" => Field::new(key, level, DataType::DateTime),
-            "IMAGE" => Field::new(key, level, DataType::Image),
-            "ATTACHMENT" => Field::new(key, level, DataType::Attachment),
+            "DATE" => Field::new(key.into(), level.into(), DataType::Date),
+            "DATE-TIME" => Field::new(key.into(), level.into(), DataType::DateTime),
+            "IMAGE" => Field::new(key.into(), level.into(), DataType::Image),
+            "ATTACHMENT" => Field::new(key.into(), level.into(), DataType::Attachment),
             _ => Err("Unvalid data type, field is skipped")?,
         };
         loop {
@@ -728,10 +737,10 @@ impl TableXmlParser {
 
         Ok(())
     }
-    fn process_options(
+    fn process_options<R: BufRead>(
         &mut self,
         field: &mut Field,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         let mut options: Vec<SelectOption> = vec![];
@@ -756,19 +765,21 @@ impl TableXmlParser {
         field.options = Some(options);
         Ok(())
     }
-    fn process_option(
+    fn process_option<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<SelectOption, Box<dyn Error>> {
         let mut option = SelectOption::new();
         option.key = attributes
             .get("key")
             .unwrap_or(&"UNKNOWN".to_owned())
-            .to_owned();
+            .as_str().into();
         match attributes.get("color") {
-            Some(color) => option.color = Some(color.to_owned()),
+            Some(color) => {
+                option.color = Some(self.known_value(Color::from(color.as_str()), "color", "Option"))
+            }
             None => (),
         }
         loop {
@@ -819,10 +830,10 @@ impl TableXmlParser {
         }
         Ok(option)
     }
-    fn process_suffixes(
+    fn process_suffixes<R: BufRead>(
         &mut self,
         field: &mut Field,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         let mut suffixes: Vec<Suffix> = vec![];
@@ -849,17 +860,17 @@ impl TableXmlParser {
         field.suffixes = Some(suffixes);
         Ok(())
     }
-    fn process_suffix(
+    fn process_suffix<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Suffix, Box<dyn Error>> {
         let mut suffix = Suffix::new();
         suffix.key = attributes
             .get("key")
             .unwrap_or(&"UNKNOWN".to_owned())
-            .to_owned();
+            .as_str().into();
         match attributes.get("default") {
             Some(default) => match default.as_bytes() {
                 b"true" => suffix.default = Some(true),
@@ -912,9 +923,9 @@ impl TableXmlParser {
         Ok(suffix)
     }
 
-    fn process_matrix(
+    fn process_matrix<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         loop {
@@ -934,8 +945,8 @@ impl TableXmlParser {
                             None => (),
                         }
                         let mut specific = MatrixSpecific {
-                            classification: classification,
-                            category: category,
+                            classification: classification.into(),
+                            category: category.into(),
                             fields: vec![],
                         };
                         self.process_matrix_specific(&mut specific, reader, buf)?;
@@ -953,9 +964,9 @@ impl TableXmlParser {
         }
         Ok(())
     }
-    fn process_matrix_common(
+    fn process_matrix_common<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         loop {
@@ -971,7 +982,7 @@ impl TableXmlParser {
                             .schema
                             .matrix
                             .common
-                            .push(MatrixField { key: key })
+                            .push(MatrixField { key: key.into() })
                     }
                     _ => (),
                 },
@@ -985,10 +996,10 @@ impl TableXmlParser {
         }
         Ok(())
     }
-    fn process_matrix_specific(
+    fn process_matrix_specific<R: BufRead>(
         &self,
         specific: &mut MatrixSpecific,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         loop {
@@ -1000,7 +1011,7 @@ impl TableXmlParser {
                             Some(value) => key = value.to_owned(),
                             None => (),
                         }
-                        specific.fields.push(MatrixField { key: key })
+                        specific.fields.push(MatrixField { key: key.into() })
                     }
                     _ => (),
                 },
@@ -1015,9 +1026,9 @@ impl TableXmlParser {
         Ok(())
     }
 
-    fn process_formulas(
+    fn process_formulas<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         loop {
@@ -1053,10 +1064,10 @@ impl TableXmlParser {
         }
         Ok(())
     }
-    fn process_formula(
+    fn process_formula<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
         attribute_type: AttributeType,
     ) -> Result<(), Box<dyn Error>> {
@@ -1094,10 +1105,10 @@ impl TableXmlParser {
         }
         Ok(())
     }
-    fn process_rule(
+    fn process_rule<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Option<Rule>, Box<dyn Error>> {
         let mut priority: usize = 0;
@@ -1146,10 +1157,10 @@ impl TableXmlParser {
             None => Ok(None),
         }
     }
-    fn process_action(
+    fn process_action<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Option<Action>, Box<dyn Error>> {
         match attributes.get("type") {
@@ -1164,9 +1175,9 @@ impl TableXmlParser {
             None => Ok(None),
         }
     }
-    fn process_action_set_text(
+    fn process_action_set_text<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Action, Box<dyn Error>> {
         let mut trim_spaces = false;
@@ -1199,9 +1210,9 @@ impl TableXmlParser {
             value: value,
         })
     }
-    fn process_action_template(
+    fn process_action_template<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<String, Box<dyn Error>> {
         let mut value = "".to_owned();
@@ -1219,13 +1230,13 @@ impl TableXmlParser {
         }
         Ok(value)
     }
-    fn process_action_set_number(
+    fn process_action_set_number<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Action, Box<dyn Error>> {
         let mut precision: usize = 0;
-        let mut round: String = "CEILING".to_owned();
+        let mut round = Rounding::Ceiling;
         let mut value = "".to_owned();
         loop {
             match reader.read_event_into(buf)? {
@@ -1239,7 +1250,13 @@ impl TableXmlParser {
                             None => (),
                         }
                         match get_attributes(ev.attributes())?.get("round") {
-                            Some(round_str) => round = round_str.to_owned(),
+                            Some(round_str) => {
+                                round = self.known_value(
+                                    Rounding::from(round_str.as_str()),
+                                    "rounding",
+                                    "Action > Template",
+                                )
+                            }
                             None => (),
                         }
                         value = self.process_action_template(reader, buf)?
@@ -1260,9 +1277,9 @@ impl TableXmlParser {
             value: value,
         })
     }
-    fn process_action_set_selectable_options(
+    fn process_action_set_selectable_options<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Action, Box<dyn Error>> {
         let mut values: Vec<String> = vec![];
@@ -1287,9 +1304,9 @@ impl TableXmlParser {
         }
         Ok(Action::SetSelectableOptions { values: values })
     }
-    fn process_action_set_selectable_options_value(
+    fn process_action_set_selectable_options_value<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Option<String>, Box<dyn Error>> {
         let mut value: Option<String> = None;
@@ -1307,9 +1324,9 @@ impl TableXmlParser {
         Ok(value)
     }
 
-    fn process_conditional_formattings(
+    fn process_conditional_formattings<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         loop {
@@ -1332,19 +1349,19 @@ impl TableXmlParser {
         }
         Ok(())
     }
-    fn process_conditional_formatting(
+    fn process_conditional_formatting<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         let mut conditional_formatting = ConditionalFormatting::new();
         match attributes.get("key") {
-            Some(key) => conditional_formatting.key = key.to_owned(),
+            Some(key) => conditional_formatting.key = key.as_str().into(),
             None => (),
         }
         match attributes.get("level") {
-            Some(level) => conditional_formatting.level = level.to_owned(),
+            Some(level) => conditional_formatting.level = level.as_str().into(),
             None => (),
         }
         loop {
@@ -1408,11 +1425,11 @@ impl TableXmlParser {
 
         Ok(())
     }
-    fn process_conditional_formatting_default_status(
+    fn process_conditional_formatting_default_status<R: BufRead>(
         &mut self,
         conditional_formatting: &mut ConditionalFormatting,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         let mut default_status = DefaultStatus::new();
@@ -1424,7 +1441,10 @@ impl TableXmlParser {
             match reader.read_event_into(buf)? {
                 Event::Start(ev) => match ev.name().as_ref() {
                     b"Title" => default_status.title = self.handle_text(reader, buf)?,
-                    b"Color" => default_status.color = self.handle_text(reader, buf)?,
+                    b"Color" => {
+                        let color = Color::from(self.handle_text(reader, buf)?);
+                        default_status.color = self.known_value(color, "color", "Default-Status")
+                    }
                     b"Description" => {
                         default_status.description = self.handle_optional_text(reader, buf)?
                     }
@@ -1463,10 +1483,10 @@ impl TableXmlParser {
         conditional_formatting.default_status = default_status;
         Ok(())
     }
-    fn process_conditional_formatting_statuses(
+    fn process_conditional_formatting_statuses<R: BufRead>(
         &mut self,
         conditional_formatting: &mut ConditionalFormatting,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         let mut statuses: Vec<Status> = vec![];
@@ -1491,10 +1511,10 @@ impl TableXmlParser {
         conditional_formatting.statuses = statuses;
         Ok(())
     }
-    fn process_conditional_formatting_status(
+    fn process_conditional_formatting_status<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Status, Box<dyn Error>> {
         let mut status = Status::new();
@@ -1507,7 +1527,10 @@ impl TableXmlParser {
                 Event::Start(ev) => {
                     match ev.name().as_ref() {
                         b"Title" => status.title = self.handle_text(reader, buf)?,
-                        b"Color" => status.color = self.handle_text(reader, buf)?,
+                        b"Color" => {
+                            let color = Color::from(self.handle_text(reader, buf)?);
+                            status.color = self.known_value(color, "color", "Status")
+                        }
                         b"Priority" => status.priority = self.handle_number(reader, buf)?,
                         b"Description" => {
                             status.description = self.handle_optional_text(reader, buf)?
@@ -1549,10 +1572,10 @@ impl TableXmlParser {
         }
         Ok(status)
     }
-    fn process_status_rules(
+    fn process_status_rules<R: BufRead>(
         &mut self,
         status: &mut Status,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         loop {
@@ -1581,9 +1604,9 @@ impl TableXmlParser {
         }
         Ok(())
     }
-    fn process_status_rules_common(
+    fn process_status_rules_common<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Vec<CommonAttributeRules>, Box<dyn Error>> {
         let mut common: Vec<CommonAttributeRules> = vec![];
@@ -1647,10 +1670,10 @@ impl TableXmlParser {
         }
         Ok(common)
     }
-    fn process_status_rules_specific(
+    fn process_status_rules_specific<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Option<SpecificRules>, Box<dyn Error>> {
         match attributes.get("classification") {
@@ -1669,7 +1692,7 @@ impl TableXmlParser {
                                     let controls = self.process_controls(reader, buf)?;
                                     if controls.len() > 0 {
                                         attributes.push(SpecificAttributeRules {
-                                            key: key,
+                                            key: key.into(),
                                             controls: controls,
                                         })
                                     }
@@ -1686,8 +1709,8 @@ impl TableXmlParser {
                     }
                     if attributes.len() > 0 {
                         Ok(Some(SpecificRules::new(
-                            classification.to_owned(),
-                            category.to_owned(),
+                            classification.as_str().into(),
+                            category.as_str().into(),
                             attributes,
                         )))
                     } else {
@@ -1699,9 +1722,9 @@ impl TableXmlParser {
             None => Ok(None),
         }
     }
-    fn process_controls(
+    fn process_controls<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Vec<Control>, Box<dyn Error>> {
         let mut controls: Vec<Control> = vec![];
@@ -1710,9 +1733,14 @@ impl TableXmlParser {
                 Event::Start(ev) | Event::Empty(ev) => match ev.name().as_ref() {
                     b"Rule-Required" => controls.push(Control::RuleRequired),
                     b"Rule-Barcode" => match get_attributes(ev.attributes())?.get("type") {
-                        Some(barcode_type) => controls.push(Control::RuleBarcode {
-                            barcode_type: barcode_type.to_owned(),
-                        }),
+                        Some(barcode_type) => {
+                            let barcode_type = self.known_value(
+                                BarcodeType::from(barcode_type.as_str()),
+                                "barcode type",
+                                "Rule-Barcode",
+                            );
+                            controls.push(Control::RuleBarcode { barcode_type })
+                        }
                         None => (),
                     },
                     b"Rule-Regex" => match get_attributes(ev.attributes())?.get("regex") {
@@ -1854,7 +1882,7 @@ impl TableXmlParser {
                         match get_attributes(ev.attributes())?.get("field") {
                             Some(field) => {
                                 controls.push(Control::RuleMustBeGreaterThanAnotherField {
-                                    field: field.to_owned(),
+                                    field: field.as_str().into(),
                                 })
                             }
                             None => (),
@@ -1864,7 +1892,7 @@ impl TableXmlParser {
                         match get_attributes(ev.attributes())?.get("field") {
                             Some(field) => {
                                 controls.push(Control::RuleMustBeGreaterThanOrEqualAnotherField {
-                                    field: field.to_owned(),
+                                    field: field.as_str().into(),
                                 })
                             }
                             None => (),
@@ -1873,7 +1901,7 @@ impl TableXmlParser {
                     b"Rule-Must-Be-Less-Than-Another-Field" => {
                         match get_attributes(ev.attributes())?.get("field") {
                             Some(field) => controls.push(Control::RuleMustBeLessThanAnotherField {
-                                field: field.to_owned(),
+                                field: field.as_str().into(),
                             }),
                             None => (),
                         }
@@ -1882,7 +1910,7 @@ impl TableXmlParser {
                         match get_attributes(ev.attributes())?.get("field") {
                             Some(field) => {
                                 controls.push(Control::RuleMustBeLessThanOrEqualAnotherField {
-                                    field: field.to_owned(),
+                                    field: field.as_str().into(),
                                 })
                             }
                             None => (),
@@ -1892,7 +1920,7 @@ impl TableXmlParser {
                         match get_attributes(ev.attributes())?.get("field") {
                             Some(field) => {
                                 controls.push(Control::RuleRequiredIfAnotherFieldIsNotEmpty {
-                                    field: field.to_owned(),
+                                    field: field.as_str().into(),
                                 })
                             }
                             None => (),
@@ -1911,7 +1939,7 @@ impl TableXmlParser {
                                                         .get("key")
                                                     {
                                                         Some(key) => options.push(OptionRule {
-                                                            key: key.to_owned(),
+                                                            key: key.as_str().into(),
                                                         }),
                                                         None => (),
                                                     }
@@ -1926,7 +1954,7 @@ impl TableXmlParser {
                                 buf.clear();
                                 if options.len() > 0 {
                                     controls.push(Control::RuleRequiredIfAnotherFieldHasOptions {
-                                        field: field.to_owned(),
+                                        field: field.as_str().into(),
                                         options: options,
                                     })
                                 }
@@ -1939,7 +1967,7 @@ impl TableXmlParser {
                             Some(field) => match get_attributes(ev.attributes())?.get("value") {
                                 Some(value) => controls.push(
                                     Control::RuleRequiredIfAnotherFieldIsGreaterThan {
-                                        field: field.to_owned(),
+                                        field: field.as_str().into(),
                                         value: value.to_owned(),
                                     },
                                 ),
@@ -1953,7 +1981,7 @@ impl TableXmlParser {
                             Some(field) => match get_attributes(ev.attributes())?.get("value") {
                                 Some(value) => controls.push(
                                     Control::RuleRequiredIfAnotherFieldIsGreaterThanOrEqual {
-                                        field: field.to_owned(),
+                                        field: field.as_str().into(),
                                         value: value.to_owned(),
                                     },
                                 ),
@@ -1967,7 +1995,7 @@ impl TableXmlParser {
                             Some(field) => match get_attributes(ev.attributes())?.get("value") {
                                 Some(value) => {
                                     controls.push(Control::RuleRequiredIfAnotherFieldIsLessThan {
-                                        field: field.to_owned(),
+                                        field: field.as_str().into(),
                                         value: value.to_owned(),
                                     })
                                 }
@@ -1981,7 +2009,7 @@ impl TableXmlParser {
                             Some(field) => match get_attributes(ev.attributes())?.get("value") {
                                 Some(value) => controls.push(
                                     Control::RuleRequiredIfAnotherFieldIsLessThanOrEqual {
-                                        field: field.to_owned(),
+                                        field: field.as_str().into(),
                                         value: value.to_owned(),
                                     },
                                 ),
@@ -1995,7 +2023,7 @@ impl TableXmlParser {
                             Some(field) => match get_attributes(ev.attributes())?.get("value") {
                                 Some(value) => {
                                     controls.push(Control::RuleRequiredIfAnotherFieldIsEqualTo {
-                                        field: field.to_owned(),
+                                        field: field.as_str().into(),
                                         value: value.to_owned(),
                                     })
                                 }
@@ -2028,10 +2056,10 @@ impl TableXmlParser {
         }
         Ok(controls)
     }
-    fn process_rule_condition(
+    fn process_rule_condition<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Option<Control>, Box<dyn Error>> {
         let mut key: String = "UNKNOWN".to_owned();
@@ -2074,9 +2102,9 @@ impl TableXmlParser {
 
         Ok(Some(control))
     }
-    fn process_rule_condition_conditions(
+    fn process_rule_condition_conditions<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Vec<ConditionGroup>, Box<dyn Error>> {
         let mut condition_groups: Vec<ConditionGroup> = vec![];
@@ -2101,9 +2129,9 @@ impl TableXmlParser {
         }
         Ok(condition_groups)
     }
-    fn process_rule_condition_condition_group(
+    fn process_rule_condition_condition_group<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Option<ConditionGroup>, Box<dyn Error>> {
         let mut conditions: Vec<Condition> = vec![];
@@ -2131,10 +2159,10 @@ impl TableXmlParser {
             conditions: conditions,
         }))
     }
-    fn process_rule_condition_condition_group_condition(
+    fn process_rule_condition_condition_group_condition<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Option<Condition>, Box<dyn Error>> {
         match attributes.get("source") {
@@ -2329,9 +2357,9 @@ impl TableXmlParser {
         }
     }
 
-    fn process_sections(
+    fn process_sections<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         loop {
@@ -2352,15 +2380,15 @@ impl TableXmlParser {
         }
         Ok(())
     }
-    fn process_section(
+    fn process_section<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         let mut section = Section::new();
         match attributes.get("key") {
-            Some(key) => section.key = key.to_owned(),
+            Some(key) => section.key = key.as_str().into(),
             None => (),
         }
         loop {
@@ -2410,9 +2438,9 @@ impl TableXmlParser {
         Ok(())
     }
 
-    fn process_screens(
+    fn process_screens<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         loop {
@@ -2433,10 +2461,10 @@ impl TableXmlParser {
         }
         Ok(())
     }
-    fn process_screen(
+    fn process_screen<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         let mut screen = Screen::new();
@@ -2445,7 +2473,7 @@ impl TableXmlParser {
             None => (),
         }
         match attributes.get("level") {
-            Some(level) => screen.level = level.to_owned(),
+            Some(level) => screen.level = level.as_str().into(),
             None => (),
         }
         loop {
@@ -2496,16 +2524,20 @@ impl TableXmlParser {
 
         Ok(())
     }
-    fn process_screen_grid(
+    fn process_screen_grid<R: BufRead>(
         &mut self,
         screen: &mut Screen,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<(), Box<dyn Error>> {
         loop {
             match reader.read_event_into(buf)? {
                 Event::Start(ev) => match ev.name().as_ref() {
-                    b"Line-Height" => screen.grid.line_height = self.handle_text(reader, buf)?,
+                    b"Line-Height" => {
+                        let line_height = LineHeight::from(self.handle_text(reader, buf)?);
+                        screen.grid.line_height =
+                            self.known_value(line_height, "line height", "Grid")
+                    }
                     b"Common" => screen.grid.common = self.process_grid_common(reader, buf)?,
                     b"Specific" => screen.grid.specifics.push(self.process_grid_specific(
                         get_attributes(ev.attributes())?,
@@ -2525,9 +2557,9 @@ impl TableXmlParser {
 
         Ok(())
     }
-    fn process_grid_common(
+    fn process_grid_common<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Vec<CommonSection>, Box<dyn Error>> {
         let mut common: Vec<CommonSection> = vec![];
@@ -2551,15 +2583,15 @@ impl TableXmlParser {
         }
         Ok(common)
     }
-    fn process_grid_common_section(
+    fn process_grid_common_section<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<CommonSection, Box<dyn Error>> {
         let mut section: CommonSection = CommonSection::new();
         match attributes.get("key") {
-            Some(key) => section.key = key.to_owned(),
+            Some(key) => section.key = key.as_str().into(),
             None => (),
         }
         match attributes.get("position") {
@@ -2579,7 +2611,17 @@ impl TableXmlParser {
                         .unwrap_or(&"0".to_owned())
                         .parse()
                         .unwrap_or(0);
-                    let width = attributes.get("width").cloned();
+                    // An empty width is written as no width
+                    let width = attributes
+                        .get("width")
+                        .filter(|width| !width.is_empty())
+                        .map(|width| {
+                            self.known_value(
+                                ColumnWidth::from(width.as_str()),
+                                "column width",
+                                "Section",
+                            )
+                        });
                     let read_only = match attributes.get("read-only") {
                         Some(read_only) => match read_only.as_str() {
                             "true" => Some(true),
@@ -2597,7 +2639,7 @@ impl TableXmlParser {
                     match ev.name().as_ref() {
                         b"Column-Identifier" => {
                             section.columns.push(CommonColumn::ColumnIdentifier(Column {
-                                key: key.to_owned(),
+                                key: key.as_str().into(),
                                 position: position,
                                 width: width,
                                 read_only: read_only,
@@ -2608,7 +2650,7 @@ impl TableXmlParser {
                             section
                                 .columns
                                 .push(CommonColumn::ColumnClassification(Column {
-                                    key: key.to_owned(),
+                                    key: key.as_str().into(),
                                     position: position,
                                     width: width,
                                     read_only: read_only,
@@ -2617,7 +2659,7 @@ impl TableXmlParser {
                         }
                         b"Column-Field" => {
                             section.columns.push(CommonColumn::ColumnField(Column {
-                                key: key.to_owned(),
+                                key: key.as_str().into(),
                                 position: position,
                                 width: width,
                                 read_only: read_only,
@@ -2628,7 +2670,7 @@ impl TableXmlParser {
                             section
                                 .columns
                                 .push(CommonColumn::ColumnConditionalFormatting(Column {
-                                    key: key.to_owned(),
+                                    key: key.as_str().into(),
                                     position: position,
                                     width: width,
                                     read_only: read_only,
@@ -2653,19 +2695,19 @@ impl TableXmlParser {
 
         Ok(section)
     }
-    fn process_grid_specific(
+    fn process_grid_specific<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<GridSpecific, Box<dyn Error>> {
         let mut specific: GridSpecific = GridSpecific::new();
         match attributes.get("classification") {
-            Some(classification) => specific.classification = classification.to_owned(),
+            Some(classification) => specific.classification = classification.as_str().into(),
             None => (),
         }
         match attributes.get("category") {
-            Some(category) => specific.category = category.to_owned(),
+            Some(category) => specific.category = category.as_str().into(),
             None => (),
         }
         loop {
@@ -2689,15 +2731,15 @@ impl TableXmlParser {
 
         Ok(specific)
     }
-    fn process_grid_specific_section(
+    fn process_grid_specific_section<R: BufRead>(
         &mut self,
         attributes: HashMap<String, String>,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<SpecificSection, Box<dyn Error>> {
         let mut section: SpecificSection = SpecificSection::new();
         match attributes.get("key") {
-            Some(key) => section.key = key.to_owned(),
+            Some(key) => section.key = key.as_str().into(),
             None => (),
         }
         match attributes.get("position") {
@@ -2717,7 +2759,17 @@ impl TableXmlParser {
                         .unwrap_or(&"0".to_owned())
                         .parse()
                         .unwrap_or(0);
-                    let width = attributes.get("width").cloned();
+                    // An empty width is written as no width
+                    let width = attributes
+                        .get("width")
+                        .filter(|width| !width.is_empty())
+                        .map(|width| {
+                            self.known_value(
+                                ColumnWidth::from(width.as_str()),
+                                "column width",
+                                "Section",
+                            )
+                        });
                     let read_only = match attributes.get("read-only") {
                         Some(read_only) => match read_only.as_str() {
                             "true" => Some(true),
@@ -2734,7 +2786,7 @@ impl TableXmlParser {
                     };
                     match ev.name().as_ref() {
                         b"Column-Field" => section.columns.push(Column {
-                            key: key.to_owned(),
+                            key: key.as_str().into(),
                             position: position,
                             width: width,
                             read_only: read_only,
@@ -2757,9 +2809,9 @@ impl TableXmlParser {
     }
 
     // Generic functions to handle text, number... private to this context
-    fn handle_text(
+    fn handle_text<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<String, Box<dyn Error>> {
         let mut text: String = "UNKNOWN".to_owned();
@@ -2773,9 +2825,9 @@ impl TableXmlParser {
         }
         Ok(text)
     }
-    fn handle_optional_text(
+    fn handle_optional_text<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Option<String>, Box<dyn Error>> {
         let mut text: String = "".to_owned();
@@ -2794,9 +2846,9 @@ impl TableXmlParser {
             Ok(Some(text))
         }
     }
-    fn handle_optional_number(
+    fn handle_optional_number<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Option<usize>, Box<dyn Error>> {
         let mut number: Option<usize> = None;
@@ -2813,10 +2865,10 @@ impl TableXmlParser {
         }
         Ok(number)
     }
-    fn handle_optional_local(
+    fn handle_optional_local<R: BufRead>(
         &mut self,
         lang: &String,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Option<Local>, Box<dyn Error>> {
         let mut value: String = "".to_owned();
@@ -2837,10 +2889,10 @@ impl TableXmlParser {
             }))
         }
     }
-    fn handle_optional_metadata(
+    fn handle_optional_metadata<R: BufRead>(
         &mut self,
         key: &String,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Option<Metadata>, Box<dyn Error>> {
         let mut value: String = "".to_owned();
@@ -2861,9 +2913,9 @@ impl TableXmlParser {
             }))
         }
     }
-    fn handle_number(
+    fn handle_number<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<usize, Box<dyn Error>> {
         let mut number: usize = 0;
@@ -2877,9 +2929,9 @@ impl TableXmlParser {
         }
         Ok(number)
     }
-    fn handle_value_text(
+    fn handle_value_text<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Option<String>, Box<dyn Error>> {
         let mut value: Option<String> = None;
@@ -2912,9 +2964,9 @@ impl TableXmlParser {
         }
         Ok(value)
     }
-    fn handle_value_number(
+    fn handle_value_number<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Option<usize>, Box<dyn Error>> {
         let mut value: Option<usize> = None;
@@ -2944,9 +2996,9 @@ impl TableXmlParser {
         }
         Ok(value)
     }
-    fn handle_values(
+    fn handle_values<R: BufRead>(
         &mut self,
-        reader: &mut Reader<BufReader<File>>,
+        reader: &mut Reader<R>,
         buf: &mut Vec<u8>,
     ) -> Result<Option<Vec<String>>, Box<dyn Error>> {
         let mut values: Vec<String> = vec![];
@@ -2986,4 +3038,14 @@ impl TableXmlParser {
     fn add_warning(&mut self, log: Log) {
         self.warnings.push(log)
     }
+    // Unknown values are kept to be written back, the warning helps to catch typos
+    fn known_value<T: OpenEnum>(&mut self, value: T, name: &str, element: &str) -> T {
+        if let Some(unknown) = value.unknown() {
+            self.add_warning(Log {
+                code: format!("UNKNOWN_{}", name.to_uppercase().replace(' ', "_")),
+                message: format!("Unknown {} '{}' in {}.", name, unknown, element),
+            });
+        }
+        value
+    }
 }
@@ -5,13 +5,21 @@ use quick_xml::Error;
 use std::fs::File;
 use std::io::{BufWriter, Cursor, Write};
 
-use crate::table_structs::{Action, AttributeType, CommonColumn, Condition, ConditionGroup, Control, DataType, Table};
+use crate::table_structs::{Action, AttributeType, ColumnWidth, CommonColumn, Condition, ConditionGroup, Control, DataType, Table};
 
 pub struct TableXmlWriter {}
 
 impl TableXmlWriter {
     pub fn write(table: &Table, path: &str) -> Result<(), Error> {
-        let mut writer = Writer::new(BufWriter::new(File::create(path).unwrap()));
+        TableXmlWriter::write_to(table, BufWriter::new(File::create(path).unwrap()))
+    }
+    pub fn write_to_string(table: &Table) -> Result<String, Error> {
+        let mut buffer = Vec::new();
+        TableXmlWriter::write_to(table, &mut buffer)?;
+        Ok(String::from_utf8_lossy(&buffer).into_owned())
+    }
+    pub fn write_to<W: Write>(table: &Table, inner: W) -> Result<(), Error> {
+        let mut writer = Writer::new(inner);
 
         writer
             .create_element("Table")
@@ -21,7 +29,7 @@ impl TableXmlWriter {
                     .create_element("Title")
                     .write_text_content(BytesText::new(table.title.to_owned().as_str()))?
                     .create_element("Color")
-                    .write_text_content(BytesText::new(table.color.to_owned().as_str()))?
+                    .write_text_content(BytesText::new(table.color.to_string().as_str()))?
                     .create_element("Position")
                     .write_text_content(BytesText::new(table.position.to_string().as_str()))?;
                 match &table.description {
@@ -524,7 +532,7 @@ impl TableXmlWriter {
                                                                                 writer
                                                                                 .create_element("Option")
                                                                                 .with_attribute(("key", option.key.to_owned().as_str()))
-                                                                                .with_attribute(("color", color.to_owned().as_str()))
+                                                                                .with_attribute(("color", color.to_string().as_str()))
                                                                             }
                                                                         };
                                                                         option_el.write_inner_content::<_,Error>(|writer| {
@@ -632,7 +640,7 @@ impl TableXmlWriter {
                                                                     writer
                                                                         .create_element("Template")
                                                                         .with_attribute(("precision", precision.to_string().as_str()))
-                                                                        .with_attribute(("round", round.to_owned().as_str()))
+                                                                        .with_attribute(("round", round.to_string().as_str()))
                                                                         .write_cdata_content(BytesCData::new(value.to_string().as_str()))?;
                                                                     Ok(())
                                                                 })?;
@@ -751,7 +759,7 @@ impl TableXmlWriter {
                                                         .write_text_content(BytesText::new(&conditional_formatting.default_status.title.to_owned().as_str()))?;
                                                     writer
                                                         .create_element("Color")
-                                                        .write_text_content(BytesText::new(conditional_formatting.default_status.color.to_owned().as_str()))?;
+                                                        .write_text_content(BytesText::new(conditional_formatting.default_status.color.to_string().as_str()))?;
                                                     match &conditional_formatting.default_status.description {
                                                         None => (),
                                                         Some(description) => {
@@ -808,7 +816,7 @@ impl TableXmlWriter {
                                                                 .write_text_content(BytesText::new(status.title.to_owned().as_str()))?;
                                                             writer
                                                                 .create_element("Color")
-                                                                .write_text_content(BytesText::new(status.color.to_owned().as_str()))?;
+                                                                .write_text_content(BytesText::new(status.color.to_string().as_str()))?;
                                                             writer
                                                                 .create_element("Priority")
                                                                 .write_text_content(BytesText::new(status.priority.to_string().as_str()))?;
@@ -1035,7 +1043,7 @@ impl TableXmlWriter {
                                                 .write_inner_content::<_, Error>(|writer| {
                                                     writer
                                                         .create_element("Line-Height")
-                                                        .write_text_content(BytesText::new(screen.grid.line_height.to_owned().as_str()))?
+                                                        .write_text_content(BytesText::new(screen.grid.line_height.to_string().as_str()))?
                                                         .create_element("Common")
                                                         .write_inner_content::<_,Error>(|writer| {
                                                             for section in screen.grid.common.iter() {
@@ -1057,8 +1065,8 @@ impl TableXmlWriter {
                                                                                         None => (),
                                                                                     }
                                                                                     match &column.width {
-                                                                                        Some(width) => if width != "" && width != "MEDIUM" {
-                                                                                            column_el = column_el.with_attribute(("width", width.to_owned().as_str()));
+                                                                                        Some(width) => if *width != ColumnWidth::Medium && !width.to_string().is_empty() {
+                                                                                            column_el = column_el.with_attribute(("width", width.to_string().as_str()));
                                                                                         },
                                                                                         None => (),
                                                                                     }
@@ -1081,8 +1089,8 @@ impl TableXmlWriter {
                                                                                         None => (),
                                                                                     }
                                                                                     match &column.width {
-                                                                                        Some(width) => if width != "" && width != "MEDIUM" {
-                                                                                            column_el = column_el.with_attribute(("width", width.to_owned().as_str()));
+                                                                                        Some(width) => if *width != ColumnWidth::Medium && !width.to_string().is_empty() {
+                                                                                            column_el = column_el.with_attribute(("width", width.to_string().as_str()));
                                                                                         },
                                                                                         None => (),
                                                                                     }
@@ -1105,8 +1113,8 @@ impl TableXmlWriter {
                                                                                         None => (),
                                                                                     }
                                                                                     match &column.width {
-                                                                                        Some(width) => if width != "" && width != "MEDIUM" {
-                                                                                            column_el = column_el.with_attribute(("width", width.to_owned().as_str()));
+                                                                                        Some(width) => if *width != ColumnWidth::Medium && !width.to_string().is_empty() {
+                                                                                            column_el = column_el.with_attribute(("width", width.to_string().as_str()));
                                                                                         },
                                                                                         None => (),
                                                                                     }
@@ -1129,8 +1137,8 @@ impl TableXmlWriter {
                                                                                         None => (),
                                                                                     }
                                                                                     match &column.width {
-                                                                                        Some(width) => if width != "" && width != "MEDIUM" {
-                                                                                            column_el = column_el.with_attribute(("width", width.to_owned().as_str()));
+                                                                                        Some(width) => if *width != ColumnWidth::Medium && !width.to_string().is_empty() {
+                                                                                            column_el = column_el.with_attribute(("width", width.to_string().as_str()));
                                                                                         },
                                                                                         None => (),
                                                                                     }
@@ -1175,8 +1183,8 @@ impl TableXmlWriter {
                                                                                         None => (),
                                                                                     }
                                                                                     match &column.width {
-                                                                                        Some(width) => if width != "" && width != "MEDIUM" {
-                                                                                            column_el = column_el.with_attribute(("width", width.to_owned().as_str()));
+                                                                                        Some(width) => if *width != ColumnWidth::Medium && !width.to_string().is_empty() {
+                                                                                            column_el = column_el.with_attribute(("width", width.to_string().as_str()));
                                                                                         },
                                                                                         None => (),
                                                                                     }
@@ -1208,7 +1216,7 @@ impl TableXmlWriter {
         Ok(())
     }
 
-    fn process_controls(writer: &mut Writer<BufWriter<File>>, controls: &Vec<Control>) -> Result<(), Error> {
+    fn process_controls<W: Write>(writer: &mut Writer<W>, controls: &Vec<Control>) -> Result<(), Error> {
         for control in controls.iter() {
             match control {
                 Control::RuleRequired => {
@@ -1242,7 +1250,7 @@ impl TableXmlWriter {
                 Control::RuleBarcode { barcode_type } => {
                     writer
                         .create_element("Rule-Barcode")
-                        .with_attribute(("type", barcode_type.to_owned().as_str()))
+                        .with_attribute(("type", barcode_type.to_string().as_str()))
                         .write_empty()?;
                 },
                 Control::RuleLessThan { value } => {
@@ -1332,7 +1340,7 @@ impl TableXmlWriter {
                 Control::RuleMaxValues { max } =>  {
                     writer
                         .create_element("Rule-Max-Values")
-                        .with_attribute(("name", max.to_string().as_str()))
+                        .with_attribute(("max", max.to_string().as_str()))
                         .write_empty()?;
                 },
                 Control::RuleMustBeGreaterThanAnotherField { field } => {
@@ -1441,7 +1449,7 @@ impl TableXmlWriter {
         Ok(())
     }
 
-    fn process_conditions(writer: &mut Writer<BufWriter<File>>, condition_groups: &Vec<ConditionGroup>) -> Result<(), Error> {
+    fn process_conditions<W: Write>(writer: &mut Writer<W>, condition_groups: &Vec<ConditionGroup>) -> Result<(), Error> {
         writer
             .create_element("Conditions")
             .write_inner_content::<_, Error>(|writer| {
@@ -1488,7 +1496,7 @@ impl TableXmlWriter {
                                             },
                                             None => (),
                                         }
-                                        condition_el.write_inner_content::<_, Error>(|writer: &mut Writer<BufWriter<File>>| {
+                                        condition_el.write_inner_content::<_, Error>(|writer: &mut Writer<W>| {
                                                 writer
                                                     .create_element("Value")
                                                     .write_text_content(BytesText::new(value.to_owned().as_str()))?;
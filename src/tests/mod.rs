@@ -1,3 +1,23 @@
 pub mod table_xml_parser_tests;
 pub mod table_xml_writer_tests;
 pub mod table_validation_tests;
+pub mod table_visitor_tests;
+pub mod table_refactoring_tests;
+pub mod table_diff_tests;
+pub mod table_merge_tests;
+pub mod table_patch_tests;
+pub mod table_typescript_tests;
+pub mod table_json_tests;
+pub mod table_record_schema_tests;
+pub mod table_xsd_tests;
+pub mod table_structs_tests;
+pub mod table_locals_tests;
+pub mod table_translation_tests;
+pub mod table_documentation_tests;
+pub mod table_spreadsheet_tests;
+pub mod table_matrix_tests;
+pub mod table_effective_tests;
+pub mod table_screens_tests;
+pub mod table_stats_tests;
+pub mod table_lint_tests;
+pub mod table_diagnostics_tests;
@@ -0,0 +1,85 @@
+use std::fs;
+
+use serde_json::json;
+
+use crate::{
+    table_diagnostics::{parse, validate},
+    table_locals::Localized,
+    table_structs::Table,
+};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+// parseTable and validateTable return these values converted to JS objects with the
+// json_compatible serializer, they have the shape of their serde_json serialization
+
+#[test]
+fn parse_table_contract() {
+    let xml = fs::read_to_string("./src/tests/inputs/valid.xml").unwrap();
+    let result = serde_json::to_value(parse(&xml).unwrap()).unwrap();
+    let keys = result
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(|key| key.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(keys, vec!["table", "warnings", "errors"]);
+    assert_eq!(result["warnings"], json!([]));
+    assert_eq!(result["errors"], json!([]));
+    assert_eq!(
+        result["table"]["schema"]["fields"][0]["key"],
+        json!("TITLE_EN")
+    );
+    // writeTable and validateTable read the table back from this shape
+    assert_eq!(
+        serde_json::from_value::<Table>(result["table"].clone()).unwrap(),
+        parse(&xml).unwrap().table
+    );
+}
+
+#[test]
+fn parse_table_errors() {
+    let result = serde_json::to_value(parse("<Test></Test>").unwrap()).unwrap();
+    assert_eq!(
+        result["errors"],
+        json!([
+            {
+                "code": "WRONG_FIRST_ELEMENT",
+                "message": "File must start with the Table element. The element name found is 'Test'.",
+                "xpath": null,
+            },
+            {
+                "code": "MISSING_ELEMENT_TABLE",
+                "message": "The xml file must start with the Table. It's seams that the file is empty",
+                "xpath": null,
+            },
+        ])
+    );
+}
+
+#[test]
+fn validate_table_contract() {
+    let xml = fs::read_to_string("./src/tests/inputs/valid.xml").unwrap();
+    let mut table = parse(&xml).unwrap().table;
+    let result = serde_json::to_value(validate(&table).unwrap()).unwrap();
+    assert_eq!(result["errors"], json!([]));
+    assert_eq!(
+        result["warnings"][0],
+        json!({
+            "code": "UNTRANSLATED_LOCAL",
+            "message": "Title-Local 'fra' is identical to the title.",
+            "xpath": "/Table/Schema/Identifiers/Identifier[@key='EAN_13']/Title-Local[@lang='fra']",
+        })
+    );
+    table.schema.fields[1].set_title_local("fr", "Couleur");
+    assert_eq!(
+        serde_json::to_value(validate(&table).unwrap()).unwrap()["errors"],
+        json!([
+            {
+                "code": "INVALID_LANG",
+                "message": "Lang 'fr' is not a three-letter lowercase lang code.",
+                "xpath": "/Table/Schema/Fields/Field[@key='COLOR']/Title-Local[@lang='fr']/@lang",
+            },
+        ])
+    );
+}
@@ -0,0 +1,109 @@
+use crate::{
+    table_diff::{diff, ChangeKind, SchemaDiff},
+    table_structs::{Control, Local, SelectOption, Table},
+    table_xml_parser::TableXmlParser,
+};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn read_table() -> Table {
+    TableXmlParser::read("./src/tests/inputs/valid.xml")
+        .unwrap()
+        .table
+}
+
+#[test]
+fn diff_same_table() {
+    let table = read_table();
+    assert!(diff(&table, &table).is_empty());
+}
+
+#[test]
+fn diff_by_key() {
+    let old = read_table();
+    let mut new = read_table();
+    // Fields in another order are matched by key, the reorder is one change of the list
+    new.schema.fields.swap(0, 1);
+    let color = new
+        .schema
+        .fields
+        .iter_mut()
+        .find(|field| field.key == "COLOR")
+        .unwrap();
+    color.title = "Colour".to_owned();
+    color.title_locals = Some(vec![Local {
+        lang: "fra".to_owned(),
+        value: "Coloris".to_owned(),
+    }]);
+    let mut blue = SelectOption::new();
+    blue.key = "BLUE".into();
+    blue.title = "Blue".to_owned();
+    color.options.as_mut().unwrap().push(blue);
+    new.schema.fields.retain(|field| field.key != "STORAGE_GB");
+    let schema_diff = diff(&old, &new);
+    assert_eq!(
+        schema_diff.to_text(),
+        [
+            "~ schema.fields[COLOR].title changed: \"Color\" -> \"Colour\"",
+            "~ schema.fields[COLOR].titleLocals[fra] changed",
+            "+ schema.fields[COLOR].options[BLUE] added",
+            "- schema.fields[STORAGE_GB] removed",
+            "> schema.fields moved: TITLE_EN, COLOR, PRICE_DOLLAR, CAMERA_RESOLUTION, MAIN_IMAGE, DESCRIPTION_EN, PRICE_EURO, INSTRUCTIONS \
+             -> COLOR, TITLE_EN, PRICE_DOLLAR, CAMERA_RESOLUTION, MAIN_IMAGE, DESCRIPTION_EN, PRICE_EURO, INSTRUCTIONS",
+            "",
+        ]
+        .join("\n")
+    );
+    assert_eq!(schema_diff.changes[3].kind, ChangeKind::Removed);
+    assert_eq!(schema_diff.changes[4].kind, ChangeKind::Moved);
+    let json: SchemaDiff = serde_json::from_str(&schema_diff.to_json().unwrap()).unwrap();
+    assert_eq!(json, schema_diff);
+}
+
+#[test]
+fn diff_reordered_rules() {
+    let old = read_table();
+    let mut new = read_table();
+    new.schema.formulas[2].rules.reverse();
+    let schema_diff = diff(&old, &new);
+    assert_eq!(
+        schema_diff.to_text(),
+        "> schema.formulas[DEEE_SCALE].rules moved: 1, 2 -> 2, 1\n"
+    );
+    // Lists with duplicate keys are compared by position
+    new.schema.formulas[2].rules[1].priority = 2;
+    let schema_diff = diff(&old, &new);
+    assert!(schema_diff
+        .changes
+        .iter()
+        .all(|change| change.kind != ChangeKind::Moved
+            && change
+                .path
+                .starts_with("schema.formulas[DEEE_SCALE].rules[")));
+    assert!(schema_diff.changes.iter().any(|change| change
+        .path
+        .starts_with("schema.formulas[DEEE_SCALE].rules[2].")));
+}
+
+#[test]
+fn diff_controls() {
+    let old = read_table();
+    let mut new = read_table();
+    let controls = &mut new.schema.conditional_formattings[0].statuses[0]
+        .rules
+        .common[1]
+        .controls;
+    controls.remove(0);
+    match &mut controls[0] {
+        Control::RuleMinLength { min } => *min += 1,
+        _ => panic!("expected a Rule-Min-Length"),
+    }
+    let path = "schema.conditionalFormattings[COMPLIANCE].statuses[INVALID].rules.common[TITLE_EN]";
+    assert_eq!(
+        diff(&old, &new).to_text(),
+        format!(
+            "- {}.controls[Rule-Required] removed\n~ {}.controls[Rule-Min-Length].min changed: 3 -> 4\n",
+            path, path
+        )
+    );
+}
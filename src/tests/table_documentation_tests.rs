@@ -0,0 +1,113 @@
+use crate::{
+    table_documentation::document,
+    table_locals::{LangChain, Localized},
+    table_structs::{Condition, ConditionGroup, Table},
+    table_xml_parser::TableXmlParser,
+};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn read_table() -> Table {
+    TableXmlParser::read("./src/tests/inputs/valid.xml")
+        .unwrap()
+        .table
+}
+
+#[test]
+fn markdown() {
+    let table = read_table();
+    let markdown = document(&table, &LangChain::new(&["fra"])).to_markdown();
+    assert!(markdown.starts_with("# Produits\n\nLa table produits\n\nKey: `PRODUCTS`\n\n"));
+    assert!(markdown.contains(
+        "| Prix ($) | `PRICE_DOLLAR` | Produit | `NUMBER` | prefix `$`, 2 decimals | Le prix en dollar |\n"
+    ));
+    assert!(markdown.contains("- Eléctroménager (`HOME_APPLIANCE`)\n  - Phones (`PHONES`)\n"));
+    assert!(markdown.contains("| Typologie > Phones | `STORAGE` |\n"));
+    assert!(markdown.contains(
+        "- If `WIDTH_CM` is not empty and `HEIGHT_CM` is not empty and `DEPTH_CM` is not empty, then set the number to `[WIDTH_CM] * [HEIGHT_CM] * [DEPTH_CM]` rounded ceiling to 0 decimals.\n"
+    ));
+    assert!(
+        markdown.contains("#### Invalide (RED, priority 1)\n\n- EAN 13 is a valid EAN13 barcode\n")
+    );
+    assert!(markdown.contains("| Identifiants | EAN 13 (read only, fixed), "));
+}
+
+#[test]
+fn lang_chain() {
+    let table = read_table();
+    let markdown = document(&table, &LangChain::default()).to_markdown();
+    assert!(markdown.starts_with("# Products\n\nProducts' table\n\n"));
+    assert!(markdown.contains("- Home appliance (`HOME_APPLIANCE`)\n"));
+}
+
+#[test]
+fn html() {
+    let mut table = read_table();
+    table.title = "Products <&>".to_owned();
+    let html = document(&table, &LangChain::new(&["deu"])).to_html();
+    assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"deu\">\n"));
+    assert!(html.contains("<h1>Products &lt;&amp;&gt;</h1>\n"));
+    assert!(html.contains(
+        "<ul>\n<li>Home appliance (<code>HOME_APPLIANCE</code>)\n<ul>\n<li>Phones (<code>PHONES</code>)</li>\n</ul>\n</li>\n</ul>\n"
+    ));
+    assert_eq!(
+        html.matches("<table>").count(),
+        html.matches("</table>").count()
+    );
+    assert!(html.ends_with("</body>\n</html>\n"));
+}
+
+#[test]
+fn condition_groups() {
+    let mut table = read_table();
+    table.schema.formulas[1].rules[0].conditions = vec![
+        ConditionGroup {
+            conditions: vec![
+                Condition::NotEmpty {
+                    source: "WIDTH_CM".to_owned(),
+                },
+                Condition::In {
+                    source: "PRICE_DOLLAR".to_owned(),
+                    values: vec!["1".to_owned(), "2".to_owned()],
+                },
+            ],
+        },
+        ConditionGroup {
+            conditions: vec![Condition::Empty {
+                source: "HEIGHT_CM".to_owned(),
+            }],
+        },
+    ];
+    let markdown = document(&table, &LangChain::default()).to_markdown();
+    assert!(markdown.contains(
+        "- If `WIDTH_CM` is not empty and Price ($) is one of `1`, `2`, or `HEIGHT_CM` is empty, then set the number to "
+    ));
+}
+
+#[test]
+fn lang_fallback() {
+    let mut table = read_table();
+    table.set_title_local("deu", "Produkte");
+    let markdown = document(&table, &LangChain::new(&["deu", "fra"])).to_markdown();
+    // Texts without a German local fall back to French
+    assert!(markdown.starts_with("# Produkte\n\nLa table produits\n\n"));
+    assert!(markdown.contains("- Eléctroménager (`HOME_APPLIANCE`)\n  - Phones (`PHONES`)\n"));
+}
+
+#[test]
+fn html_escaping() {
+    let mut table = read_table();
+    table.description = Some("Prices in \"$\" & <b>cents</b>".to_owned());
+    table.schema.formulas[1].rules[0].conditions = vec![ConditionGroup {
+        conditions: vec![Condition::Equals {
+            source: "WIDTH_CM".to_owned(),
+            value: "<1&2>".to_owned(),
+            use_suffix: None,
+        }],
+    }];
+    let html = document(&table, &LangChain::new(&["e\"n"])).to_html();
+    assert!(html.contains("<html lang=\"e&quot;n\">\n"));
+    assert!(html.contains("<p>Prices in &quot;$&quot; &amp; &lt;b&gt;cents&lt;/b&gt;</p>\n"));
+    assert!(html.contains("<code>WIDTH_CM</code> equals <code>&lt;1&amp;2&gt;</code>"));
+    assert!(!html.contains("<b>"));
+}
@@ -0,0 +1,140 @@
+use crate::{
+    table_effective::{effective_schema, EffectiveSchema},
+    table_matrix::{Inheritance, Source},
+    table_structs::{
+        CategoryRef, Column, CommonColumn, Control, SpecificAttributeRules, SpecificRules, Table,
+    },
+    table_xml_parser::TableXmlParser,
+};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+// valid.xml with COLOR required for PHONES in the INVALID status
+fn read_table() -> Table {
+    let mut table = TableXmlParser::read("./src/tests/inputs/valid.xml")
+        .unwrap()
+        .table;
+    table.schema.conditional_formattings[0].statuses[0]
+        .rules
+        .specifics
+        .push(SpecificRules::new(
+            "TYPOLOGY".into(),
+            "PHONES".into(),
+            vec![SpecificAttributeRules {
+                key: "COLOR".into(),
+                controls: vec![Control::RuleRequired],
+            }],
+        ));
+    table
+}
+
+fn phones(table: &Table, inheritance: Inheritance) -> EffectiveSchema {
+    effective_schema(
+        &table.schema,
+        &CategoryRef::new("TYPOLOGY".into(), "PHONES".into()),
+        inheritance,
+    )
+    .unwrap()
+}
+
+fn rules(effective: &EffectiveSchema, status: usize) -> Vec<(String, Source)> {
+    effective.conditional_formattings[0].statuses[status]
+        .rules
+        .iter()
+        .map(|rules| (rules.key.to_owned(), rules.source))
+        .collect()
+}
+
+fn sections(effective: &EffectiveSchema) -> Vec<(String, Source)> {
+    effective.screens[0]
+        .sections
+        .iter()
+        .map(|section| (section.key.to_string(), section.source))
+        .collect()
+}
+
+#[test]
+fn without_inheritance() {
+    let table = read_table();
+    let effective = phones(&table, Inheritance::None);
+    assert_eq!(
+        effective
+            .fields
+            .iter()
+            .map(|field| (field.key.to_string(), field.source))
+            .collect::<Vec<_>>(),
+        vec![
+            ("TITLE_EN".to_owned(), Source::Common),
+            ("COLOR".to_owned(), Source::Common),
+            ("STORAGE".to_owned(), Source::Specific),
+        ]
+    );
+    assert_eq!(
+        rules(&effective, 0),
+        vec![
+            ("EAN_13".to_owned(), Source::Common),
+            ("TITLE_EN".to_owned(), Source::Common),
+            ("ATTR00480".to_owned(), Source::Common),
+            ("COLOR".to_owned(), Source::Specific),
+        ]
+    );
+    assert_eq!(rules(&effective, 1), vec![]);
+    // The INFORMATIONS section is specific to HOME_APPLIANCE
+    assert_eq!(
+        sections(&effective),
+        vec![("IDENTIFIERS".to_owned(), Source::Common)]
+    );
+}
+
+#[test]
+fn with_inheritance() {
+    let table = read_table();
+    let effective = phones(&table, Inheritance::Parents);
+    assert_eq!(
+        sections(&effective),
+        vec![
+            ("IDENTIFIERS".to_owned(), Source::Common),
+            ("INFORMATIONS".to_owned(), Source::Inherited),
+        ]
+    );
+    assert_eq!(
+        effective.screens[0].sections[1].columns,
+        vec![CommonColumn::ColumnField(Column {
+            key: "INSTRUCTIONS".into(),
+            position: 1,
+            width: None,
+            read_only: None,
+            fixed: None,
+        })]
+    );
+    // The rules specific to PHONES do not apply to its parent
+    let home_appliance = effective_schema(
+        &table.schema,
+        &CategoryRef::new("TYPOLOGY".into(), "HOME_APPLIANCE".into()),
+        Inheritance::Parents,
+    )
+    .unwrap();
+    assert_eq!(rules(&home_appliance, 0).len(), 3);
+    assert_eq!(
+        sections(&home_appliance),
+        vec![
+            ("IDENTIFIERS".to_owned(), Source::Common),
+            ("INFORMATIONS".to_owned(), Source::Specific),
+        ]
+    );
+}
+
+#[test]
+fn unknown_category() {
+    let table = read_table();
+    let error = effective_schema(
+        &table.schema,
+        &CategoryRef::new("TYPOLOGY".into(), "TOYS".into()),
+        Inheritance::None,
+    )
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Unknown category 'TOYS' in classification 'TYPOLOGY'."
+    );
+}
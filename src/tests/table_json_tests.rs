@@ -0,0 +1,69 @@
+use std::{env, fs};
+
+use crate::{
+    table_json::{from_json, to_json, TableDocument, JSON_FORMAT_VERSION},
+    table_xml_parser::TableXmlParser,
+    table_xml_writer::TableXmlWriter,
+};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+const SCHEMA_PATH: &str = "./schemas/table.schema.json";
+
+#[test]
+fn json_round_trip() {
+    let table = TableXmlParser::read("./src/tests/inputs/valid.xml")
+        .unwrap()
+        .table;
+    let json = to_json(&table).unwrap();
+    let from_json_table = from_json(&json).unwrap();
+    assert_eq!(from_json_table, table);
+    let xml = TableXmlWriter::write_to_string(&from_json_table).unwrap();
+    assert_eq!(TableXmlParser::read_from_str(&xml).unwrap().table, table);
+}
+
+#[test]
+fn json_vocabulary() {
+    let table = TableXmlParser::read("./src/tests/inputs/valid.xml")
+        .unwrap()
+        .table;
+    let json: serde_json::Value = serde_json::from_str(&to_json(&table).unwrap()).unwrap();
+    assert_eq!(json["formatVersion"], JSON_FORMAT_VERSION);
+    let field = &json["table"]["schema"]["fields"][0];
+    assert_eq!(field["type"], "SINGLE-LINE-TEXT");
+    assert_eq!(field["titleLocals"][0]["lang"], "fra");
+    // Empty lists and missing values are omitted
+    assert!(field.get("options").is_none());
+    assert!(field.get("prefix").is_none());
+    let controls = &json["table"]["schema"]["conditionalFormattings"][0]["statuses"][0]["rules"]
+        ["common"][1]["controls"];
+    assert_eq!(controls[0], serde_json::json!({ "type": "Rule-Required" }));
+    assert_eq!(
+        controls[1],
+        serde_json::json!({ "type": "Rule-Min-Length", "min": 3 })
+    );
+}
+
+#[test]
+fn json_unsupported_version() {
+    let error = from_json("{\"formatVersion\": 0, \"table\": {}}").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Unsupported JSON format version 0 (expected 1).".to_owned()
+    );
+}
+
+#[test]
+fn json_schema_is_up_to_date() {
+    let schema = schemars::schema_for!(TableDocument);
+    let schema = format!("{}\n", serde_json::to_string_pretty(&schema).unwrap());
+    if env::var("UPDATE_BINDINGS").is_ok() {
+        fs::write(SCHEMA_PATH, &schema).unwrap();
+    }
+    let committed = fs::read_to_string(SCHEMA_PATH).unwrap_or_default();
+    assert!(
+        committed == schema,
+        "{} is stale, run `UPDATE_BINDINGS=1 cargo test` to update it.",
+        SCHEMA_PATH
+    );
+}
@@ -0,0 +1,183 @@
+use std::fs;
+
+use crate::{
+    table_lint::{suppressions, Finding, LintConfig, LintRule, Linter, Severity, Suppression},
+    table_structs::Table,
+    table_xml_parser::TableXmlParser,
+};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+const INPUT: &str = "./src/tests/inputs/valid.xml";
+
+fn read_table() -> Table {
+    TableXmlParser::read(INPUT).unwrap().table
+}
+
+fn rules(linter: &Linter, table: &Table, suppressions: &[Suppression]) -> Vec<(String, Severity)> {
+    linter
+        .lint(table, suppressions)
+        .unwrap()
+        .into_iter()
+        .map(|diagnostic| (diagnostic.rule, diagnostic.severity))
+        .collect()
+}
+
+#[test]
+fn config_formats() {
+    let toml = LintConfig::from_toml(
+        "[rules]\nUNTRANSLATED_LOCAL = \"off\"\nFIELD_DESCRIPTION = \"warning\"\n",
+    )
+    .unwrap();
+    let json = LintConfig::from_json(
+        r#"{ "rules": { "UNTRANSLATED_LOCAL": "off", "FIELD_DESCRIPTION": "warning" } }"#,
+    )
+    .unwrap();
+    assert_eq!(toml, json);
+    assert_eq!(
+        toml.rules.get("FIELD_DESCRIPTION"),
+        Some(&Severity::Warning)
+    );
+    assert!(LintConfig::from_toml("[rules]\nFIELD_DESCRIPTION = \"fatal\"\n").is_err());
+}
+
+#[test]
+fn default_severities() {
+    let mut table = read_table();
+    table.schema.screens[0].grid.specifics[0].sections[0].columns[0].key = "MANUAL".into();
+    let linter = Linter::new(LintConfig::default());
+    let rules = rules(&linter, &table, &[]);
+    assert!(rules.contains(&("UNTRANSLATED_LOCAL".to_owned(), Severity::Warning)));
+    assert!(rules.contains(&("UNKNOWN_KEY".to_owned(), Severity::Error)));
+    // House-style rules are off by default
+    assert!(rules
+        .iter()
+        .all(|(rule, _)| rule != "FIELD_DESCRIPTION" && rule != "UPPER_SNAKE_KEYS"));
+}
+
+#[test]
+fn configured_rules() {
+    let mut table = read_table();
+    table.schema.fields[1].description = None;
+    table.schema.fields[1].options.as_mut().unwrap()[0].key = "Red".into();
+    table.schema.screens[0].grid.specifics[0].sections[0].columns[0].key = "MANUAL".into();
+    let config = LintConfig::from_toml(
+        "[rules]\nUNTRANSLATED_LOCAL = \"off\"\nUNKNOWN_KEY = \"info\"\nUNKNOWN_SECTION = \"off\"\nFIELD_DESCRIPTION = \"warning\"\nUPPER_SNAKE_KEYS = \"error\"\n",
+    )
+    .unwrap();
+    let linter = Linter::new(config);
+    let diagnostics = linter.lint(&table, &[]).unwrap();
+    let rules = diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.rule.as_str(), diagnostic.severity))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        rules,
+        vec![
+            ("UNKNOWN_KEY", Severity::Info),
+            ("UPPER_SNAKE_KEYS", Severity::Error),
+            ("FIELD_DESCRIPTION", Severity::Warning),
+        ]
+    );
+    assert_eq!(
+        diagnostics[1].xpath,
+        "/Table/Schema/Fields/Field[@key='COLOR']/Options/Option[@key='Red']/@key"
+    );
+    assert_eq!(diagnostics[2].message, "Field 'COLOR' has no description.");
+}
+
+// A house-style rule defined outside the crate
+struct NoMetadata;
+impl LintRule for NoMetadata {
+    fn name(&self) -> &str {
+        "NO_METADATA"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+    fn check(&self, table: &Table) -> Vec<Finding> {
+        table
+            .schema
+            .levels
+            .iter()
+            .filter(|level| level.metadata.is_some())
+            .map(|level| Finding {
+                message: format!("Level '{}' has metadata.", level.key),
+                xpath: format!("/Table/Schema/Levels/Level[@key='{}']", level.key),
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn custom_rule() {
+    let table = read_table();
+    let mut linter = Linter::new(LintConfig::default());
+    linter.add_rule(Box::new(NoMetadata));
+    assert_eq!(
+        linter.rule_names(),
+        vec![
+            "UNTRANSLATED_LOCAL",
+            "UPPER_SNAKE_KEYS",
+            "FIELD_DESCRIPTION",
+            "NO_METADATA"
+        ]
+    );
+    let diagnostics = linter.lint(&table, &[]).unwrap();
+    let diagnostic = diagnostics.last().unwrap();
+    assert_eq!(
+        (diagnostic.rule.as_str(), diagnostic.severity),
+        ("NO_METADATA", Severity::Info)
+    );
+}
+
+#[test]
+fn lint_disable_comments() {
+    let xml = fs::read_to_string(INPUT)
+        .unwrap()
+        .replacen(
+            "<Screen key=\"ALL_PROPERTIES\"",
+            "<!-- lint-disable UNKNOWN_KEY, UNKNOWN_SECTION -->\n<Screen key=\"ALL_PROPERTIES\"",
+            1,
+        )
+        .replacen(
+            "<Identifier key=\"EAN_13\" index=\"1\"",
+            "<!-- lint-disable -->\n<Identifier key=\"EAN_13\" index=\"1\"",
+            1,
+        )
+        .replacen(
+            "<Section key=\"INFORMATIONS\" position=\"1\">",
+            "<Section key=\"MANUALS\" position=\"1\">",
+            1,
+        )
+        .replacen(
+            "<Column-Field key=\"INSTRUCTIONS\"",
+            "<Column-Field key=\"MANUAL\"",
+            1,
+        );
+    let suppressions = suppressions(&xml).unwrap();
+    assert_eq!(
+        suppressions,
+        vec![
+            Suppression {
+                rules: vec![],
+                xpath: "/Table/Schema/Identifiers/Identifier[@key='EAN_13']".to_owned(),
+            },
+            Suppression {
+                rules: vec!["UNKNOWN_KEY".to_owned(), "UNKNOWN_SECTION".to_owned()],
+                xpath: "/Table/Schema/Screens/Screen[@key='ALL_PROPERTIES']".to_owned(),
+            },
+        ]
+    );
+    let table = TableXmlParser::read_from_str(&xml).unwrap().table;
+    let linter = Linter::new(LintConfig::default());
+    let diagnostics = linter.lint(&table, &suppressions).unwrap();
+    // The untranslated local of EAN_13 and the unknown keys of the screen are suppressed
+    assert_eq!(
+        diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.xpath.as_str())
+            .collect::<Vec<_>>(),
+        vec!["/Table/Schema/Fields/Field[@key='CAMERA_RESOLUTION']/Suffixes/Suffix[@key='PIXEL']/Title-Local[@lang='fra']"]
+    );
+}
@@ -0,0 +1,58 @@
+use crate::{
+    table_locals::{is_three_letter_lang, LangChain, Localized},
+    table_structs::{Local, Table},
+    table_xml_parser::TableXmlParser,
+};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn read_table() -> Table {
+    TableXmlParser::read("./src/tests/inputs/valid.xml")
+        .unwrap()
+        .table
+}
+
+#[test]
+fn title_and_description_in_lang() {
+    let table = read_table();
+    let field = &table.schema.fields[1];
+    assert_eq!(field.title_in("fra"), "Couleur");
+    assert_eq!(field.title_in("deu"), "Color");
+    assert_eq!(field.description_in("fra"), Some("La couleur du produit"));
+    assert_eq!(field.description_in("deu"), Some("Product's color"));
+    assert_eq!(table.title_in("fra"), "Produits");
+    assert_eq!(field.langs(), vec!["fra"]);
+}
+
+#[test]
+fn fallback_chain() {
+    let mut table = read_table();
+    let field = &mut table.schema.fields[1];
+    field.add_title_local(Some(Local {
+        lang: "eng".to_owned(),
+        value: "Colour".to_owned(),
+    }));
+    let chain = LangChain::new(&["deu"]).then("eng").then("fra");
+    assert_eq!(chain.langs(), &["deu", "eng", "fra"]);
+    assert_eq!(field.title_with(&chain), "Colour");
+    assert_eq!(
+        field.title_with(&LangChain::new(&["deu", "fra"])),
+        "Couleur"
+    );
+    // The description has no eng local, fra comes next
+    assert_eq!(
+        field.description_with(&chain),
+        Some("La couleur du produit")
+    );
+    assert_eq!(field.title_with(&LangChain::default()), "Color");
+}
+
+#[test]
+fn lang_codes() {
+    assert!(is_three_letter_lang("fra"));
+    assert!(!is_three_letter_lang("fr"));
+    assert!(!is_three_letter_lang("FRA"));
+    assert!(!is_three_letter_lang("fr-FR"));
+    // Only the shape is checked
+    assert!(is_three_letter_lang("xyz"));
+}
@@ -0,0 +1,138 @@
+use crate::{
+    table_matrix::{
+        coverage, effective_fields, pivot, CategoryFields, EffectiveField, Source, Inheritance,
+    },
+    table_structs::{Category, CategoryRef, FieldKey, MatrixField, MatrixSpecific, Table},
+    table_xml_parser::TableXmlParser,
+};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+// valid.xml with a SMARTPHONES category under PHONES and a field specific to HOME_APPLIANCE
+fn read_table() -> Table {
+    let mut table = TableXmlParser::read("./src/tests/inputs/valid.xml")
+        .unwrap()
+        .table;
+    table.schema.classifications[0].categories.push(Category {
+        key: "SMARTPHONES".into(),
+        parent: Some("PHONES".into()),
+        title: "Smartphones".to_owned(),
+        ..Category::new()
+    });
+    table.schema.matrix.specifics.push(MatrixSpecific {
+        classification: "TYPOLOGY".into(),
+        category: "HOME_APPLIANCE".into(),
+        fields: vec![
+            MatrixField {
+                key: "PRICE_DOLLAR".into(),
+            },
+            MatrixField {
+                key: "COLOR".into(),
+            },
+        ],
+    });
+    table
+}
+
+fn field(key: &str, source: Source) -> EffectiveField {
+    EffectiveField {
+        key: key.into(),
+        source,
+    }
+}
+
+#[test]
+fn without_inheritance() {
+    let table = read_table();
+    let categories = effective_fields(&table.schema, Inheritance::None);
+    assert_eq!(
+        categories,
+        vec![
+            CategoryFields {
+                category: CategoryRef::new("TYPOLOGY".into(), "HOME_APPLIANCE".into()),
+                fields: vec![
+                    field("TITLE_EN", Source::Common),
+                    field("COLOR", Source::Common),
+                    field("PRICE_DOLLAR", Source::Specific),
+                ],
+            },
+            CategoryFields {
+                category: CategoryRef::new("TYPOLOGY".into(), "PHONES".into()),
+                fields: vec![
+                    field("TITLE_EN", Source::Common),
+                    field("COLOR", Source::Common),
+                    field("STORAGE", Source::Specific),
+                ],
+            },
+            CategoryFields {
+                category: CategoryRef::new("TYPOLOGY".into(), "SMARTPHONES".into()),
+                fields: vec![
+                    field("TITLE_EN", Source::Common),
+                    field("COLOR", Source::Common),
+                ],
+            },
+        ]
+    );
+}
+
+#[test]
+fn with_parents() {
+    let mut table = read_table();
+    // A cycle in the parents doesn't loop forever
+    table.schema.classifications[0].categories[0].parent = Some("SMARTPHONES".into());
+    let categories = effective_fields(&table.schema, Inheritance::Parents);
+    assert_eq!(
+        categories[2].fields,
+        vec![
+            field("TITLE_EN", Source::Common),
+            field("COLOR", Source::Common),
+            field("STORAGE", Source::Inherited),
+            field("PRICE_DOLLAR", Source::Inherited),
+        ]
+    );
+    assert_eq!(
+        categories[0].fields,
+        vec![
+            field("TITLE_EN", Source::Common),
+            field("COLOR", Source::Common),
+            field("PRICE_DOLLAR", Source::Specific),
+            field("STORAGE", Source::Inherited),
+        ]
+    );
+}
+
+#[test]
+fn matrix_coverage() {
+    let table = read_table();
+    let without_inheritance = coverage(&table.schema, Inheritance::None);
+    assert_eq!(
+        without_inheritance.categories_without_specific_fields,
+        vec![CategoryRef::new("TYPOLOGY".into(), "SMARTPHONES".into())]
+    );
+    assert_eq!(
+        without_inheritance.unused_fields,
+        vec![
+            FieldKey::from("STORAGE_GB"),
+            FieldKey::from("CAMERA_RESOLUTION"),
+            FieldKey::from("MAIN_IMAGE"),
+            FieldKey::from("DESCRIPTION_EN"),
+            FieldKey::from("PRICE_EURO"),
+            FieldKey::from("INSTRUCTIONS")
+        ]
+    );
+    let with_parents = coverage(&table.schema, Inheritance::Parents);
+    assert!(with_parents.categories_without_specific_fields.is_empty());
+}
+
+#[test]
+fn pivot_csv() {
+    let table = read_table();
+    assert_eq!(
+        pivot(&table.schema, Inheritance::Parents).to_csv(),
+        "\u{feff}classification,category,TITLE_EN,COLOR,STORAGE_GB,PRICE_DOLLAR,CAMERA_RESOLUTION,\
+         MAIN_IMAGE,DESCRIPTION_EN,PRICE_EURO,INSTRUCTIONS\r\n\
+         TYPOLOGY,HOME_APPLIANCE,common,common,,specific,,,,,\r\n\
+         TYPOLOGY,PHONES,common,common,,inherited,,,,,\r\n\
+         TYPOLOGY,SMARTPHONES,common,common,,inherited,,,,,\r\n"
+    );
+}
@@ -0,0 +1,175 @@
+use crate::{
+    table_merge::merge,
+    table_structs::{Column, CommonColumn, DataType, Field, SelectOption, Table},
+    table_xml_parser::TableXmlParser,
+};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn read_table() -> Table {
+    TableXmlParser::read("./src/tests/inputs/valid.xml")
+        .unwrap()
+        .table
+}
+
+fn field<'a>(table: &'a mut Table, key: &str) -> &'a mut Field {
+    table
+        .schema
+        .fields
+        .iter_mut()
+        .find(|field| field.key == key)
+        .unwrap()
+}
+
+fn field_keys(table: &Table) -> Vec<String> {
+    table
+        .schema
+        .fields
+        .iter()
+        .map(|field| field.key.to_string())
+        .collect()
+}
+
+#[test]
+fn merge_without_conflict() {
+    let base = read_table();
+    let mut ours = read_table();
+    let mut theirs = read_table();
+    field(&mut ours, "COLOR").title = "Colour".to_owned();
+    let mut blue = SelectOption::new();
+    blue.key = "BLUE".into();
+    blue.title = "Blue".to_owned();
+    field(&mut theirs, "COLOR")
+        .options
+        .as_mut()
+        .unwrap()
+        .push(blue);
+    theirs
+        .schema
+        .fields
+        .retain(|field| field.key != "STORAGE_GB");
+    let result = merge(&base, &ours, &theirs).unwrap();
+    assert!(result.is_clean());
+    let mut merged = result.table;
+    assert!(!field_keys(&merged).contains(&"STORAGE_GB".to_owned()));
+    let color = field(&mut merged, "COLOR");
+    assert_eq!(color.title, "Colour".to_owned());
+    assert_eq!(
+        color
+            .options
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|option| option.key.to_string())
+            .collect::<Vec<String>>(),
+        vec!["RED".to_owned(), "GREEN".to_owned(), "BLUE".to_owned()]
+    );
+}
+
+#[test]
+fn merge_conflicts() {
+    let base = read_table();
+    let mut ours = read_table();
+    let mut theirs = read_table();
+    field(&mut ours, "TITLE_EN").title = "Title".to_owned();
+    field(&mut theirs, "TITLE_EN").title = "English title".to_owned();
+    field(&mut ours, "COLOR").title = "Colour".to_owned();
+    theirs.schema.fields.retain(|field| field.key != "COLOR");
+    let result = merge(&base, &ours, &theirs).unwrap();
+    assert_eq!(
+        result
+            .conflicts
+            .iter()
+            .map(|conflict| conflict.path.to_owned())
+            .collect::<Vec<String>>(),
+        vec![
+            "schema.fields[TITLE_EN].title".to_owned(),
+            "schema.fields[COLOR]".to_owned(),
+        ]
+    );
+    assert_eq!(
+        result.conflicts[0].theirs,
+        Some(serde_json::json!("English title"))
+    );
+    assert_eq!(result.conflicts[1].theirs, None);
+    // Our side is kept until the conflicts are resolved
+    assert_eq!(result.table, ours);
+}
+
+#[test]
+fn merge_ordered_lists() {
+    let base = read_table();
+    let mut ours = read_table();
+    let mut theirs = read_table();
+    ours.schema.fields.swap(0, 1);
+    let size = Field::new("SIZE".into(), "PRODUCT".into(), DataType::SingleLineText);
+    theirs.schema.fields.insert(2, size);
+    let result = merge(&base, &ours, &theirs).unwrap();
+    assert!(result.is_clean());
+    // The added field follows the field it was inserted after
+    assert_eq!(
+        field_keys(&result.table)[..3].to_vec(),
+        vec!["COLOR".to_owned(), "SIZE".to_owned(), "TITLE_EN".to_owned()]
+    );
+}
+
+#[test]
+fn merge_divergent_reorders() {
+    let base = read_table();
+    let mut ours = read_table();
+    let mut theirs = read_table();
+    ours.schema.fields.swap(0, 1);
+    theirs.schema.fields.swap(1, 2);
+    let result = merge(&base, &ours, &theirs).unwrap();
+    assert_eq!(result.conflicts.len(), 1);
+    let conflict = &result.conflicts[0];
+    assert_eq!(conflict.path, "schema.fields");
+    assert_eq!(
+        conflict.ours,
+        Some(serde_json::to_value(field_keys(&ours)).unwrap())
+    );
+    assert_eq!(
+        conflict.theirs,
+        Some(serde_json::to_value(field_keys(&theirs)).unwrap())
+    );
+    assert_eq!(field_keys(&result.table), field_keys(&ours));
+}
+
+fn column_field(key: &str, position: u32) -> CommonColumn {
+    CommonColumn::ColumnField(Column {
+        key: key.into(),
+        position,
+        width: None,
+        read_only: None,
+        fixed: None,
+    })
+}
+
+#[test]
+fn merge_columns_inserted_at_same_position() {
+    let base = read_table();
+    let mut ours = read_table();
+    let mut theirs = read_table();
+    ours.schema.screens[0].grid.common[0]
+        .columns
+        .push(column_field("COLOR", 8));
+    theirs.schema.screens[0].grid.common[0]
+        .columns
+        .push(column_field("STORAGE_GB", 8));
+    let result = merge(&base, &ours, &theirs).unwrap();
+    assert!(result.is_clean());
+    // The columns keep the order of the merged list and are renumbered
+    let positions = result.table.schema.screens[0].grid.common[0]
+        .columns
+        .iter()
+        .map(|column| (column.key().to_owned(), column.position()))
+        .collect::<Vec<(String, u32)>>();
+    assert_eq!(
+        positions[6..].to_vec(),
+        vec![
+            ("PRICE_EURO".to_owned(), 7),
+            ("STORAGE_GB".to_owned(), 8),
+            ("COLOR".to_owned(), 9)
+        ]
+    );
+}
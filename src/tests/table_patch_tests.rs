@@ -0,0 +1,156 @@
+use serde_json::json;
+
+use crate::{
+    table_patch::{Patch, PatchOperation},
+    table_structs::Table,
+    table_xml_parser::TableXmlParser,
+};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn read_table() -> Table {
+    TableXmlParser::read("./src/tests/inputs/valid.xml")
+        .unwrap()
+        .table
+}
+
+fn edit_patch(table: &Table) -> Patch {
+    let storage = serde_json::to_value(&table.schema.fields[2]).unwrap();
+    let mut patch = Patch::new();
+    patch.add_operation(PatchOperation::Replace {
+        path: "/schema/fields[COLOR]/options[RED]/title".to_owned(),
+        old_value: json!("Red"),
+        value: json!("Dark red"),
+    });
+    patch.add_operation(PatchOperation::Add {
+        path: "/schema/fields[COLOR]/options[BLUE]".to_owned(),
+        value: json!({ "key": "BLUE", "title": "Blue", "color": "BLUE" }),
+        index: Some(0),
+    });
+    patch.add_operation(PatchOperation::Replace {
+        path: "/schema/fields[TITLE_EN]/titleLocals[fra]/value".to_owned(),
+        old_value: json!("Titre EN"),
+        value: json!("Titre anglais"),
+    });
+    patch.add_operation(PatchOperation::Remove {
+        path: "/schema/fields[STORAGE_GB]".to_owned(),
+        old_value: storage,
+        index: Some(2),
+    });
+    patch
+}
+
+#[test]
+fn apply_patch() {
+    let mut table = read_table();
+    edit_patch(&table).apply(&mut table).unwrap();
+    let color = &table.schema.fields[1];
+    let options = color.options.as_ref().unwrap();
+    assert_eq!(options[0].key, "BLUE".to_owned());
+    assert_eq!(options[1].title, "Dark red".to_owned());
+    assert_eq!(
+        table.schema.fields[0].title_locals.as_ref().unwrap()[0].value,
+        "Titre anglais".to_owned()
+    );
+    assert!(table
+        .schema
+        .fields
+        .iter()
+        .all(|field| field.key != "STORAGE_GB"));
+}
+
+#[test]
+fn invert_patch() {
+    let original = read_table();
+    let mut table = read_table();
+    let patch = edit_patch(&table);
+    patch.apply(&mut table).unwrap();
+    patch.invert().apply(&mut table).unwrap();
+    assert_eq!(table, original);
+}
+
+#[test]
+fn invert_applied_removal() {
+    let original = read_table();
+    let mut table = read_table();
+    let rule = serde_json::to_value(&table.schema.formulas[2].rules[0]).unwrap();
+    let column = serde_json::to_value(&table.schema.screens[0].grid.common[0].columns[1]).unwrap();
+    let mut patch = Patch::new();
+    patch.add_operation(PatchOperation::Remove {
+        path: "/schema/formulas[DEEE_SCALE]/rules[1]".to_owned(),
+        old_value: rule,
+        index: None,
+    });
+    patch.add_operation(PatchOperation::Remove {
+        path: "/schema/screens[ALL_PROPERTIES]/grid/common[IDENTIFIERS]/columns[MAIN_IMAGE]"
+            .to_owned(),
+        old_value: column,
+        index: None,
+    });
+    let applied = patch.apply(&mut table).unwrap();
+    let indexes = applied
+        .operations
+        .iter()
+        .map(|operation| match operation {
+            PatchOperation::Remove { index, .. } => *index,
+            _ => None,
+        })
+        .collect::<Vec<Option<usize>>>();
+    assert_eq!(indexes, vec![Some(0), Some(1)]);
+    // The removed rule and column are put back in place, not appended
+    applied.invert().apply(&mut table).unwrap();
+    assert_eq!(table, original);
+    // A removal recorded at another position is refused
+    let mut moved = read_table();
+    moved.schema.formulas[2].rules.reverse();
+    let error = applied.apply(&mut moved).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Can't apply patch on '/schema/formulas[DEEE_SCALE]/rules[1]': 'rules[1]' is not at the expected position."
+    );
+}
+
+#[test]
+fn compose_patches() {
+    let mut patch = Patch::new();
+    patch.add_operation(PatchOperation::Replace {
+        path: "/title".to_owned(),
+        old_value: json!("Products"),
+        value: json!("Product"),
+    });
+    let mut next = Patch::new();
+    next.add_operation(PatchOperation::Replace {
+        path: "/title".to_owned(),
+        old_value: json!("Product"),
+        value: json!("Product list"),
+    });
+    let composed = patch.compose(&next);
+    assert_eq!(
+        composed.operations,
+        vec![PatchOperation::Replace {
+            path: "/title".to_owned(),
+            old_value: json!("Products"),
+            value: json!("Product list"),
+        }]
+    );
+}
+
+#[test]
+fn serialize_patch() {
+    let patch = edit_patch(&read_table());
+    let json = serde_json::to_string(&patch).unwrap();
+    assert!(json.starts_with("{\"operations\":[{\"op\":\"replace\",\"path\":\"/schema/fields[COLOR]/options[RED]/title\""));
+    assert_eq!(serde_json::from_str::<Patch>(&json).unwrap(), patch);
+}
+
+#[test]
+fn apply_stale_patch() {
+    let mut table = read_table();
+    let patch = edit_patch(&table);
+    patch.apply(&mut table).unwrap();
+    let error = patch.apply(&mut table).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Can't apply patch on '/schema/fields[COLOR]/options[RED]/title': the current value is not the expected old value.".to_owned()
+    );
+}
@@ -0,0 +1,200 @@
+use serde_json::json;
+
+use crate::{
+    table_record_schema::{record_schema, StatusRef},
+    table_structs::{
+        AttributeType, CommonAttributeRules, Control, SpecificAttributeRules, SpecificRules, Table,
+    },
+    table_xml_parser::TableXmlParser,
+};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn read_table() -> Table {
+    TableXmlParser::read("./src/tests/inputs/valid.xml")
+        .unwrap()
+        .table
+}
+
+#[test]
+fn record_schema_of_level() {
+    let table = read_table();
+    let schema = record_schema(&table, &"PRODUCT".into(), None).unwrap();
+    assert_eq!(
+        schema,
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "Products - Product",
+            "type": "object",
+            "properties": {
+                "EAN_13": {
+                    "title": "EAN 13",
+                    "description": "EAN13 code",
+                    "type": "string",
+                    "pattern": "^[0-9]{13}$"
+                },
+                "TYPOLOGY": {
+                    "title": "Typology",
+                    "description": "Product typology",
+                    "type": "string",
+                    "enum": ["HOME_APPLIANCE", "PHONES"]
+                },
+                "TITLE_EN": {
+                    "title": "Title EN",
+                    "description": "Product's title in english",
+                    "type": "string",
+                    "minLength": 3,
+                    "maxLength": 27
+                },
+                "COLOR": {
+                    "title": "Color",
+                    "description": "Product's color",
+                    "type": "string",
+                    "enum": ["RED", "GREEN"]
+                },
+                "STORAGE_GB": {
+                    "title": "Storage GB",
+                    "description": "Storage capacity",
+                    "type": "number"
+                },
+                "PRICE_DOLLAR": {
+                    "title": "Price ($)",
+                    "description": "Price in dollar",
+                    "type": "number",
+                    "$comment": "Maximum number of decimal places: 2."
+                },
+                "CAMERA_RESOLUTION": {
+                    "title": "Camera resolution",
+                    "description": "The resolution of an image is the number of pixels contained in the image per unit length.",
+                    "type": "number"
+                },
+                "MAIN_IMAGE": {
+                    "title": "Main image",
+                    "description": "Product's main image",
+                    "type": "string",
+                    "format": "uri"
+                },
+                "DESCRIPTION_EN": {
+                    "title": "Description EN",
+                    "description": "Product's description in english",
+                    "type": "string"
+                },
+                "PRICE_EURO": {
+                    "title": "Price (€)",
+                    "description": "Price in euro",
+                    "type": "number",
+                    "$comment": "Maximum number of decimal places: 2."
+                },
+                "INSTRUCTIONS": {
+                    "title": "Instructions",
+                    "description": "Instructions for use",
+                    "type": "string"
+                }
+            },
+            "required": ["EAN_13", "TITLE_EN"],
+            "additionalProperties": false
+        })
+    );
+}
+
+#[test]
+fn record_schema_controls() {
+    let mut table = read_table();
+    let rules = &mut table.schema.conditional_formattings[0].statuses[0].rules;
+    rules.common.push(CommonAttributeRules {
+        attribute_type: AttributeType::Classification,
+        key: "TYPOLOGY".to_owned(),
+        controls: vec![Control::RuleRequired, Control::RuleIsLeaf],
+    });
+    rules.common.push(CommonAttributeRules {
+        attribute_type: AttributeType::Field,
+        key: "PRICE_DOLLAR".to_owned(),
+        controls: vec![
+            Control::RuleGreaterThanOrEqual { value: 1 },
+            Control::RuleDecimalPlaces { precision: 1 },
+            Control::RuleRequiredIfAnotherFieldIsNotEmpty {
+                field: "STORAGE_GB".into(),
+            },
+        ],
+    });
+    rules.specifics.push(SpecificRules::new(
+        "TYPOLOGY".into(),
+        "HOME_APPLIANCE".into(),
+        vec![SpecificAttributeRules {
+            key: "STORAGE_GB".into(),
+            controls: vec![Control::RuleRequired, Control::RuleLessThan { value: 1024 }],
+        }],
+    ));
+    let schema = record_schema(&table, &"PRODUCT".into(), None).unwrap();
+    assert_eq!(schema["properties"]["TYPOLOGY"]["enum"], json!(["PHONES"]));
+    assert_eq!(schema["properties"]["PRICE_DOLLAR"]["minimum"], json!(1));
+    assert_eq!(
+        schema["properties"]["PRICE_DOLLAR"]["$comment"],
+        json!("Maximum number of decimal places: 1.")
+    );
+    assert_eq!(schema["properties"]["PRICE_DOLLAR"].get("multipleOf"), None);
+    assert_eq!(
+        schema["required"],
+        json!(["EAN_13", "TITLE_EN", "TYPOLOGY"])
+    );
+    assert_eq!(
+        schema["dependentRequired"],
+        json!({ "STORAGE_GB": ["PRICE_DOLLAR"] })
+    );
+    assert_eq!(
+        schema["allOf"],
+        json!([{
+            "if": {
+                "properties": { "TYPOLOGY": { "enum": ["HOME_APPLIANCE", "PHONES"] } },
+                "required": ["TYPOLOGY"]
+            },
+            "then": {
+                "properties": { "STORAGE_GB": { "exclusiveMaximum": 1024 } },
+                "required": ["STORAGE_GB"]
+            }
+        }])
+    );
+}
+
+#[test]
+fn record_schema_of_status() {
+    let mut table = read_table();
+    // RECOMMENDED (priority 2) is a softer alternative to INVALID (priority 1)
+    table.schema.conditional_formattings[0].statuses[1]
+        .rules
+        .common
+        .push(CommonAttributeRules {
+            attribute_type: AttributeType::Field,
+            key: "PRICE_DOLLAR".to_owned(),
+            controls: vec![
+                Control::RuleRequired,
+                Control::RuleGreaterThanOrEqual { value: 5 },
+            ],
+        });
+    // By default the status with the highest priority is enforced
+    let schema = record_schema(&table, &"PRODUCT".into(), None).unwrap();
+    assert_eq!(schema["properties"]["PRICE_DOLLAR"].get("minimum"), None);
+    assert_eq!(schema["required"], json!(["EAN_13", "TITLE_EN"]));
+    let recommended = StatusRef::new("COMPLIANCE".into(), "RECOMMENDED");
+    let schema = record_schema(&table, &"PRODUCT".into(), Some(&recommended)).unwrap();
+    assert_eq!(schema["properties"]["PRICE_DOLLAR"]["minimum"], json!(5));
+    assert_eq!(schema["properties"]["TITLE_EN"].get("minLength"), None);
+    assert_eq!(schema["required"], json!(["EAN_13", "PRICE_DOLLAR"]));
+}
+
+#[test]
+fn record_schema_unknown_level() {
+    let table = read_table();
+    let error = record_schema(&table, &"VARIANT".into(), None).unwrap_err();
+    assert_eq!(error.to_string(), "Unknown level 'VARIANT'.".to_owned());
+    let error = record_schema(
+        &table,
+        &"PRODUCT".into(),
+        Some(&StatusRef::new("COMPLIANCE".into(), "BLOCKED")),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Unknown status 'BLOCKED' in conditional formatting 'COMPLIANCE'.".to_owned()
+    );
+}
@@ -0,0 +1,304 @@
+use crate::{
+    table_refactoring::{DeleteImpact, DeleteMode, KeyChange, KeyKind},
+    table_structs::{Action, CommonColumn, Condition, Control, Table},
+    table_xml_parser::TableXmlParser,
+};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn read_table() -> Table {
+    TableXmlParser::read("./src/tests/inputs/valid.xml")
+        .unwrap()
+        .table
+}
+
+#[test]
+fn rename_field() {
+    let mut table = read_table();
+    let changes = table
+        .rename_key(KeyKind::Field, "TITLE_EN", "TITLE_ENG")
+        .unwrap();
+    assert_eq!(
+        changes.iter().map(|change| change.xpath.to_owned()).collect::<Vec<String>>(),
+        vec![
+            "/Table/Schema/Fields/Field[@key='TITLE_EN']/@key".to_owned(),
+            "/Table/Schema/Matrix/Common/Field[@key='TITLE_EN']/@key".to_owned(),
+            "/Table/Schema/Conditional-Formattings/Conditional-Formatting[@key='COMPLIANCE']/Statuses/Status[@key='INVALID']/Rules/Common/Field[@key='TITLE_EN']/@key".to_owned(),
+            "/Table/Schema/Screens/Screen[@key='ALL_PROPERTIES']/Grid/Common/Section[@key='IDENTIFIERS']/Column-Field[@key='TITLE_EN']/@key".to_owned(),
+        ]
+    );
+    assert_eq!(table.schema.fields[0].key, "TITLE_ENG".to_owned());
+    assert_eq!(table.schema.matrix.common[0].key, "TITLE_ENG".to_owned());
+    match &table.schema.screens[0].grid.common[0].columns[4] {
+        CommonColumn::ColumnField(column) => assert_eq!(column.key, "TITLE_ENG".to_owned()),
+        _ => panic!("expected a Column-Field"),
+    }
+}
+
+#[test]
+fn rename_condition_source_and_template() {
+    let mut table = read_table();
+    table.schema.identifiers[0].key = "SUPPLIER".into();
+    let changes = table
+        .rename_key(KeyKind::Identifier, "SUPPLIER", "VENDOR")
+        .unwrap();
+    assert!(changes.contains(&KeyChange {
+        xpath: "/Table/Schema/Formulas/Identifier[@key='SUPPLIER-SUPPLIER_REF']/Rule[@priority='1']/Action/Template".to_owned(),
+        old_value: "{{source(\"SUPPLIER\",\"key\")}}-{{source(\"SUPPLIER_REF\")}}".to_owned(),
+        new_value: "{{source(\"VENDOR\",\"key\")}}-{{source(\"SUPPLIER_REF\")}}".to_owned(),
+    }));
+    assert_eq!(
+        table.schema.formulas[2].rules[0].conditions[0].conditions[0],
+        Condition::In {
+            source: "VENDOR".to_owned(),
+            values: vec!["SUPPLIER-1".to_owned(), "SUPPLIER-2".to_owned()],
+        }
+    );
+}
+
+#[test]
+fn rename_shared_source_key() {
+    let mut table = read_table();
+    table.schema.identifiers[0].key = "SUPPLIER".into();
+    table.schema.fields[0].key = "SUPPLIER".into();
+    // The sources resolve to the field, renaming the identifier leaves them unchanged
+    let mut renamed = table.clone();
+    let changes = renamed
+        .rename_key(KeyKind::Identifier, "SUPPLIER", "VENDOR")
+        .unwrap();
+    assert!(changes
+        .iter()
+        .all(|change| !change.xpath.starts_with("/Table/Schema/Formulas/")));
+    assert_eq!(renamed.schema.formulas, table.schema.formulas);
+    assert_eq!(renamed.schema.identifiers[0].key, "VENDOR".to_owned());
+    let changes = table
+        .rename_key(KeyKind::Field, "SUPPLIER", "VENDOR")
+        .unwrap();
+    assert!(changes.iter().any(|change| change.xpath
+        == "/Table/Schema/Formulas/Identifier[@key='SUPPLIER-SUPPLIER_REF']/Rule[@priority='1']/Action/Template"));
+    assert_eq!(
+        table.schema.formulas[2].rules[0].conditions[0].conditions[0].source(),
+        "VENDOR"
+    );
+}
+
+#[test]
+fn rename_level() {
+    let mut table = read_table();
+    let changes = table.rename_key(KeyKind::Level, "PRODUCT", "ITEM").unwrap();
+    assert_eq!(changes.len(), 13);
+    assert!(table
+        .schema
+        .fields
+        .iter()
+        .all(|field| field.level == "ITEM"));
+    assert_eq!(table.schema.screens[0].level, "ITEM".to_owned());
+}
+
+#[test]
+fn rename_category() {
+    let mut table = read_table();
+    let changes = table
+        .rename_key(
+            KeyKind::Category {
+                classification: "TYPOLOGY".into(),
+            },
+            "HOME_APPLIANCE",
+            "APPLIANCES",
+        )
+        .unwrap();
+    assert_eq!(changes.len(), 3);
+    let categories = &table.schema.classifications[0].categories;
+    assert_eq!(categories[0].key, "APPLIANCES".to_owned());
+    assert_eq!(categories[1].parent, Some("APPLIANCES".into()));
+    assert_eq!(
+        table.schema.screens[0].grid.specifics[0].category,
+        "APPLIANCES".to_owned()
+    );
+}
+
+#[test]
+fn rename_option() {
+    let mut table = read_table();
+    table.schema.formulas[2].key = "COLOR".to_owned();
+    table.schema.formulas[2].rules[0].action = Action::SetSelectableOptions {
+        values: vec!["RED".to_owned(), "GREEN".to_owned()],
+    };
+    let changes = table
+        .rename_key(
+            KeyKind::Option {
+                field: "COLOR".into(),
+            },
+            "RED",
+            "DARK_RED",
+        )
+        .unwrap();
+    assert_eq!(changes.len(), 2);
+    assert_eq!(
+        table.schema.formulas[2].rules[0].action,
+        Action::SetSelectableOptions {
+            values: vec!["DARK_RED".to_owned(), "GREEN".to_owned()],
+        }
+    );
+}
+
+#[test]
+fn rename_to_existing_key() {
+    let mut table = read_table();
+    assert!(table
+        .rename_key(KeyKind::Field, "TITLE_EN", "COLOR")
+        .is_err());
+    assert!(table
+        .rename_key(KeyKind::Field, "MISSING", "OTHER")
+        .is_err());
+    assert_eq!(table, read_table());
+}
+
+#[test]
+fn delete_impact_of_field() {
+    let table = read_table();
+    let impacts = table.delete_impact(&KeyKind::Field, "TITLE_EN").unwrap();
+    assert_eq!(
+        impacts.iter().map(|impact| impact.xpath.to_owned()).collect::<Vec<String>>(),
+        vec![
+            "/Table/Schema/Matrix/Common/Field[@key='TITLE_EN']".to_owned(),
+            "/Table/Schema/Conditional-Formattings/Conditional-Formatting[@key='COMPLIANCE']/Statuses/Status[@key='INVALID']/Rules/Common/Field[@key='TITLE_EN']".to_owned(),
+            "/Table/Schema/Screens/Screen[@key='ALL_PROPERTIES']/Grid/Common/Section[@key='IDENTIFIERS']/Column-Field[@key='TITLE_EN']".to_owned(),
+        ]
+    );
+    assert_eq!(impacts[0].reason, "Field 'TITLE_EN' is deleted.".to_owned());
+    assert_eq!(table, read_table());
+}
+
+#[test]
+fn delete_refused_when_referenced() {
+    let mut table = read_table();
+    match table.delete_key(KeyKind::Field, "TITLE_EN", DeleteMode::Refuse) {
+        Ok(_) => panic!("expected the deletion to be refused"),
+        Err(error) => assert_eq!(
+            error.to_string(),
+            "Field 'TITLE_EN' can't be deleted, 3 element(s) depend on it.".to_owned()
+        ),
+    }
+    assert_eq!(table, read_table());
+    assert!(table
+        .delete_key(KeyKind::Field, "UNKNOWN", DeleteMode::Cascade)
+        .is_err());
+}
+
+#[test]
+fn delete_cascade() {
+    let mut table = read_table();
+    table
+        .delete_key(KeyKind::Field, "TITLE_EN", DeleteMode::Cascade)
+        .unwrap();
+    assert!(!table.contains_key(&KeyKind::Field, "TITLE_EN"));
+    assert!(table
+        .schema
+        .matrix
+        .common
+        .iter()
+        .all(|field| field.key != "TITLE_EN"));
+    assert!(table.schema.screens[0].grid.common[0]
+        .columns
+        .iter()
+        .all(|column| column.key() != "TITLE_EN"));
+    assert!(table.delete_impact(&KeyKind::Field, "COLOR").is_ok());
+}
+
+#[test]
+fn delete_level_cascade() {
+    let mut table = read_table();
+    let impacts = table
+        .delete_key(KeyKind::Level, "PRODUCT", DeleteMode::Cascade)
+        .unwrap();
+    assert_eq!(impacts.len(), 14);
+    assert!(table
+        .schema
+        .identifiers
+        .iter()
+        .all(|identifier| identifier.level != "PRODUCT"));
+    assert!(table
+        .schema
+        .fields
+        .iter()
+        .all(|field| field.level != "PRODUCT"));
+    assert!(table.schema.screens.is_empty());
+    assert!(table.schema.conditional_formattings.is_empty());
+}
+
+fn with_field(key: &str) -> Table {
+    let mut table = read_table();
+    let mut field = table.schema.fields[0].clone();
+    field.key = key.into();
+    table.schema.fields.push(field);
+    table
+}
+
+#[test]
+fn delete_cascade_removes_condition_groups() {
+    let mut table = with_field("ATTR00460");
+    let impacts = table
+        .delete_key(KeyKind::Field, "ATTR00460", DeleteMode::Cascade)
+        .unwrap();
+    let attribute = "/Table/Schema/Conditional-Formattings/Conditional-Formatting[@key='COMPLIANCE']/Statuses/Status[@key='INVALID']/Rules/Common/Field[@key='ATTR00480']";
+    assert_eq!(
+        impacts,
+        vec![
+            DeleteImpact {
+                xpath: format!(
+                    "{}/Rule-Condition[@key='ATTR00480_NOT_EA']/Conditions/Condition-Group[1]",
+                    attribute
+                ),
+                reason: "Field 'ATTR00460' is deleted.".to_owned(),
+            },
+            DeleteImpact {
+                xpath: format!("{}/Rule-Condition[@key='ATTR00480_NOT_EA']", attribute),
+                reason: "Field 'ATTR00460' is deleted.".to_owned(),
+            },
+        ]
+    );
+    // The group is not narrowed to its other conditions, the Rule-Condition is removed
+    assert_eq!(
+        table.schema.conditional_formattings[0].statuses[0]
+            .rules
+            .common[2]
+            .controls,
+        vec![Control::RuleRequired]
+    );
+    let mut expected = read_table();
+    expected.schema.conditional_formattings[0].statuses[0]
+        .rules
+        .common[2]
+        .controls
+        .truncate(1);
+    assert_eq!(table, expected);
+}
+
+#[test]
+fn delete_cascade_removes_emptied_formulas() {
+    let mut table = with_field("WIDTH_CM");
+    let impacts = table
+        .delete_key(KeyKind::Field, "WIDTH_CM", DeleteMode::Cascade)
+        .unwrap();
+    assert_eq!(
+        impacts,
+        vec![
+            DeleteImpact {
+                xpath: "/Table/Schema/Formulas/Field[@key='VOLUME_CM_3']/Rule[@priority='1']"
+                    .to_owned(),
+                reason: "Field 'WIDTH_CM' is deleted.".to_owned(),
+            },
+            DeleteImpact {
+                xpath: "/Table/Schema/Formulas/Field[@key='VOLUME_CM_3']".to_owned(),
+                reason: "Field 'WIDTH_CM' is deleted.".to_owned(),
+            },
+        ]
+    );
+    let mut expected = read_table();
+    expected
+        .schema
+        .formulas
+        .retain(|formula| formula.key != "VOLUME_CM_3");
+    assert_eq!(table, expected);
+}
@@ -0,0 +1,106 @@
+use crate::{
+    table_screens::{default_screens, ScreenOptions},
+    table_structs::{ColumnWidth, CommonColumn, LineHeight, MatrixField, Table},
+    table_validation::TableValidation,
+    table_xml_parser::TableXmlParser,
+};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+// valid.xml with CAMERA_RESOLUTION specific to PHONES in the matrix
+fn read_table() -> Table {
+    let mut table = TableXmlParser::read("./src/tests/inputs/valid.xml")
+        .unwrap()
+        .table;
+    table.schema.matrix.specifics[0].fields.push(MatrixField {
+        key: "CAMERA_RESOLUTION".into(),
+    });
+    table
+}
+
+#[test]
+fn one_screen_per_level() {
+    let table = read_table();
+    let options = ScreenOptions {
+        line_height: LineHeight::Short,
+        field_width: Some(ColumnWidth::Small),
+        ..ScreenOptions::default()
+    };
+    let screens = default_screens(&table.schema, &options);
+    assert_eq!(screens.len(), 1);
+    let screen = &screens[0];
+    assert_eq!(
+        (screen.key.as_str(), screen.level.as_str(), screen.position),
+        ("ALL_PROPERTIES_PRODUCT", "PRODUCT", 1)
+    );
+    assert_eq!(screen.grid.line_height, LineHeight::Short);
+    let columns = screen.grid.common[0]
+        .columns
+        .iter()
+        .map(|column| (column.element_name(), column.key(), column.position()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        columns,
+        vec![
+            ("Column-Identifier", "EAN_13", 1),
+            ("Column-Classification", "TYPOLOGY", 2),
+            ("Column-Conditional-Formatting", "COMPLIANCE", 3),
+            ("Column-Field", "TITLE_EN", 4),
+            ("Column-Field", "COLOR", 5),
+            ("Column-Field", "STORAGE_GB", 6),
+            ("Column-Field", "PRICE_DOLLAR", 7),
+            ("Column-Field", "MAIN_IMAGE", 8),
+            ("Column-Field", "DESCRIPTION_EN", 9),
+            ("Column-Field", "PRICE_EURO", 10),
+            ("Column-Field", "INSTRUCTIONS", 11),
+        ]
+    );
+    match &screen.grid.common[0].columns[0] {
+        CommonColumn::ColumnIdentifier(column) => {
+            assert_eq!((column.fixed, &column.width), (Some(true), &None))
+        }
+        _ => panic!("expected an identifier column"),
+    }
+    let specific = &screen.grid.specifics[0];
+    assert_eq!(
+        (specific.classification.as_str(), specific.category.as_str()),
+        ("TYPOLOGY", "PHONES")
+    );
+    let column = &specific.sections[0].columns[0];
+    assert_eq!(
+        (column.key.as_str(), column.position, &column.width),
+        ("CAMERA_RESOLUTION", 1, &Some(ColumnWidth::Small))
+    );
+}
+
+#[test]
+fn add_default_screens() {
+    let mut table = read_table();
+    table.schema.screens.clear();
+    let added = table.add_default_screens(&ScreenOptions::default());
+    assert_eq!(added, vec!["ALL_PROPERTIES_PRODUCT".to_owned()]);
+    assert_eq!(
+        table
+            .schema
+            .sections
+            .iter()
+            .map(|section| section.key.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "IDENTIFIERS",
+            "INFORMATIONS",
+            "PROPERTIES",
+            "SPECIFIC_PROPERTIES"
+        ]
+    );
+    // The generated screens only reference existing keys and are well numbered
+    let mut table_validation = TableValidation::new();
+    table_validation.validate_screens(&table);
+    assert_eq!(table_validation.errors.len(), 0);
+    // Screens already present are kept
+    assert_eq!(
+        table.add_default_screens(&ScreenOptions::default()),
+        Vec::<String>::new()
+    );
+    assert_eq!(table.schema.screens.len(), 1);
+}
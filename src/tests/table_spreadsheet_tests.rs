@@ -0,0 +1,279 @@
+use std::io::{Cursor, Write};
+
+use zip::{write::SimpleFileOptions, ZipWriter};
+
+use crate::{
+    table_diff::ChangeKind,
+    table_locals::Localized,
+    table_spreadsheet::{from_sheets, from_xlsx, to_sheets, to_xlsx, Sheet, FIELDS, OPTIONS},
+    table_structs::{DataType, Table},
+    table_xml_parser::TableXmlParser,
+};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn read_table() -> Table {
+    TableXmlParser::read("./src/tests/inputs/valid.xml")
+        .unwrap()
+        .table
+}
+
+#[test]
+fn csv_round_trip() {
+    let mut table = read_table();
+    let sheets = to_sheets(&table);
+    assert_eq!(
+        sheets[0].rows[0],
+        vec![
+            "key",
+            "level",
+            "type",
+            "title",
+            "description",
+            "prefix",
+            "suffix",
+            "precision",
+            "title:fra",
+            "description:fra",
+            "metadata:SYSTEM"
+        ]
+    );
+    let parsed = sheets
+        .iter()
+        .map(|sheet| Sheet::from_csv(&sheet.name, &sheet.to_csv()).unwrap())
+        .collect::<Vec<Sheet>>();
+    assert_eq!(parsed, sheets);
+    let schema_diff = from_sheets(&mut table, &parsed).unwrap();
+    assert!(schema_diff.is_empty());
+    assert_eq!(table, read_table());
+}
+
+#[test]
+fn csv_quoting() {
+    let sheet = Sheet {
+        name: FIELDS.to_owned(),
+        rows: vec![vec![
+            "a,b".to_owned(),
+            "say \"hi\"".to_owned(),
+            "two\nlines".to_owned(),
+            "".to_owned(),
+        ]],
+    };
+    let csv = sheet.to_csv();
+    assert_eq!(
+        csv,
+        "\u{feff}\"a,b\",\"say \"\"hi\"\"\",\"two\nlines\",\r\n"
+    );
+    assert_eq!(Sheet::from_csv(FIELDS, &csv).unwrap(), sheet);
+    assert!(Sheet::from_csv(FIELDS, "\"unterminated").is_err());
+}
+
+#[test]
+fn xlsx_round_trip() {
+    let mut table = read_table();
+    let sheets = to_sheets(&table);
+    let parsed = from_xlsx(&to_xlsx(&sheets).unwrap()).unwrap();
+    // Empty cells at the end of a row are not written
+    for (parsed, sheet) in parsed.iter().zip(sheets.iter()) {
+        assert_eq!(parsed.name, sheet.name);
+        for (parsed_row, row) in parsed.rows.iter().zip(sheet.rows.iter()) {
+            assert_eq!(parsed_row[..], row[..parsed_row.len()]);
+        }
+    }
+    assert!(from_sheets(&mut table, &parsed).unwrap().is_empty());
+}
+
+#[test]
+fn xlsx_with_shared_strings() {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    let parts = [
+        ("xl/workbook.xml", "<workbook xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\"><sheets><sheet name=\"Fields\" sheetId=\"1\" r:id=\"rId3\"/></sheets></workbook>"),
+        ("xl/_rels/workbook.xml.rels", "<Relationships><Relationship Id=\"rId3\" Target=\"/xl/worksheets/data.xml\"/></Relationships>"),
+        ("xl/sharedStrings.xml", "<sst><si><t>key</t></si><si><t>precision</t></si><si><r><t>PRICE_</t></r><r><t>DOLLAR</t></r></si></sst>"),
+        ("xl/worksheets/data.xml", "<worksheet><sheetData><row r=\"1\"><c r=\"A1\" t=\"s\"><v>0</v></c><c r=\"C1\" t=\"s\"><v>1</v></c></row><row r=\"3\"><c r=\"A3\" t=\"s\"><v>2</v></c><c r=\"C3\"><v>3</v></c></row></sheetData></worksheet>"),
+    ];
+    for (name, content) in parts {
+        zip.start_file(name, options).unwrap();
+        zip.write_all(content.as_bytes()).unwrap();
+    }
+    let sheets = from_xlsx(&zip.finish().unwrap().into_inner()).unwrap();
+    assert_eq!(
+        sheets,
+        vec![Sheet {
+            name: "Fields".to_owned(),
+            rows: vec![
+                vec!["key".to_owned(), "".to_owned(), "precision".to_owned()],
+                vec![],
+                vec!["PRICE_DOLLAR".to_owned(), "".to_owned(), "3".to_owned()],
+            ],
+        }]
+    );
+    let mut table = read_table();
+    let schema_diff = from_sheets(&mut table, &sheets).unwrap();
+    assert_eq!(
+        schema_diff
+            .changes
+            .iter()
+            .map(|change| change.path.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["schema.fields[PRICE_DOLLAR].precision"]
+    );
+    assert_eq!(table.schema.fields[3].precision, Some(3));
+}
+
+fn xlsx_with_worksheet(worksheet: &str) -> Vec<u8> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    let parts = [
+        ("xl/workbook.xml", "<workbook xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\"><sheets><sheet name=\"Fields\" sheetId=\"1\" r:id=\"rId1\"/></sheets></workbook>"),
+        ("xl/_rels/workbook.xml.rels", "<Relationships><Relationship Id=\"rId1\" Target=\"worksheets/sheet1.xml\"/></Relationships>"),
+        ("xl/worksheets/sheet1.xml", worksheet),
+    ];
+    for (name, content) in parts {
+        zip.start_file(name, options).unwrap();
+        zip.write_all(content.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+}
+
+#[test]
+fn xlsx_beyond_worksheet_size() {
+    let xlsx = xlsx_with_worksheet(
+        "<worksheet><sheetData><row r=\"999999999\"><c t=\"inlineStr\"><is><t>key</t></is></c></row></sheetData></worksheet>",
+    );
+    assert_eq!(
+        from_xlsx(&xlsx).unwrap_err().to_string(),
+        "Row 999999999 is beyond the last row of a worksheet (1048576)."
+    );
+    let xlsx = xlsx_with_worksheet(
+        "<worksheet><sheetData><row r=\"1\"><c r=\"ZZZZZZZZZZZZZZ1\"><v>1</v></c></row></sheetData></worksheet>",
+    );
+    assert!(from_xlsx(&xlsx)
+        .unwrap_err()
+        .to_string()
+        .ends_with("is beyond the last column of a worksheet (16384)."));
+    let xlsx = xlsx_with_worksheet(
+        "<worksheet><sheetData><row r=\"1048576\"><c r=\"XFD1048576\"><v>1</v></c></row></sheetData></worksheet>",
+    );
+    let sheets = from_xlsx(&xlsx).unwrap();
+    assert_eq!(sheets[0].rows.len(), 1_048_576);
+    assert_eq!(sheets[0].rows[1_048_575].len(), 16_384);
+}
+
+#[test]
+fn merge_by_key() {
+    let mut table = read_table();
+    let mut sheets = to_sheets(&table);
+    let fields = &mut sheets[0].rows;
+    fields[0].push("title:deu".to_owned());
+    for row in fields.iter_mut().skip(1) {
+        row.push("".to_owned());
+    }
+    // COLOR
+    fields[2][3] = "Colour".to_owned();
+    fields[2][4] = "".to_owned();
+    fields[2][11] = "Farbe".to_owned();
+    fields[2][10] = "".to_owned();
+    let mut weight = [""; 12];
+    weight[0] = "WEIGHT_KG";
+    weight[1] = "PRODUCT";
+    weight[2] = "NUMBER";
+    weight[3] = "Weight";
+    weight[6] = "kg";
+    weight[11] = "Gewicht";
+    fields.push(weight.iter().map(|cell| cell.to_string()).collect());
+    sheets[1].rows.push(vec![
+        "COLOR".to_owned(),
+        "BLUE".to_owned(),
+        "Blue".to_owned(),
+        "".to_owned(),
+        "BLUE".to_owned(),
+        "Bleu".to_owned(),
+        "".to_owned(),
+    ]);
+    // Rows removed from a sheet are kept in the table
+    sheets[2].rows.truncate(1);
+
+    let schema_diff = from_sheets(&mut table, &sheets).unwrap();
+    let changes = schema_diff
+        .changes
+        .iter()
+        .map(|change| (change.kind.clone(), change.path.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        changes,
+        vec![
+            (ChangeKind::Modified, "schema.fields[COLOR].title"),
+            (ChangeKind::Removed, "schema.fields[COLOR].description"),
+            (ChangeKind::Added, "schema.fields[COLOR].titleLocals[deu]"),
+            (ChangeKind::Removed, "schema.fields[COLOR].metadata"),
+            (ChangeKind::Added, "schema.fields[COLOR].options[BLUE]"),
+            (ChangeKind::Added, "schema.fields[WEIGHT_KG]"),
+        ]
+    );
+    let color = &table.schema.fields[1];
+    assert_eq!(color.title_in("deu"), "Farbe");
+    assert_eq!(color.options.as_ref().unwrap()[2].title_in("fra"), "Bleu");
+    let weight = &table.schema.fields[9];
+    assert_eq!(weight.data_type, DataType::Number);
+    assert_eq!(weight.suffix.as_deref(), Some("kg"));
+    assert_eq!(weight.title_in("deu"), "Gewicht");
+    assert_eq!(table.schema.fields[4].suffixes.as_ref().unwrap().len(), 2);
+}
+
+#[test]
+fn invalid_rows() {
+    let mut table = read_table();
+    let sheet = |name: &str, rows: Vec<Vec<&str>>| Sheet {
+        name: name.to_owned(),
+        rows: rows
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect(),
+    };
+    let error = from_sheets(
+        &mut table,
+        &[
+            sheet(FIELDS, vec![vec!["key", "title"], vec!["COLOR", "Colour"]]),
+            sheet(
+                OPTIONS,
+                vec![vec!["field", "key", "title"], vec!["SIZE", "XL", "XL"]],
+            ),
+        ],
+    )
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Sheet 'Options' row 2: unknown field 'SIZE'."
+    );
+    // Nothing is merged when a row is invalid
+    assert_eq!(table, read_table());
+    let error = from_sheets(
+        &mut table,
+        &[sheet(
+            FIELDS,
+            vec![
+                vec!["key", "level", "type", "title"],
+                vec!["SIZE", "PRODUCT", "SIZE", "Size"],
+            ],
+        )],
+    )
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Sheet 'Fields' row 2: unknown type 'SIZE'."
+    );
+    let error = from_sheets(
+        &mut table,
+        &[sheet(
+            FIELDS,
+            vec![vec!["key", "title"], vec!["COLOR", " "]],
+        )],
+    )
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Sheet 'Fields' row 2: column 'title' is empty."
+    );
+}
@@ -0,0 +1,129 @@
+use crate::{
+    table_stats::{CategoryTree, Count, EntityCounts, FormulaStats, StatusRules},
+    table_structs::{Category, Table},
+    table_xml_parser::TableXmlParser,
+};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn read_table() -> Table {
+    TableXmlParser::read("./src/tests/inputs/valid.xml")
+        .unwrap()
+        .table
+}
+
+fn count(key: &str, count: usize) -> Count {
+    Count {
+        key: key.to_owned(),
+        count,
+    }
+}
+
+#[test]
+fn stats() {
+    let table = read_table();
+    let stats = table.stats();
+    assert_eq!(
+        stats.entities,
+        EntityCounts {
+            partitions: 1,
+            levels: 1,
+            identifiers: 1,
+            classifications: 1,
+            categories: 2,
+            fields: 9,
+            options: 2,
+            suffixes: 2,
+            formulas: 3,
+            conditional_formattings: 1,
+            statuses: 2,
+            sections: 2,
+            screens: 1,
+        }
+    );
+    assert_eq!(
+        stats.fields_by_type,
+        vec![
+            count("SINGLE-LINE-TEXT", 1),
+            count("SINGLE-SELECT", 1),
+            count("NUMBER", 4),
+            count("IMAGE", 1),
+            count("LONG-TEXT", 2),
+        ]
+    );
+    assert_eq!(stats.options_per_select_field, vec![count("COLOR", 2)]);
+    assert_eq!(
+        stats.rules_per_status,
+        vec![
+            StatusRules {
+                conditional_formatting: "COMPLIANCE".to_owned(),
+                status: "INVALID".to_owned(),
+                attributes: 3,
+                controls: 7,
+            },
+            StatusRules {
+                conditional_formatting: "COMPLIANCE".to_owned(),
+                status: "RECOMMENDED".to_owned(),
+                attributes: 0,
+                controls: 0,
+            },
+        ]
+    );
+    assert_eq!(
+        stats.controls_by_type,
+        vec![
+            count("Rule-Barcode", 1),
+            count("Rule-Required", 3),
+            count("Rule-Min-Length", 1),
+            count("Rule-Max-Length", 1),
+            count("Rule-Condition", 1),
+        ]
+    );
+    assert_eq!(
+        stats.formulas,
+        FormulaStats {
+            formulas: 3,
+            rules: 4,
+            max_condition_group_size: 3,
+        }
+    );
+    assert_eq!(stats.columns_per_screen, vec![count("ALL_PROPERTIES", 8)]);
+    assert_eq!(stats.locales[0].lang, "fra");
+    assert!(stats.locales[0].translated < stats.locales[0].total);
+}
+
+#[test]
+fn category_trees() {
+    let mut table = read_table();
+    let categories = &mut table.schema.classifications[0].categories;
+    for (key, parent) in [
+        ("SMARTPHONES", Some("PHONES")),
+        ("LANDLINES", Some("PHONES")),
+        ("TOYS", None),
+    ] {
+        categories.push(Category {
+            key: key.into(),
+            parent: parent.map(|parent| parent.into()),
+            ..Category::new()
+        });
+    }
+    assert_eq!(
+        table.stats().category_trees,
+        vec![CategoryTree {
+            classification: "TYPOLOGY".to_owned(),
+            categories: 5,
+            roots: 2,
+            leaves: 3,
+            depth: 3,
+            breadth: 2,
+        }]
+    );
+}
+
+#[test]
+fn text_report() {
+    let text = read_table().stats().to_text();
+    assert!(text.starts_with("Entities\n  partitions "));
+    assert!(text.contains("\nCategory trees\n  TYPOLOGY                  2 categories, 1 roots, 1 leaves, depth 2, breadth 1\n"));
+    assert!(text.contains("\nFormulas\n  formulas                  3\n  rules                     4\n  max condition group size  3\n"));
+}
@@ -0,0 +1,52 @@
+use serde_json::json;
+
+use crate::{
+    table_structs::{CategoryRef, FieldKey, LevelKey, OptionKey, SectionKey, SuffixKey, Table},
+    table_xml_parser::TableXmlParser,
+};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn read_table() -> Table {
+    TableXmlParser::read("./src/tests/inputs/valid.xml")
+        .unwrap()
+        .table
+}
+
+#[test]
+fn resolve_references() {
+    let table = read_table();
+    let schema = &table.schema;
+    let field = schema.field(&"COLOR".into()).unwrap();
+    assert_eq!(schema.level(&field.level).unwrap().key, "PRODUCT");
+    assert_eq!(
+        field.option(&OptionKey::from("GREEN")).unwrap().title,
+        "Green"
+    );
+    let camera_resolution = schema.field(&"CAMERA_RESOLUTION".into()).unwrap();
+    assert!(camera_resolution
+        .suffix(&SuffixKey::from("MEGAPIXEL"))
+        .is_some());
+    let matrix_specific = &schema.matrix.specifics[0];
+    let category = schema.category(&matrix_specific.category_ref()).unwrap();
+    assert_eq!(category.parent.as_ref().unwrap(), "HOME_APPLIANCE");
+    assert!(schema.section(&SectionKey::from("IDENTIFIERS")).is_some());
+    // Categories are only unique inside their classification
+    assert!(schema
+        .category(&CategoryRef::new("COLOR".into(), "PHONES".into()))
+        .is_none());
+    assert!(schema.field(&FieldKey::from("PRODUCT")).is_none());
+    assert!(schema.level(&LevelKey::from("COLOR")).is_none());
+}
+
+#[test]
+fn keys_are_plain_strings() {
+    let key = FieldKey::from("COLOR");
+    assert_eq!(serde_json::to_value(&key).unwrap(), json!("COLOR"));
+    assert_eq!(
+        serde_json::from_value::<FieldKey>(json!("COLOR")).unwrap(),
+        key
+    );
+    assert_eq!(key.to_string(), "COLOR".to_owned());
+    assert_eq!(key, "COLOR");
+}
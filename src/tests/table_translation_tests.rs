@@ -0,0 +1,224 @@
+use crate::{
+    table_locals::Localized,
+    table_structs::Table,
+    table_translation::{
+        completeness, from_po, from_xliff, identical_locals, to_po, to_xliff, IdenticalLocal,
+    },
+    table_xml_parser::TableXmlParser,
+};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn read_table() -> Table {
+    TableXmlParser::read("./src/tests/inputs/valid.xml")
+        .unwrap()
+        .table
+}
+
+#[test]
+fn export_xliff() {
+    let table = read_table();
+    let xliff = to_xliff(&table, "eng", "fra").unwrap();
+    assert!(xliff.contains(
+        "<xliff xmlns=\"urn:oasis:names:tc:xliff:document:2.0\" version=\"2.0\" srcLang=\"eng\" trgLang=\"fra\">"
+    ));
+    assert!(xliff.contains("<unit id=\"u1\" name=\"/Table/Title\">"));
+    assert!(xliff.contains("<source>Products</source>"));
+    assert!(xliff.contains("<target>Produits</target>"));
+}
+
+#[test]
+fn export_po() {
+    let table = read_table();
+    let po = to_po(&table, "fra");
+    assert!(po.contains("\"Language: fra\\n\""));
+    assert!(po.contains(
+        "#: /Table/Title\nmsgctxt \"/Table/Title\"\nmsgid \"Products\"\nmsgstr \"Produits\"\n"
+    ));
+    assert!(po.contains("msgid \"Products' table\"\nmsgstr \"La table produits\"\n"));
+}
+
+#[test]
+fn round_trip() {
+    let table = read_table();
+    let mut imported = read_table();
+    let report = from_xliff(&mut imported, &to_xliff(&table, "eng", "fra").unwrap()).unwrap();
+    assert_eq!(report.lang, "fra");
+    assert!(report.stale.is_empty());
+    assert_eq!(imported, table);
+
+    let report = from_po(&mut imported, &to_po(&table, "fra")).unwrap();
+    assert!(report.stale.is_empty());
+    assert!(report.translated.contains(&"/Table/Title".to_owned()));
+    assert_eq!(imported, table);
+}
+
+#[test]
+fn missing_and_stale() {
+    let mut table = read_table();
+    let po = r#"msgid ""
+msgstr ""
+"Language: deu\n"
+
+msgctxt "/Table/Title"
+msgid "Products"
+msgstr "Produkte"
+
+msgctxt "/Table/Description"
+msgid "Old description"
+msgstr "Alte Beschreibung"
+
+#, fuzzy
+msgctxt "/Table/Schema/Levels/Level[@key='PRODUCT']/Title"
+msgid "Product"
+msgstr "Produkt"
+
+msgctxt "/Table/Schema/Fields/Field[@key='REMOVED']/Title"
+msgid "Removed"
+msgstr "Entfernt"
+"#;
+    let report = from_po(&mut table, po).unwrap();
+    assert_eq!(report.lang, "deu");
+    assert_eq!(report.translated, vec!["/Table/Title"]);
+    assert_eq!(
+        report.stale,
+        vec![
+            "/Table/Description",
+            "/Table/Schema/Fields/Field[@key='REMOVED']/Title"
+        ]
+    );
+    assert!(report.missing.contains(&"/Table/Description".to_owned()));
+    assert!(report
+        .missing
+        .contains(&"/Table/Schema/Levels/Level[@key='PRODUCT']/Title".to_owned()));
+    assert!(!report.missing.contains(&"/Table/Title".to_owned()));
+    assert_eq!(table.title_in("deu"), "Produkte");
+    assert_eq!(table.description_in("deu"), Some("Products' table"));
+}
+
+#[test]
+fn import_crlf_po() {
+    let mut table = read_table();
+    let po = "msgid \"\"\r\nmsgstr \"\"\r\n\"Language: deu\\n\"\r\n\r\nmsgctxt \"/Table/Title\"\r\nmsgid \"Products\"\r\nmsgstr \"Produkte\"\r\n";
+    let report = from_po(&mut table, po).unwrap();
+    assert_eq!(report.lang, "deu");
+    assert_eq!(report.translated, vec!["/Table/Title"]);
+    assert_eq!(table.title_in("deu"), "Produkte");
+}
+
+#[test]
+fn import_po_with_blank_separators() {
+    let mut table = read_table();
+    // Separator lines holding spaces or tabs, and entries without separator
+    let po = [
+        "msgid \"\"\nmsgstr \"\"\n\"Language: deu\\n\"",
+        " \t",
+        "msgctxt \"/Table/Title\"\nmsgid \"Products\"\nmsgstr \"Produkte\"",
+        "\t",
+        "#, fuzzy\nmsgctxt \"/Table/Schema/Levels/Level[@key='PRODUCT']/Title\"\nmsgid \"Product\"\nmsgstr \"Produkt\"",
+        "msgctxt \"/Table/Description\"\nmsgid \"Products' table\"\nmsgstr \"Produkttabelle\"",
+        "",
+    ]
+    .join("\n");
+    let report = from_po(&mut table, &po).unwrap();
+    assert_eq!(report.lang, "deu");
+    assert_eq!(
+        report.translated,
+        vec!["/Table/Title", "/Table/Description"]
+    );
+    assert!(report.stale.is_empty());
+    assert_eq!(table.title_in("deu"), "Produkte");
+    assert_eq!(table.description_in("deu"), Some("Produkttabelle"));
+    assert_eq!(
+        table.schema.levels[0].title_in("deu"),
+        table.schema.levels[0].title
+    );
+}
+
+#[test]
+fn import_plural_po() {
+    let mut table = read_table();
+    let po = r#"msgid ""
+msgstr ""
+"Language: deu\n"
+
+msgctxt "/Table/Title"
+msgid "Product"
+msgid_plural "Products"
+msgstr[0] "Produkt"
+msgstr[1] "Produkte"
+"#;
+    assert_eq!(
+        from_po(&mut table, po).unwrap_err().to_string(),
+        "Plural forms are not supported, 'msgid_plural' in entry 2."
+    );
+    assert_eq!(table, read_table());
+}
+
+#[test]
+fn import_without_lang() {
+    let mut table = read_table();
+    assert!(from_po(
+        &mut table,
+        "msgctxt \"/Table/Title\"\nmsgid \"Products\"\nmsgstr \"Produits\"\n"
+    )
+    .is_err());
+    assert!(from_xliff(&mut table, "<xliff version=\"2.0\"></xliff>").is_err());
+}
+
+#[test]
+fn completeness_per_lang() {
+    let mut table = read_table();
+    table.schema.fields[1].set_title_local("deu", "Farbe");
+    let report = completeness(&table);
+    assert_eq!(
+        report
+            .iter()
+            .map(|lang| lang.lang.as_str())
+            .collect::<Vec<_>>(),
+        vec!["fra", "deu"]
+    );
+    let deu = &report[1];
+    assert_eq!(deu.translated, 1);
+    assert_eq!(deu.missing.len(), deu.total - 1);
+    assert!(!deu
+        .missing
+        .contains(&"/Table/Schema/Fields/Field[@key='COLOR']/Title".to_owned()));
+    assert_eq!(deu.percentage, 100.0 / deu.total as f64);
+    let fra = &report[0];
+    assert_eq!(fra.translated + fra.missing.len(), fra.total);
+    assert!(fra.percentage > deu.percentage);
+}
+
+#[test]
+fn identical_locals_are_reported() {
+    let mut table = read_table();
+    table.schema.fields[1].description_locals.as_mut().unwrap()[0].value =
+        "Product's color".to_owned();
+    assert_eq!(
+        identical_locals(&table),
+        vec![
+            IdenticalLocal {
+                xpath: "/Table/Schema/Identifiers/Identifier[@key='EAN_13']/Title-Local[@lang='fra']".to_owned(),
+                element: "Title".to_owned(),
+                lang: "fra".to_owned(),
+            },
+            IdenticalLocal {
+                xpath: "/Table/Schema/Fields/Field[@key='COLOR']/Description-Local[@lang='fra']".to_owned(),
+                element: "Description".to_owned(),
+                lang: "fra".to_owned(),
+            },
+            IdenticalLocal {
+                xpath: "/Table/Schema/Fields/Field[@key='CAMERA_RESOLUTION']/Suffixes/Suffix[@key='PIXEL']/Title-Local[@lang='fra']".to_owned(),
+                element: "Title".to_owned(),
+                lang: "fra".to_owned(),
+            },
+        ]
+    );
+    // Identical locals are translated texts, listed for a review
+    let fra = &completeness(&table)[0];
+    assert_eq!(fra.identical.len(), 3);
+    assert!(!fra
+        .missing
+        .contains(&"/Table/Schema/Identifiers/Identifier[@key='EAN_13']/Title".to_owned()));
+}
@@ -0,0 +1,97 @@
+use std::{env, fs};
+
+use ts_rs::TS;
+
+use crate::table_structs::{
+    Action, AttributeType, BarcodeType, Category, CategoryKey, Classification, ClassificationKey,
+    Color, Column, ColumnWidth, CommonAttributeRules, CommonColumn, CommonSection, Condition,
+    ConditionGroup, ConditionalFormatting, ConditionalFormattingKey, Control, DataType,
+    DefaultStatus, Field, FieldKey, Formula, GridSpecific, Identifier, IdentifierKey, Level,
+    LevelKey, LineHeight, Local, Matrix, MatrixField, MatrixSpecific, Metadata, OptionKey,
+    OptionRule, Partition, Rounding, Rule, Rules, Schema, Screen, ScreenGrid, Section, SectionKey,
+    SelectOption, SpecificAttributeRules, SpecificRules, SpecificSection, Status, Suffix,
+    SuffixKey, Table, UseSuffix,
+};
+
+const BINDINGS_PATH: &str = "./bindings/table_structs.d.ts";
+
+// TypeScript declarations of the JSON produced by the serde derives of table_structs
+fn typescript_declarations() -> String {
+    let declarations = vec![
+        Table::decl(),
+        Schema::decl(),
+        Partition::decl(),
+        Level::decl(),
+        Identifier::decl(),
+        Classification::decl(),
+        Category::decl(),
+        DataType::decl(),
+        Field::decl(),
+        SelectOption::decl(),
+        Suffix::decl(),
+        Matrix::decl(),
+        MatrixField::decl(),
+        MatrixSpecific::decl(),
+        ConditionalFormatting::decl(),
+        DefaultStatus::decl(),
+        Status::decl(),
+        Rules::decl(),
+        AttributeType::decl(),
+        CommonAttributeRules::decl(),
+        SpecificRules::decl(),
+        SpecificAttributeRules::decl(),
+        Control::decl(),
+        OptionRule::decl(),
+        ConditionGroup::decl(),
+        Condition::decl(),
+        UseSuffix::decl(),
+        Formula::decl(),
+        Rule::decl(),
+        Action::decl(),
+        Section::decl(),
+        Screen::decl(),
+        ScreenGrid::decl(),
+        CommonSection::decl(),
+        CommonColumn::decl(),
+        GridSpecific::decl(),
+        SpecificSection::decl(),
+        Column::<FieldKey>::decl(),
+        Local::decl(),
+        Metadata::decl(),
+        Color::decl(),
+        LineHeight::decl(),
+        ColumnWidth::decl(),
+        Rounding::decl(),
+        BarcodeType::decl(),
+        LevelKey::decl(),
+        IdentifierKey::decl(),
+        ClassificationKey::decl(),
+        CategoryKey::decl(),
+        FieldKey::decl(),
+        OptionKey::decl(),
+        SuffixKey::decl(),
+        ConditionalFormattingKey::decl(),
+        SectionKey::decl(),
+    ];
+    let mut typescript =
+        "// Generated from src/table_structs.rs, run `UPDATE_BINDINGS=1 cargo test` to update.\n"
+            .to_owned();
+    for declaration in declarations.iter() {
+        typescript.push_str(&format!("\nexport {}\n", declaration));
+    }
+    typescript
+}
+
+#[test]
+fn typescript_bindings_are_up_to_date() {
+    let typescript = typescript_declarations();
+    if env::var("UPDATE_BINDINGS").is_ok() {
+        fs::write(BINDINGS_PATH, &typescript).unwrap();
+    }
+    let committed = fs::read_to_string(BINDINGS_PATH).unwrap_or_default();
+    assert!(
+        committed == typescript,
+        "{} is stale, run `UPDATE_BINDINGS=1 cargo test` to update it.",
+        BINDINGS_PATH
+    );
+}
@@ -216,14 +216,14 @@ pub mod fields {
     #[test]
     fn whitespace_in_key() {
         let field = Field {
-            key: "TITLE  ".to_owned(),
+            key: "TITLE  ".into(),
             title: "Title".to_owned(),
             data_type: DataType::SingleLineText,
             description: None,
             title_locals: None,
             description_locals: None,
             metadata: None,
-            level: "PRODUCT".to_owned(),
+            level: "PRODUCT".into(),
             prefix: None,
             suffix: None,
             precision: None,
@@ -247,14 +247,14 @@ pub mod fields {
     #[test]
     fn min_length_key() {
         let field = Field {
-            key: "T".to_owned(),
+            key: "T".into(),
             title: "Title".to_owned(),
             data_type: DataType::SingleLineText,
             description: None,
             title_locals: None,
             description_locals: None,
             metadata: None,
-            level: "PRODUCT".to_owned(),
+            level: "PRODUCT".into(),
             prefix: None,
             suffix: None,
             precision: None,
@@ -278,14 +278,14 @@ pub mod fields {
     #[test]
     fn max_length_key() {
         let field = Field {
-            key: "An extra long key with lorem Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo conseq".to_owned(),
+            key: "An extra long key with lorem Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo conseq".into(),
             title: "Title".to_owned(),
             data_type: DataType::SingleLineText,
             description: None,
             title_locals: None,
             description_locals: None,
             metadata: None,
-            level: "PRODUCT".to_owned(),
+            level: "PRODUCT".into(),
             prefix: None,
             suffix: None,
             precision: None,
@@ -309,14 +309,14 @@ pub mod fields {
     #[test]
     fn min_length_title() {
         let field = Field {
-            key: "Title".to_owned(),
+            key: "Title".into(),
             title: "t".to_owned(),
             data_type: DataType::SingleLineText,
             description: None,
             title_locals: None,
             description_locals: None,
             metadata: None,
-            level: "PRODUCT".to_owned(),
+            level: "PRODUCT".into(),
             prefix: None,
             suffix: None,
             precision: None,
@@ -340,14 +340,14 @@ pub mod fields {
     #[test]
     fn max_length_title() {
         let field = Field {
-            key: "TITLE".to_owned(),
+            key: "TITLE".into(),
             title: "An extra long key with lorem Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo conseq".to_owned(),
             data_type: DataType::SingleLineText,
             description: None,
             title_locals: None,
             description_locals: None,
             metadata: None,
-            level: "PRODUCT".to_owned(),
+            level: "PRODUCT".into(),
             prefix: None,
             suffix: None,
             precision: None,
@@ -372,14 +372,14 @@ pub mod fields {
     fn key_uniqueness() {
         let fields = vec![
             Field {
-                key: "TITLE".to_owned(),
+                key: "TITLE".into(),
                 title: "Title".to_owned(),
                 data_type: DataType::SingleLineText,
                 description: None,
                 title_locals: None,
                 description_locals: None,
                 metadata: None,
-                level: "PRODUCT".to_owned(),
+                level: "PRODUCT".into(),
                 prefix: None,
                 suffix: None,
                 precision: None,
@@ -387,14 +387,14 @@ pub mod fields {
                 options: None,
             },
             Field {
-                key: "TITLE".to_owned(),
+                key: "TITLE".into(),
                 title: "Title".to_owned(),
                 data_type: DataType::SingleLineText,
                 description: None,
                 title_locals: None,
                 description_locals: None,
                 metadata: None,
-                level: "PRODUCT".to_owned(),
+                level: "PRODUCT".into(),
                 prefix: None,
                 suffix: None,
                 precision: None,
@@ -417,3 +417,258 @@ pub mod fields {
         }
     }
 }
+
+pub mod locals {
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        table_structs::Local,
+        table_validation::{LogError, TableValidation},
+        table_xml_parser::TableXmlParser,
+    };
+    #[test]
+    fn invalid_and_duplicate_langs() {
+        let mut table = TableXmlParser::read("./src/tests/inputs/valid.xml")
+            .unwrap()
+            .table;
+        let mut table_validation = TableValidation::new();
+        table_validation.validate_locals(&table);
+        assert_eq!(table_validation.errors.len(), 0);
+        let title_locals = table.schema.fields[1].title_locals.as_mut().unwrap();
+        title_locals.push(Local {
+            lang: "fra".to_owned(),
+            value: "Coloris".to_owned(),
+        });
+        title_locals.push(Local {
+            lang: "fr".to_owned(),
+            value: "Couleur".to_owned(),
+        });
+        table_validation.validate_locals(&table);
+        let errors = table_validation
+            .errors
+            .iter()
+            .map(|error| match error {
+                LogError::Local {
+                    code, xpath, lang, ..
+                } => (code.as_str(), xpath.as_str(), lang.as_str()),
+                _ => ("", "", ""),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![
+                (
+                    "DUPLICATE_LANG",
+                    "/Table/Schema/Fields/Field[@key='COLOR']/Title-Local[@lang='fra']/@lang",
+                    "fra"
+                ),
+                (
+                    "INVALID_LANG",
+                    "/Table/Schema/Fields/Field[@key='COLOR']/Title-Local[@lang='fr']/@lang",
+                    "fr"
+                ),
+            ]
+        );
+    }
+    #[test]
+    fn untranslated_locals() {
+        let mut table = TableXmlParser::read("./src/tests/inputs/valid.xml")
+            .unwrap()
+            .table;
+        table.schema.fields[1].description_locals.as_mut().unwrap()[0].value =
+            "Product's color".to_owned();
+        let mut table_validation = TableValidation::new();
+        table_validation.validate(&table).unwrap();
+        let warnings = table_validation
+            .warnings
+            .iter()
+            .map(|warning| match warning {
+                LogError::Local {
+                    code,
+                    message,
+                    xpath,
+                    ..
+                } => (code.as_str(), message.as_str(), xpath.as_str()),
+                _ => ("", "", ""),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            vec![
+                (
+                    "UNTRANSLATED_LOCAL",
+                    "Title-Local 'fra' is identical to the title.",
+                    "/Table/Schema/Identifiers/Identifier[@key='EAN_13']/Title-Local[@lang='fra']"
+                ),
+                (
+                    "UNTRANSLATED_LOCAL",
+                    "Description-Local 'fra' is identical to the description.",
+                    "/Table/Schema/Fields/Field[@key='COLOR']/Description-Local[@lang='fra']"
+                ),
+                (
+                    "UNTRANSLATED_LOCAL",
+                    "Title-Local 'fra' is identical to the title.",
+                    "/Table/Schema/Fields/Field[@key='CAMERA_RESOLUTION']/Suffixes/Suffix[@key='PIXEL']/Title-Local[@lang='fra']"
+                ),
+            ]
+        );
+        assert!(table_validation
+            .errors
+            .iter()
+            .all(|error| error.code() != "UNTRANSLATED_LOCAL"));
+    }
+}
+pub mod screens {
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        table_structs::{CommonColumn, Table},
+        table_validation::{LogError, TableValidation},
+        table_xml_parser::TableXmlParser,
+    };
+
+    fn read_table() -> Table {
+        TableXmlParser::read("./src/tests/inputs/valid.xml")
+            .unwrap()
+            .table
+    }
+
+    fn errors(table: &Table) -> Vec<(String, String)> {
+        let mut table_validation = TableValidation::new();
+        table_validation.validate_screens(table);
+        table_validation
+            .errors
+            .iter()
+            .map(|error| match error {
+                LogError::Screen { code, xpath, .. } => (code.to_owned(), xpath.to_owned()),
+                _ => (String::new(), String::new()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn valid_table() {
+        let table = read_table();
+        let mut table_validation = TableValidation::new();
+        table_validation.validate(&table).unwrap();
+        assert_eq!(
+            table_validation
+                .errors
+                .iter()
+                .map(|error| error.code())
+                .collect::<Vec<_>>(),
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    fn unknown_references() {
+        let mut table = read_table();
+        table.schema.screens[0].level = "VARIANT".into();
+        table
+            .schema
+            .fields
+            .retain(|field| field.key != "MAIN_IMAGE" && field.key != "INSTRUCTIONS");
+        table
+            .schema
+            .sections
+            .retain(|section| section.key != "INFORMATIONS");
+        let grid = "/Table/Schema/Screens/Screen[@key='ALL_PROPERTIES']/Grid";
+        let common = format!("{}/Common/Section[@key='IDENTIFIERS']", grid);
+        let specific = format!(
+            "{}/Specific[@classification='TYPOLOGY'][@category='HOME_APPLIANCE']/Section[@key='INFORMATIONS']",
+            grid
+        );
+        let unknown_key = |xpath: &str, key: &str| {
+            (
+                "UNKNOWN_KEY".to_owned(),
+                format!("{}/Column-Field[@key='{}']/@key", xpath, key),
+            )
+        };
+        assert_eq!(
+            errors(&table),
+            vec![
+                (
+                    "UNKNOWN_LEVEL".to_owned(),
+                    "/Table/Schema/Screens/Screen[@key='ALL_PROPERTIES']/@level".to_owned()
+                ),
+                unknown_key(&common, "MAIN_IMAGE"),
+                ("UNKNOWN_SECTION".to_owned(), format!("{}/@key", specific)),
+                unknown_key(&specific, "INSTRUCTIONS"),
+            ]
+        );
+    }
+
+    #[test]
+    fn positions_and_fixed_columns() {
+        let mut table = read_table();
+        let columns = &mut table.schema.screens[0].grid.common[0].columns;
+        // MAIN_IMAGE moved to 9, TITLE_EN on the position of TYPOLOGY, PRICE_EURO fixed
+        columns[1].set_position(9);
+        columns[4].set_position(3);
+        if let CommonColumn::ColumnField(column) = &mut columns[6] {
+            column.fixed = Some(true);
+        }
+        let mut table_validation = TableValidation::new();
+        table_validation.validate_screens(&table);
+        let messages = table_validation
+            .errors
+            .iter()
+            .map(|error| (error.code(), error.message()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                (
+                    "DUPLICATE_POSITION",
+                    "Columns 'TYPOLOGY', 'TITLE_EN' have the same position 3."
+                ),
+                (
+                    "POSITION_GAP",
+                    "Column positions are not numbered from 1 without gaps (actual: 1, 3, 4, 6, 7, 9)."
+                ),
+                (
+                    "FIXED_COLUMN_AFTER_NON_FIXED",
+                    "Column-Field 'PRICE_EURO' is fixed but placed after the non-fixed column 'TYPOLOGY'."
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn normalize_positions() {
+        let mut table = read_table();
+        let grid = &mut table.schema.screens[0].grid;
+        grid.common[0].position = 4;
+        let columns = &mut grid.common[0].columns;
+        columns[0].set_position(10);
+        columns[1].set_position(30);
+        columns[2].set_position(20);
+        grid.specifics[0].sections[0].position = 0;
+        table.normalize_positions();
+        let grid = &table.schema.screens[0].grid;
+        assert_eq!(grid.common[0].position, 1);
+        assert_eq!(
+            grid.common[0]
+                .columns
+                .iter()
+                .map(|column| (column.key(), column.position()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("COMPLIANCE", 1),
+                ("TITLE_EN", 2),
+                ("DESCRIPTION_EN", 3),
+                ("PRICE_EURO", 4),
+                ("EAN_13", 5),
+                ("TYPOLOGY", 6),
+                ("MAIN_IMAGE", 7),
+            ]
+        );
+        assert_eq!(grid.specifics[0].sections[0].position, 1);
+        assert!(errors(&table)
+            .iter()
+            .all(|(code, _)| code != "POSITION_GAP" && code != "DUPLICATE_POSITION"));
+    }
+}
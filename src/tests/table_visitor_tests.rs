@@ -0,0 +1,107 @@
+use crate::{
+    table_structs::{Condition, Control, Field, Local, Table},
+    table_visitor::{walk, walk_control_mut, walk_field, walk_mut, Visitor, VisitorMut},
+    table_xml_parser::TableXmlParser,
+};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+struct FieldCollector {
+    xpaths: Vec<String>,
+}
+impl Visitor for FieldCollector {
+    fn visit_field(&mut self, field: &Field, xpath: &str) {
+        self.xpaths.push(xpath.to_owned());
+        walk_field(self, field, xpath);
+    }
+    fn visit_local(&mut self, _local: &Local, xpath: &str) {
+        if xpath.contains("/Options/") {
+            self.xpaths.push(xpath.to_owned());
+        }
+    }
+}
+
+#[test]
+fn visit_fields_and_options() {
+    let table = TableXmlParser::read("./src/tests/inputs/valid.xml").unwrap().table;
+    let mut collector = FieldCollector { xpaths: vec![] };
+    walk(&mut collector, &table);
+    assert_eq!(
+        collector.xpaths,
+        vec![
+            "/Table/Schema/Fields/Field[@key='TITLE_EN']".to_owned(),
+            "/Table/Schema/Fields/Field[@key='COLOR']".to_owned(),
+            "/Table/Schema/Fields/Field[@key='COLOR']/Options/Option[@key='RED']/Title-Local[@lang='fra']".to_owned(),
+            "/Table/Schema/Fields/Field[@key='COLOR']/Options/Option[@key='GREEN']/Title-Local[@lang='fra']".to_owned(),
+            "/Table/Schema/Fields/Field[@key='STORAGE_GB']".to_owned(),
+            "/Table/Schema/Fields/Field[@key='PRICE_DOLLAR']".to_owned(),
+            "/Table/Schema/Fields/Field[@key='CAMERA_RESOLUTION']".to_owned(),
+            "/Table/Schema/Fields/Field[@key='MAIN_IMAGE']".to_owned(),
+            "/Table/Schema/Fields/Field[@key='DESCRIPTION_EN']".to_owned(),
+            "/Table/Schema/Fields/Field[@key='PRICE_EURO']".to_owned(),
+            "/Table/Schema/Fields/Field[@key='INSTRUCTIONS']".to_owned(),
+        ]
+    );
+}
+
+struct ConditionCollector {
+    xpaths: Vec<String>,
+}
+impl Visitor for ConditionCollector {
+    fn visit_condition(&mut self, _condition: &Condition, xpath: &str) {
+        self.xpaths.push(xpath.to_owned());
+    }
+}
+
+#[test]
+fn visit_conditions_of_status_rules_and_formulas() {
+    let table = TableXmlParser::read("./src/tests/inputs/valid.xml").unwrap().table;
+    let mut collector = ConditionCollector { xpaths: vec![] };
+    walk(&mut collector, &table);
+    assert_eq!(collector.xpaths.len(), 10);
+    assert_eq!(
+        collector.xpaths[0],
+        "/Table/Schema/Formulas/Identifier[@key='SUPPLIER-SUPPLIER_REF']/Rule[@priority='1']/Conditions/Condition-Group[1]/Condition[1]"
+    );
+    assert_eq!(
+        collector.xpaths[9],
+        "/Table/Schema/Conditional-Formattings/Conditional-Formatting[@key='COMPLIANCE']/Statuses/Status[@key='INVALID']/Rules/Common/Field[@key='ATTR00480']/Rule-Condition[@key='ATTR00480_NOT_EA']/Conditions/Condition-Group[1]/Condition[3]"
+    );
+}
+
+struct SourceRenamer {}
+impl VisitorMut for SourceRenamer {
+    fn visit_condition_mut(&mut self, condition: &mut Condition, _xpath: &str) {
+        if let Condition::Empty { source } = condition {
+            *source = "RENAMED".to_owned();
+        }
+    }
+    fn visit_control_mut(&mut self, control: &mut Control, xpath: &str) {
+        control.set_title("Renamed".to_owned());
+        walk_control_mut(self, control, xpath);
+    }
+}
+
+#[test]
+fn visit_mut_rewrites_nested_nodes() {
+    let mut table: Table = TableXmlParser::read("./src/tests/inputs/valid.xml").unwrap().table;
+    walk_mut(&mut SourceRenamer {}, &mut table);
+    let status = &table.schema.conditional_formattings[0].statuses[0];
+    match &status.rules.common[2].controls[1] {
+        Control::RuleCondition {
+            key: _,
+            condition_groups,
+            title,
+            title_locals: _,
+        } => {
+            assert_eq!(title.to_owned(), "Renamed".to_owned());
+            assert_eq!(
+                condition_groups[0].conditions[0],
+                Condition::Empty {
+                    source: "RENAMED".to_owned()
+                }
+            );
+        }
+        _ => panic!("expected a Rule-Condition control"),
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     table_structs::{
-        Action, AttributeType, Category, Classification, Column, CommonAttributeRules, CommonColumn, CommonSection, Condition, ConditionGroup, ConditionalFormatting, Control, DataType, DefaultStatus, Field, Formula, GridSpecific, Identifier, Level, Local, Matrix, MatrixField, MatrixSpecific, Metadata, Partition, Rule, Rules, Schema, Screen, ScreenGrid, Section, SelectOption, SpecificSection, Status, Suffix, Table
+        Action, AttributeType, BarcodeType, Category, Classification, Color, Column, ColumnWidth, CommonAttributeRules, CommonColumn, CommonSection, Condition, ConditionGroup, ConditionalFormatting, Control, DataType, DefaultStatus, Field, Formula, GridSpecific, Identifier, Level, LineHeight, Local, Matrix, MatrixField, MatrixSpecific, Metadata, Partition, Rounding, Rule, Rules, Schema, Screen, ScreenGrid, Section, SelectOption, SpecificSection, Status, Suffix, Table
     },
     table_xml_parser::TableXmlParser,
 };
@@ -40,7 +40,7 @@ fn xml_parser() {
         key: "PRODUCTS".to_owned(),
         title: "Products".to_owned(),
         position: 1,
-        color: "RED".to_owned(),
+        color: Color::Red,
         description: Some("Products' table".to_owned()),
         title_locals: Some(vec![Local {
             lang: "fra".to_owned(),
@@ -72,7 +72,7 @@ fn xml_parser() {
                 ]),
             }],
             levels: vec![Level {
-                key: "PRODUCT".to_owned(),
+                key: "PRODUCT".into(),
                 title: "Product".to_owned(),
                 index: 1,
                 description: Some("Product level".to_owned()),
@@ -92,10 +92,10 @@ fn xml_parser() {
                 ]),
             }],
             identifiers: vec![Identifier {
-                key: "EAN_13".to_owned(),
+                key: "EAN_13".into(),
                 title: "EAN 13".to_owned(),
                 index: 1,
-                level: "PRODUCT".to_owned(),
+                level: "PRODUCT".into(),
                 description: Some("EAN13 code".to_owned()),
                 title_locals: Some(vec![
                     Local {
@@ -115,7 +115,7 @@ fn xml_parser() {
                 }]),
             }],
             classifications: vec![Classification {
-                key: "TYPOLOGY".to_owned(),
+                key: "TYPOLOGY".into(),
                 title: "Typology".to_owned(),
                 description: Some("Product typology".to_owned()),
                 title_locals: Some(vec![
@@ -136,7 +136,7 @@ fn xml_parser() {
                 }]),
                 categories: vec![
                     Category {
-                        key: "HOME_APPLIANCE".to_owned(),
+                        key: "HOME_APPLIANCE".into(),
                         parent: None,
                         title: "Home appliance".to_owned(),
                         description: Some("Home appliance category".to_owned()),
@@ -158,8 +158,8 @@ fn xml_parser() {
                         }]),
                     },
                     Category {
-                        key: "PHONES".to_owned(),
-                        parent: Some("HOME_APPLIANCE".to_owned()),
+                        key: "PHONES".into(),
+                        parent: Some("HOME_APPLIANCE".into()),
                         title: "Phones".to_owned(),
                         description: None,
                         title_locals: None,
@@ -170,8 +170,8 @@ fn xml_parser() {
             }],
             fields: vec![
                 Field {
-                    key: "TITLE_EN".to_owned(),
-                    level: "PRODUCT".to_owned(),
+                    key: "TITLE_EN".into(),
+                    level: "PRODUCT".into(),
                     data_type: DataType::SingleLineText,
                     title: "Title EN".to_owned(),
                     description: Some("Product's title in english".to_owned()),
@@ -194,8 +194,8 @@ fn xml_parser() {
                     options: None,
                 },
                 Field {
-                    key: "COLOR".to_owned(),
-                    level: "PRODUCT".to_owned(),
+                    key: "COLOR".into(),
+                    level: "PRODUCT".into(),
                     data_type: DataType::SingleSelect,
                     title: "Color".to_owned(),
                     description: Some("Product's color".to_owned()),
@@ -217,9 +217,9 @@ fn xml_parser() {
                     suffixes: None,
                     options: Some(vec![
                         SelectOption {
-                            key: "RED".to_owned(),
+                            key: "RED".into(),
                             title: "Red".to_owned(),
-                            color: Some("RED".to_owned()),
+                            color: Some(Color::Red),
                             description: None,
                             title_locals: Some(vec![
                                 Local { lang: "fra".to_owned(), value: "Rouge".to_owned()}
@@ -231,9 +231,9 @@ fn xml_parser() {
                             }])
                         },
                         SelectOption {
-                            key: "GREEN".to_owned(),
+                            key: "GREEN".into(),
                             title: "Green".to_owned(),
-                            color: Some("GREEN".to_owned()),
+                            color: Some(Color::Green),
                             description: None,
                             title_locals: Some(vec![
                                 Local { lang: "fra".to_owned(), value: "Vert".to_owned()}
@@ -244,8 +244,8 @@ fn xml_parser() {
                     ]),
                 },
                 Field {
-                    key: "STORAGE_GB".to_owned(),
-                    level: "PRODUCT".to_owned(),
+                    key: "STORAGE_GB".into(),
+                    level: "PRODUCT".into(),
                     data_type: DataType::Number,
                     title: "Storage GB".to_owned(),
                     description: Some("Storage capacity".to_owned()),
@@ -268,8 +268,8 @@ fn xml_parser() {
                     options: None,
                 },
                 Field {
-                    key: "PRICE_DOLLAR".to_owned(),
-                    level: "PRODUCT".to_owned(),
+                    key: "PRICE_DOLLAR".into(),
+                    level: "PRODUCT".into(),
                     data_type: DataType::Number,
                     title: "Price ($)".to_owned(),
                     description: Some("Price in dollar".to_owned()),
@@ -292,8 +292,8 @@ fn xml_parser() {
                     options: None,
                 },
                 Field {
-                    key: "CAMERA_RESOLUTION".to_owned(),
-                    level: "PRODUCT".to_owned(),
+                    key: "CAMERA_RESOLUTION".into(),
+                    level: "PRODUCT".into(),
                     data_type: DataType::Number,
                     title: "Camera resolution".to_owned(),
                     description: Some("The resolution of an image is the number of pixels contained in the image per unit length.".to_owned()),
@@ -305,7 +305,7 @@ fn xml_parser() {
                     precision: None,
                     suffixes: Some(vec![
                         Suffix {
-                            key: "PIXEL".to_owned(),
+                            key: "PIXEL".into(),
                             title: "Pixel".to_owned(),
                             default: Some(true),
                             description: Some("A unit of count defining the number of pixels".to_owned()),
@@ -323,7 +323,7 @@ fn xml_parser() {
                             }])
                         },
                         Suffix {
-                            key: "MEGAPIXEL".to_owned(),
+                            key: "MEGAPIXEL".into(),
                             title: "Megapixel".to_owned(),
                             default: None,
                             description: Some("A unit of count equal to 10⁶ (1000000) pixels (picture elements).".to_owned()),
@@ -334,6 +334,90 @@ fn xml_parser() {
                     ]),
                     options: None,
                 },
+                Field {
+                    key: "MAIN_IMAGE".into(),
+                    level: "PRODUCT".into(),
+                    data_type: DataType::Image,
+                    title: "Main image".to_owned(),
+                    description: Some("Product's main image".to_owned()),
+                    title_locals: Some(vec![Local {
+                        lang: "fra".to_owned(),
+                        value: "Image principale".to_owned(),
+                    }]),
+                    description_locals: Some(vec![Local {
+                        lang: "fra".to_owned(),
+                        value: "L'image principale du produit".to_owned(),
+                    }]),
+                    metadata: None,
+                    prefix: None,
+                    suffix: None,
+                    precision: None,
+                    suffixes: None,
+                    options: None,
+                },
+                Field {
+                    key: "DESCRIPTION_EN".into(),
+                    level: "PRODUCT".into(),
+                    data_type: DataType::LongText,
+                    title: "Description EN".to_owned(),
+                    description: Some("Product's description in english".to_owned()),
+                    title_locals: Some(vec![Local {
+                        lang: "fra".to_owned(),
+                        value: "Descriptif EN".to_owned(),
+                    }]),
+                    description_locals: Some(vec![Local {
+                        lang: "fra".to_owned(),
+                        value: "La description du produit en anglais".to_owned(),
+                    }]),
+                    metadata: None,
+                    prefix: None,
+                    suffix: None,
+                    precision: None,
+                    suffixes: None,
+                    options: None,
+                },
+                Field {
+                    key: "PRICE_EURO".into(),
+                    level: "PRODUCT".into(),
+                    data_type: DataType::Number,
+                    title: "Price (€)".to_owned(),
+                    description: Some("Price in euro".to_owned()),
+                    title_locals: Some(vec![Local {
+                        lang: "fra".to_owned(),
+                        value: "Prix (€)".to_owned(),
+                    }]),
+                    description_locals: Some(vec![Local {
+                        lang: "fra".to_owned(),
+                        value: "Le prix en euro".to_owned(),
+                    }]),
+                    metadata: None,
+                    prefix: None,
+                    suffix: Some("€".to_owned()),
+                    precision: Some(2),
+                    suffixes: None,
+                    options: None,
+                },
+                Field {
+                    key: "INSTRUCTIONS".into(),
+                    level: "PRODUCT".into(),
+                    data_type: DataType::LongText,
+                    title: "Instructions".to_owned(),
+                    description: Some("Instructions for use".to_owned()),
+                    title_locals: Some(vec![Local {
+                        lang: "fra".to_owned(),
+                        value: "Mode d'emploi".to_owned(),
+                    }]),
+                    description_locals: Some(vec![Local {
+                        lang: "fra".to_owned(),
+                        value: "Les instructions d'utilisation".to_owned(),
+                    }]),
+                    metadata: None,
+                    prefix: None,
+                    suffix: None,
+                    precision: None,
+                    suffixes: None,
+                    options: None,
+                },
             ],
             formulas: vec![
                 Formula {
@@ -373,7 +457,7 @@ fn xml_parser() {
                                     ]
                                 }
                             ],
-                            action: Action::SetNumberTemplate { precision: 0, round: "CEILING".to_owned(), value: "{{source(\"WIDTH_CM\")}} * {{source(\"HEIGHT_CM\")}} * {{source(\"DEPTH_CM\")}}".to_owned() }
+                            action: Action::SetNumberTemplate { precision: 0, round: Rounding::Ceiling, value: "{{source(\"WIDTH_CM\")}} * {{source(\"HEIGHT_CM\")}} * {{source(\"DEPTH_CM\")}}".to_owned() }
                         }
                     ]
                 },
@@ -429,23 +513,23 @@ fn xml_parser() {
             ],
             matrix: Matrix {
                 common: vec![
-                    MatrixField { key: "TITLE_EN".to_owned() },
-                    MatrixField { key: "COLOR".to_owned() },
+                    MatrixField { key: "TITLE_EN".into() },
+                    MatrixField { key: "COLOR".into() },
                 ],
                 specifics: vec![
                     MatrixSpecific {
-                        classification: "TYPOLOGY".to_owned(),
-                        category: "PHONES".to_owned(),
+                        classification: "TYPOLOGY".into(),
+                        category: "PHONES".into(),
                         fields: vec![
-                            MatrixField { key: "STORAGE".to_owned() }
+                            MatrixField { key: "STORAGE".into() }
                         ]
                     }
                 ],
             },
             conditional_formattings: vec![
                 ConditionalFormatting {
-                    key: "COMPLIANCE".to_owned(),
-                    level: "PRODUCT".to_owned(),
+                    key: "COMPLIANCE".into(),
+                    level: "PRODUCT".into(),
                     title: "Compliance".to_owned(),
                     description: None,
                     title_locals: Some(vec![
@@ -456,7 +540,7 @@ fn xml_parser() {
                     default_status: DefaultStatus {
                         key: "VALID".to_owned(),
                         title: "Valid".to_owned(),
-                        color: "GREEN".to_owned(),
+                        color: Color::Green,
                         description: None,
                         title_locals: Some(vec![
                             Local { lang: "fra".to_owned(), value: "Valide".to_owned()}
@@ -468,7 +552,7 @@ fn xml_parser() {
                         Status {
                             key: "INVALID".to_owned(),
                             title: "Invalid".to_owned(),
-                            color: "RED".to_owned(),
+                            color: Color::Red,
                             priority: 1,
                             rules: Rules {
                                 common: vec![
@@ -477,7 +561,7 @@ fn xml_parser() {
                                         key: "EAN_13".to_owned(),
                                         controls: vec![
                                             Control::RuleBarcode {
-                                                barcode_type: "EAN13".to_owned()
+                                                barcode_type: BarcodeType::Ean13
                                             },
                                             Control::RuleRequired
                                         ]
@@ -541,7 +625,7 @@ fn xml_parser() {
                         Status {
                             key: "RECOMMENDED".to_owned(),
                             title: "Recommended".to_owned(),
-                            color: "ORANGE".to_owned(),
+                            color: Color::Orange,
                             priority: 2,
                             description: None,
                             title_locals: None,
@@ -557,7 +641,7 @@ fn xml_parser() {
             ],
             sections: vec![
                 Section {
-                    key: "IDENTIFIERS".to_owned(),
+                    key: "IDENTIFIERS".into(),
                     title: "Identifiers".to_owned(),
                     description: Some("Identifiers section".to_owned()),
                     title_locals: Some(vec![
@@ -572,12 +656,24 @@ fn xml_parser() {
                             value: "ERP".to_owned()
                         }
                     ])
+                },
+                Section {
+                    key: "INFORMATIONS".into(),
+                    title: "Informations".to_owned(),
+                    description: Some("Informations section".to_owned()),
+                    title_locals: Some(vec![
+                        Local { lang: "fra".to_owned(), value: "Renseignements".to_owned() }
+                    ]),
+                    description_locals: Some(vec![
+                        Local { lang: "fra".to_owned(), value: "La section des informations".to_owned() }
+                    ]),
+                    metadata: None
                 }
             ],
             screens: vec![
                 Screen {
                     key: "ALL_PROPERTIES".to_owned(),
-                    level: "PRODUCT".to_owned(),
+                    level: "PRODUCT".into(),
                     title: "All properties".to_owned(),
                     position: 1,
                     description: Some("All properties screen".to_owned()),
@@ -594,75 +690,75 @@ fn xml_parser() {
                         }
                     ]),
                     grid: ScreenGrid {
-                        line_height: "SHORT".to_owned(),
+                        line_height: LineHeight::Short,
                         common: vec![
                             CommonSection {
-                                key: "IDENTIFIERS".to_owned(),
+                                key: "IDENTIFIERS".into(),
                                 position: 1,
                                 columns: vec![
                                     CommonColumn::ColumnIdentifier(Column {
-                                        key: "EAN_13".to_owned(),
+                                        key: "EAN_13".into(),
                                         position: 1,
                                         fixed: Some(true),
                                         read_only: Some(true),
                                         width: None
                                     }), 
                                     CommonColumn::ColumnField(Column {
-                                        key: "MAIN_IMAGE".to_owned(),
+                                        key: "MAIN_IMAGE".into(),
                                         position: 2,
                                         fixed: None,
                                         read_only: None,
                                         width: None
                                     }), 
                                     CommonColumn::ColumnClassification(Column {
-                                        key: "TYPOLOGY".to_owned(),
+                                        key: "TYPOLOGY".into(),
                                         position: 3,
                                         fixed: None,
                                         read_only: None,
                                         width: None
                                     }), 
                                     CommonColumn::ColumnConditionalFormatting(Column {
-                                        key: "COMPLIANCE".to_owned(),
+                                        key: "COMPLIANCE".into(),
                                         position: 4,
                                         fixed: None,
                                         read_only: None,
                                         width: None
                                     }), 
                                     CommonColumn::ColumnField(Column {
-                                        key: "TITLE_EN".to_owned(),
+                                        key: "TITLE_EN".into(),
                                         position: 5,
                                         fixed: None,
                                         read_only: None,
                                         width: None
                                     }), 
                                     CommonColumn::ColumnField(Column {
-                                        key: "DESCRIPTION_EN".to_owned(),
+                                        key: "DESCRIPTION_EN".into(),
                                         position: 6,
                                         fixed: None,
                                         read_only: None,
                                         width: None
                                     }), 
                                     CommonColumn::ColumnField(Column {
-                                        key: "PRICE_EURO".to_owned(),
+                                        key: "PRICE_EURO".into(),
                                         position: 7,
                                         fixed: None,
                                         read_only: None,
-                                        width: Some("SMALL".to_owned())
+                                        width: Some(ColumnWidth::Small)
                                     }), 
                                 ]
                             }
                         ],
                         specifics: vec![
                             GridSpecific {
-                                classification: "TYPOLOGY".to_owned(),
-                                category: "HOME_APPLIANCE".to_owned(),
+                                classification: "TYPOLOGY".into(),
+                                category: "HOME_APPLIANCE".into(),
                                 sections: vec![
                                     SpecificSection {
-                                        key: "INFORMATIONS".to_owned(),
+                                        key: "INFORMATIONS".into(),
                                         position: 1,
                                         columns: vec![
                                             Column {
-                                                key: "INSTRUCTIONS".to_owned(),
+                                                key: "INSTRUCTIONS".into(),
                                                 position: 1,
                                                 width: None,
                                                 fixed: None,
@@ -686,3 +782,56 @@ fn xml_parser() {
     // println!("{:#?}", table_xml_parser.table.description.unwrap());
     assert_eq!(table_xml_parser.table, expected);
 }
+
+#[test]
+fn read_from_str() {
+    let xml = std::fs::read_to_string("./src/tests/inputs/valid.xml").unwrap();
+    let table_xml_parser = TableXmlParser::read_from_str(&xml).unwrap();
+    assert_eq!(
+        table_xml_parser.table,
+        TableXmlParser::read("./src/tests/inputs/valid.xml")
+            .unwrap()
+            .table
+    );
+}
+
+#[test]
+fn unknown_color() {
+    let xml = std::fs::read_to_string("./src/tests/inputs/valid.xml")
+        .unwrap()
+        .replacen("<Color>RED</Color>", "<Color>GREN</Color>", 1);
+    let table_xml_parser = TableXmlParser::read_from_str(&xml).unwrap();
+    assert_eq!(
+        table_xml_parser.table.color,
+        Color::Unknown("GREN".to_owned())
+    );
+    let warning = table_xml_parser
+        .warnings
+        .iter()
+        .find(|warning| warning.code == "UNKNOWN_COLOR")
+        .unwrap();
+    assert_eq!(warning.message, "Unknown color 'GREN' in Table.".to_owned());
+    // Unknown values are kept as they are when the table is written back
+    let written =
+        crate::table_xml_writer::TableXmlWriter::write_to_string(&table_xml_parser.table).unwrap();
+    assert!(written.contains("<Color>GREN</Color>"));
+}
+
+#[test]
+fn empty_column_width() {
+    let xml = std::fs::read_to_string("./src/tests/inputs/valid.xml")
+        .unwrap()
+        .replacen(
+            "<Column-Field key=\"TITLE_EN\" position=\"5\"/>",
+            "<Column-Field key=\"TITLE_EN\" position=\"5\" width=\"\"/>",
+            1,
+        );
+    let table_xml_parser = TableXmlParser::read_from_str(&xml).unwrap();
+    assert!(table_xml_parser.warnings.is_empty());
+    assert_eq!(
+        table_xml_parser.table,
+        TableXmlParser::read("./src/tests/inputs/valid.xml")
+            .unwrap()
+            .table
+    );
+}
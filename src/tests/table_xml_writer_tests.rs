@@ -18,3 +18,11 @@ fn write_file() {
         Err(_) => assert!(false),
     }
 }
+
+#[test]
+fn write_string() {
+    let table_xml_parser = TableXmlParser::read("./src/tests/inputs/valid.xml").unwrap();
+    let xml = TableXmlWriter::write_to_string(&table_xml_parser.table).unwrap();
+    assert!(xml.starts_with("<Table key=\"PRODUCTS\"><Title>Products</Title>"));
+    assert!(xml.ends_with("</Table>"));
+}
@@ -0,0 +1,313 @@
+use std::collections::{HashMap, HashSet};
+
+use quick_xml::{events::Event, Reader};
+
+use crate::{
+    table_structs::{
+        BarcodeType, Color, ColumnWidth, Control, DataType, FieldKey, LineHeight, OptionRule,
+        UseSuffix,
+    },
+    table_xml_parser::TableXmlParser,
+    table_xml_writer::TableXmlWriter,
+};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+const XSD: &str = include_str!("../../schemas/table.xsd");
+
+// Minimal tree of the xsd, enough to find declared elements and their attributes
+struct Node {
+    name: String,
+    attributes: HashMap<String, String>,
+    children: Vec<Node>,
+}
+
+fn read_node(
+    name: String,
+    attributes: HashMap<String, String>,
+    reader: &mut Reader<&[u8]>,
+    empty: bool,
+) -> Node {
+    let mut node = Node {
+        name,
+        attributes,
+        children: vec![],
+    };
+    if empty {
+        return node;
+    }
+    loop {
+        match reader.read_event().unwrap() {
+            Event::Start(ev) => {
+                let (name, attributes) = element(&ev);
+                node.children
+                    .push(read_node(name, attributes, reader, false));
+            }
+            Event::Empty(ev) => {
+                let (name, attributes) = element(&ev);
+                node.children
+                    .push(read_node(name, attributes, reader, true));
+            }
+            Event::End(_) | Event::Eof => break,
+            _ => (),
+        }
+    }
+    node
+}
+
+fn element(ev: &quick_xml::events::BytesStart) -> (String, HashMap<String, String>) {
+    let attributes = ev
+        .attributes()
+        .map(|attribute| {
+            let attribute = attribute.unwrap();
+            (
+                String::from_utf8(attribute.key.as_ref().to_vec()).unwrap(),
+                attribute.unescape_value().unwrap().to_string(),
+            )
+        })
+        .collect();
+    (
+        String::from_utf8(ev.name().as_ref().to_vec()).unwrap(),
+        attributes,
+    )
+}
+
+fn read_xsd() -> Node {
+    let mut reader = Reader::from_str(XSD);
+    read_node("document".to_owned(), HashMap::new(), &mut reader, false)
+}
+
+fn named<'a>(node: &'a Node, tag: &str, types: &mut HashMap<String, &'a Node>) {
+    if node.name == tag {
+        if let Some(name) = node.attributes.get("name") {
+            types.insert(name.to_owned(), node);
+        }
+    }
+    for child in node.children.iter() {
+        named(child, tag, types);
+    }
+}
+
+// Attributes of a type, including the ones of its base type
+fn type_attributes(node: &Node, types: &HashMap<String, &Node>, attributes: &mut HashSet<String>) {
+    for child in node.children.iter() {
+        match child.name.as_str() {
+            "xs:element" => (),
+            "xs:attribute" => {
+                attributes.insert(child.attributes["name"].to_owned());
+            }
+            "xs:extension" => {
+                if let Some(base) = types.get(&child.attributes["base"]) {
+                    type_attributes(base, types, attributes);
+                }
+                type_attributes(child, types, attributes);
+            }
+            _ => type_attributes(child, types, attributes),
+        }
+    }
+}
+
+// Attributes allowed for each element name, in any context
+fn declared_elements(xsd: &Node) -> HashMap<String, HashSet<String>> {
+    let mut types = HashMap::new();
+    named(xsd, "xs:complexType", &mut types);
+    let mut declared: HashMap<String, HashSet<String>> = HashMap::new();
+    fn collect(
+        node: &Node,
+        types: &HashMap<String, &Node>,
+        declared: &mut HashMap<String, HashSet<String>>,
+    ) {
+        if node.name == "xs:element" {
+            let mut attributes = HashSet::new();
+            match node.attributes.get("type").and_then(|name| types.get(name)) {
+                Some(element_type) => type_attributes(element_type, types, &mut attributes),
+                None => type_attributes(node, types, &mut attributes),
+            }
+            declared
+                .entry(node.attributes["name"].to_owned())
+                .or_default()
+                .extend(attributes);
+        }
+        for child in node.children.iter() {
+            collect(child, types, declared);
+        }
+    }
+    collect(xsd, &types, &mut declared);
+    declared
+}
+
+fn enumeration(xsd: &Node, name: &str) -> Vec<String> {
+    let mut simple_types = HashMap::new();
+    named(xsd, "xs:simpleType", &mut simple_types);
+    simple_types[name].children[0]
+        .children
+        .iter()
+        .map(|value| value.attributes["value"].to_owned())
+        .collect()
+}
+
+#[test]
+fn xsd_describes_written_xml() {
+    let mut table = TableXmlParser::read("./src/tests/inputs/valid.xml")
+        .unwrap()
+        .table;
+    let field = FieldKey::from("COLOR");
+    let value = "1".to_owned();
+    table.schema.conditional_formattings[0].statuses[0]
+        .rules
+        .common[1]
+        .controls = vec![
+        Control::RuleRequired,
+        Control::RuleIsLeaf,
+        Control::RuleMinLength { min: 1 },
+        Control::RuleMaxLength { max: 1 },
+        Control::RuleRegex {
+            regex: ".*".to_owned(),
+        },
+        Control::RuleBarcode {
+            barcode_type: BarcodeType::Ean13,
+        },
+        Control::RuleLessThan { value: 1 },
+        Control::RuleGreaterThan { value: 1 },
+        Control::RuleLessThanOrEqual { value: 1 },
+        Control::RuleGreaterThanOrEqual { value: 1 },
+        Control::RuleDecimalPlaces { precision: 1 },
+        Control::RuleMinWidthPx { min: 1 },
+        Control::RuleMaxWidthPx { max: 1 },
+        Control::RuleMinHeightPx { min: 1 },
+        Control::RuleMaxHeightPx { max: 1 },
+        Control::RuleMaxSizeKb { max: 1 },
+        Control::RuleExtension {
+            extension: "png".to_owned(),
+        },
+        Control::RuleColorSpace {
+            name: "RGB".to_owned(),
+        },
+        Control::RuleColorProfile {
+            name: "sRGB".to_owned(),
+        },
+        Control::RuleMinValues { min: 1 },
+        Control::RuleMaxValues { max: 1 },
+        Control::RuleMustBeGreaterThanAnotherField {
+            field: field.to_owned(),
+        },
+        Control::RuleMustBeGreaterThanOrEqualAnotherField {
+            field: field.to_owned(),
+        },
+        Control::RuleMustBeLessThanAnotherField {
+            field: field.to_owned(),
+        },
+        Control::RuleMustBeLessThanOrEqualAnotherField {
+            field: field.to_owned(),
+        },
+        Control::RuleRequiredIfAnotherFieldIsNotEmpty {
+            field: field.to_owned(),
+        },
+        Control::RuleRequiredIfAnotherFieldHasOptions {
+            field: field.to_owned(),
+            options: vec![OptionRule { key: "RED".into() }],
+        },
+        Control::RuleRequiredIfAnotherFieldIsGreaterThan {
+            field: field.to_owned(),
+            value: value.to_owned(),
+        },
+        Control::RuleRequiredIfAnotherFieldIsGreaterThanOrEqual {
+            field: field.to_owned(),
+            value: value.to_owned(),
+        },
+        Control::RuleRequiredIfAnotherFieldIsLessThan {
+            field: field.to_owned(),
+            value: value.to_owned(),
+        },
+        Control::RuleRequiredIfAnotherFieldIsLessThanOrEqual {
+            field: field.to_owned(),
+            value: value.to_owned(),
+        },
+        Control::RuleRequiredIfAnotherFieldIsEqualTo {
+            field: field.to_owned(),
+            value: value.to_owned(),
+        },
+    ];
+    let declared = declared_elements(&read_xsd());
+    let xml = TableXmlWriter::write_to_string(&table).unwrap();
+    let mut reader = Reader::from_str(&xml);
+    let mut undeclared = vec![];
+    loop {
+        match reader.read_event().unwrap() {
+            Event::Start(ev) | Event::Empty(ev) => {
+                let (name, attributes) = element(&ev);
+                match declared.get(&name) {
+                    Some(allowed) => {
+                        for attribute in attributes.keys() {
+                            if !allowed.contains(attribute) {
+                                undeclared.push(format!("{}/@{}", name, attribute));
+                            }
+                        }
+                    }
+                    None => undeclared.push(name),
+                }
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    undeclared.sort();
+    undeclared.dedup();
+    assert_eq!(undeclared, Vec::<String>::new());
+}
+
+#[test]
+fn xsd_enumerations() {
+    let xsd = read_xsd();
+    let data_types = [
+        DataType::SingleLineText,
+        DataType::LongText,
+        DataType::HtmlText,
+        DataType::Number,
+        DataType::SingleSelect,
+        DataType::MultipleSelect,
+        DataType::MultipleSelectQuantified,
+        DataType::MultipleSelectQuantifiedWithComments,
+        DataType::Date,
+        DataType::DateTime,
+        DataType::Image,
+        DataType::Attachment,
+    ];
+    assert_eq!(
+        enumeration(&xsd, "DataTypeEnum"),
+        data_types
+            .iter()
+            .map(|data_type| data_type.to_string())
+            .collect::<Vec<String>>()
+    );
+    let uses = [UseSuffix::Value, UseSuffix::SuffixKey, UseSuffix::Suffix];
+    assert_eq!(
+        enumeration(&xsd, "UseEnum"),
+        uses.iter()
+            .map(|use_suffix| use_suffix.to_string())
+            .collect::<Vec<String>>()
+    );
+    assert!(enumeration(&xsd, "RoundEnum").contains(&"CEILING".to_owned()));
+    // Known values of the open enums are all declared
+    for color in enumeration(&xsd, "ColorEnum") {
+        assert!(!matches!(Color::from(color.as_str()), Color::Unknown(_)));
+    }
+    for line_height in enumeration(&xsd, "LineHeightEnum") {
+        assert!(!matches!(
+            LineHeight::from(line_height.as_str()),
+            LineHeight::Unknown(_)
+        ));
+    }
+    for width in enumeration(&xsd, "ColumnWidthEnum") {
+        assert!(!matches!(
+            ColumnWidth::from(width.as_str()),
+            ColumnWidth::Unknown(_)
+        ));
+    }
+    for barcode_type in enumeration(&xsd, "BarcodeTypeEnum") {
+        assert!(!matches!(
+            BarcodeType::from(barcode_type.as_str()),
+            BarcodeType::Unknown(_)
+        ));
+    }
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{prelude::*, JsCast};
+
+use crate::{
+    table_diagnostics::{parse, validate},
+    table_structs::Table,
+    table_xml_writer::TableXmlWriter,
+};
+
+// Typings of table_structs generated by the table_typescript_tests
+#[wasm_bindgen(typescript_custom_section)]
+const TABLE_STRUCTS: &str = include_str!("../bindings/table_structs.d.ts");
+
+#[wasm_bindgen(typescript_custom_section)]
+const DIAGNOSTICS: &str = r#"
+export type Diagnostic = { code: string, message: string, xpath: string | null, };
+
+export type ParseResult = { table: Table, warnings: Array<Diagnostic>, errors: Array<Diagnostic>, };
+
+export type ValidationResult = { warnings: Array<Diagnostic>, errors: Array<Diagnostic>, };
+"#;
+
+// Types of the values exchanged with JS
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Table")]
+    pub type JsTable;
+    #[wasm_bindgen(typescript_type = "ParseResult")]
+    pub type JsParseResult;
+    #[wasm_bindgen(typescript_type = "ValidationResult")]
+    pub type JsValidationResult;
+}
+
+// Values are converted like serde_json does (None as null), so that JS objects
+// have the same shape as the JSON produced by the backend.
+fn to_js<T: Serialize, J: JsCast>(value: &T) -> Result<J, JsError> {
+    let js_value = value.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?;
+    Ok(js_value.unchecked_into::<J>())
+}
+
+fn from_js<T: for<'a> Deserialize<'a>>(value: JsValue) -> Result<T, JsError> {
+    Ok(serde_wasm_bindgen::from_value(value)?)
+}
+
+#[wasm_bindgen(js_name = parseTable)]
+pub fn parse_table(xml: &str) -> Result<JsParseResult, JsError> {
+    to_js(&parse(xml).map_err(|error| JsError::new(&error.to_string()))?)
+}
+
+#[wasm_bindgen(js_name = writeTable)]
+pub fn write_table(table: JsTable) -> Result<String, JsError> {
+    let table: Table = from_js(table.into())?;
+    Ok(TableXmlWriter::write_to_string(&table)?)
+}
+
+#[wasm_bindgen(js_name = validateTable)]
+pub fn validate_table(table: JsTable) -> Result<JsValidationResult, JsError> {
+    let table: Table = from_js(table.into())?;
+    to_js(&validate(&table).map_err(|error| JsError::new(&error.to_string()))?)
+}
@@ -0,0 +1,168 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+use table_schema_core::{table_xml_parser::TableXmlParser, table_xml_writer::TableXmlWriter};
+
+const VALID: &str = "./src/tests/inputs/valid.xml";
+const WRONG_FIRST_ELEMENT: &str = "./src/tests/inputs/WRONG_FIRST_ELEMENT.xml";
+
+fn table_schema(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_table-schema"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn code(output: &Output) -> i32 {
+    output.status.code().unwrap()
+}
+
+// Empty directory of a test in the temporary directory
+fn directory(test: &str) -> PathBuf {
+    let directory = env::temp_dir().join(format!("table-schema-cli-{}", test));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+fn path(directory: &Path, name: &str) -> String {
+    directory.join(name).to_string_lossy().to_string()
+}
+
+// valid.xml without the MAIN_IMAGE field, which its screen references
+fn write_invalid_table(path: &str) {
+    let mut table = TableXmlParser::read(VALID).unwrap().table;
+    table
+        .schema
+        .fields
+        .retain(|field| field.key != "MAIN_IMAGE");
+    fs::write(path, TableXmlWriter::write_to_string(&table).unwrap()).unwrap();
+}
+
+#[test]
+fn validate() {
+    let directory = directory("validate");
+    let output = table_schema(&["validate", VALID]);
+    assert_eq!(code(&output), 0);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("{}: valid\n", VALID)
+    );
+    let invalid = path(&directory, "invalid.xml");
+    write_invalid_table(&invalid);
+    let output = table_schema(&["validate", &invalid]);
+    assert_eq!(code(&output), 1);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("error[UNKNOWN_KEY]"));
+    assert_eq!(code(&table_schema(&["validate", WRONG_FIRST_ELEMENT])), 1);
+    assert_eq!(
+        code(&table_schema(&[
+            "validate",
+            &path(&directory, "missing.xml")
+        ])),
+        2
+    );
+    assert_eq!(code(&table_schema(&["validate"])), 2);
+    assert_eq!(code(&table_schema(&["validate", VALID, "--json"])), 2);
+}
+
+#[test]
+fn fmt_check() {
+    let directory = directory("fmt");
+    let table = path(&directory, "table.xml");
+    fs::copy(VALID, &table).unwrap();
+    let original = fs::read_to_string(&table).unwrap();
+    let output = table_schema(&["fmt", &table, "--check"]);
+    assert_eq!(code(&output), 1);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("{}: not formatted\n", table)
+    );
+    assert_eq!(fs::read_to_string(&table).unwrap(), original);
+    // A mistyped flag is refused, the file is not rewritten
+    let output = table_schema(&["fmt", &table, "--chek"]);
+    assert_eq!(code(&output), 2);
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .starts_with("error: unknown option '--chek' for fmt.\n\nUsage: table-schema"));
+    assert_eq!(fs::read_to_string(&table).unwrap(), original);
+    assert_eq!(code(&table_schema(&["fmt", &table])), 0);
+    assert_ne!(fs::read_to_string(&table).unwrap(), original);
+    assert_eq!(code(&table_schema(&["fmt", &table, "--check"])), 0);
+}
+
+#[test]
+fn fmt_suppressions() {
+    let directory = directory("fmt-suppressions");
+    let table = path(&directory, "table.xml");
+    let unsuppressed = fs::read_to_string(VALID).unwrap().replacen(
+        "<Column-Field key=\"INSTRUCTIONS\"",
+        "<Column-Field key=\"MANUAL\"",
+        1,
+    );
+    fs::write(&table, &unsuppressed).unwrap();
+    assert_eq!(code(&table_schema(&["lint", &table])), 1);
+    let xml = unsuppressed.replacen(
+        "<Screen key=\"ALL_PROPERTIES\"",
+        "<!-- lint-disable UNKNOWN_KEY -->\n<Screen key=\"ALL_PROPERTIES\"",
+        1,
+    );
+    fs::write(&table, &xml).unwrap();
+    assert_eq!(code(&table_schema(&["lint", &table])), 0);
+    // Rewriting the file would drop the comment, it is refused and the file is unchanged
+    let output = table_schema(&["fmt", &table]);
+    assert_eq!(code(&output), 2);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        format!(
+            "error: {} has lint-disable comments, they would be lost by rewriting the file.\n",
+            table
+        )
+    );
+    assert_eq!(code(&table_schema(&["fmt", &table, "--check"])), 2);
+    assert_eq!(code(&table_schema(&["screens", &table, &table])), 2);
+    assert_eq!(fs::read_to_string(&table).unwrap(), xml);
+    assert_eq!(code(&table_schema(&["lint", &table])), 0);
+}
+
+#[test]
+fn convert() {
+    let directory = directory("convert");
+    let json = path(&directory, "table.json");
+    let xml = path(&directory, "table.xml");
+    assert_eq!(code(&table_schema(&["convert", VALID, &json])), 0);
+    assert_eq!(code(&table_schema(&["convert", &json, &xml])), 0);
+    assert_eq!(
+        TableXmlParser::read(&xml).unwrap().table,
+        TableXmlParser::read(VALID).unwrap().table
+    );
+    let wrong = path(&directory, "wrong.json");
+    assert_eq!(
+        code(&table_schema(&["convert", WRONG_FIRST_ELEMENT, &wrong])),
+        1
+    );
+    assert!(fs::metadata(&wrong).is_err());
+    assert_eq!(code(&table_schema(&["convert", VALID])), 2);
+    assert_eq!(code(&table_schema(&["convert", VALID, &json, "--xml"])), 2);
+}
+
+#[test]
+fn diff() {
+    let directory = directory("diff");
+    let changed = path(&directory, "changed.xml");
+    let mut table = TableXmlParser::read(VALID).unwrap().table;
+    table.schema.fields[0].title = "English title".to_owned();
+    fs::write(&changed, TableXmlWriter::write_to_string(&table).unwrap()).unwrap();
+    let output = table_schema(&["diff", VALID, VALID]);
+    assert_eq!(code(&output), 0);
+    assert!(output.stdout.is_empty());
+    let output = table_schema(&["diff", VALID, &changed]);
+    assert_eq!(code(&output), 1);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("TITLE_EN"));
+    let output = table_schema(&["diff", VALID, &changed, "--json"]);
+    assert_eq!(code(&output), 1);
+    assert!(serde_json::from_slice::<serde_json::Value>(&output.stdout).is_ok());
+    assert_eq!(code(&table_schema(&["diff", VALID, &changed, "--text"])), 2);
+    assert_eq!(code(&table_schema(&["diff", VALID])), 2);
+}